@@ -2,7 +2,7 @@
 //!
 //! Cache structure:
 //! ```text
-//! ~/.local/share/draftkit/
+//! ~/.local/share/draftkit/            # or profiles/{name}/
 //! └── cache/
 //!     └── components/
 //!         └── {component-id}/
@@ -13,19 +13,30 @@
 //! ```
 //!
 //! Files are stored per-component, per-variant to enable incremental caching
-//! as users request different components.
+//! as users request different components. Each [profile](crate::profile) has
+//! its own cache.
 
+use std::fmt;
 use std::fs;
+use std::io;
+use std::time::{Duration, SystemTime};
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 
 use crate::components::{Framework, Mode};
 use crate::data_dir::data_dir;
+use crate::profile::{active_profile, profile_dir_in};
 
-/// Get the cache directory path.
+/// Get the cache directory path of the active profile.
 #[must_use]
 pub fn cache_dir() -> Option<Utf8PathBuf> {
-    data_dir().map(|d| d.join("cache"))
+    data_dir().map(|dir| cache_dir_in(dir, active_profile()))
+}
+
+/// Cache directory of `profile` within the data directory `data_dir`.
+#[must_use]
+pub fn cache_dir_in(data_dir: &Utf8Path, profile: &str) -> Utf8PathBuf {
+    profile_dir_in(data_dir, profile).join("cache")
 }
 
 /// Get the path for a cached component file.
@@ -37,14 +48,26 @@ pub fn component_cache_path(
     framework: Framework,
     mode: Mode,
 ) -> Option<Utf8PathBuf> {
-    let dir = cache_dir()?;
+    Some(variant_path(&cache_dir()?, component_id, framework, mode))
+}
+
+/// Path of a component variant under the cache directory `cache_dir`.
+fn variant_path(
+    cache_dir: &Utf8Path,
+    component_id: &str,
+    framework: Framework,
+    mode: Mode,
+) -> Utf8PathBuf {
     let filename = format!(
         "{}-v4-{}.{}",
         framework.as_str(),
         mode.as_str(),
         framework.file_extension()
     );
-    Some(dir.join("components").join(component_id).join(filename))
+    cache_dir
+        .join("components")
+        .join(component_id)
+        .join(filename)
 }
 
 /// Check if a component variant is cached.
@@ -58,8 +81,18 @@ pub fn is_cached(component_id: &str, framework: Framework, mode: Mode) -> bool {
 /// Get cached component code if it exists.
 #[must_use]
 pub fn get_cached(component_id: &str, framework: Framework, mode: Mode) -> Option<String> {
-    let path = component_cache_path(component_id, framework, mode)?;
-    fs::read_to_string(path.as_std_path()).ok()
+    get_cached_in(&cache_dir()?, component_id, framework, mode)
+}
+
+/// Get cached component code from the cache directory `cache_dir`.
+#[must_use]
+pub fn get_cached_in(
+    cache_dir: &Utf8Path,
+    component_id: &str,
+    framework: Framework,
+    mode: Mode,
+) -> Option<String> {
+    fs::read_to_string(variant_path(cache_dir, component_id, framework, mode)).ok()
 }
 
 /// Store component code in the cache.
@@ -75,13 +108,28 @@ pub fn store_cached(
     mode: Mode,
     code: &str,
 ) -> std::io::Result<Utf8PathBuf> {
-    let path = component_cache_path(component_id, framework, mode).ok_or_else(|| {
+    let dir = cache_dir().ok_or_else(|| {
         std::io::Error::new(
             std::io::ErrorKind::NotFound,
             "Cannot determine cache directory",
         )
     })?;
+    store_cached_in(&dir, component_id, framework, mode, code)
+}
 
+/// Store component code in the cache directory `cache_dir`.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be created or the file cannot be written.
+pub fn store_cached_in(
+    cache_dir: &Utf8Path,
+    component_id: &str,
+    framework: Framework,
+    mode: Mode,
+    code: &str,
+) -> std::io::Result<Utf8PathBuf> {
+    let path = variant_path(cache_dir, component_id, framework, mode);
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent.as_std_path())?;
     }
@@ -169,6 +217,306 @@ pub fn cache_path_string() -> Option<String> {
     cache_dir().map(|p| p.to_string())
 }
 
+/// Directory holding one subdirectory per cached component.
+fn components_dir() -> Option<Utf8PathBuf> {
+    cache_dir().map(|d| d.join("components"))
+}
+
+/// A cached variant of a component.
+#[derive(Debug, Clone)]
+pub struct CachedVariant {
+    /// Framework of the code
+    pub framework: Framework,
+    /// Theme mode of the code
+    pub mode: Mode,
+    /// Path of the cached file
+    pub path: Utf8PathBuf,
+    /// Size in bytes
+    pub bytes: u64,
+    /// When the file was written, if the platform reports it
+    pub modified: Option<SystemTime>,
+}
+
+/// A component with cached code.
+#[derive(Debug, Clone)]
+pub struct CachedComponent {
+    /// Component UUID, as used for the cache directory
+    pub id: String,
+    /// Cached variants, ordered by file name
+    pub variants: Vec<CachedVariant>,
+}
+
+impl CachedComponent {
+    /// Total size of the cached variants in bytes.
+    #[must_use]
+    pub fn total_bytes(&self) -> u64 {
+        self.variants.iter().map(|v| v.bytes).sum()
+    }
+}
+
+/// Parse a cached file name (`{framework}-v4-{mode}.{ext}`).
+fn parse_variant_file(name: &str) -> Option<(Framework, Mode)> {
+    let (stem, extension) = name.rsplit_once('.')?;
+    let mut parts = stem.splitn(3, '-');
+    let framework = Framework::parse(parts.next()?)?;
+    if parts.next()? != "v4" {
+        return None;
+    }
+    let mode = Mode::parse(parts.next()?)?;
+    (extension == framework.file_extension()).then_some((framework, mode))
+}
+
+fn read_component(dir: &Utf8Path, id: &str) -> CachedComponent {
+    let mut variants: Vec<CachedVariant> = dir
+        .read_dir_utf8()
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|file| {
+            let (framework, mode) = parse_variant_file(file.file_name())?;
+            let meta = file.metadata().ok().filter(fs::Metadata::is_file)?;
+            Some(CachedVariant {
+                framework,
+                mode,
+                path: file.into_path(),
+                bytes: meta.len(),
+                modified: meta.modified().ok(),
+            })
+        })
+        .collect();
+    variants.sort_by(|a, b| a.path.cmp(&b.path));
+    CachedComponent {
+        id: id.to_string(),
+        variants,
+    }
+}
+
+fn list_in(components_dir: &Utf8Path) -> Vec<CachedComponent> {
+    let mut components: Vec<CachedComponent> = components_dir
+        .read_dir_utf8()
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|entry| read_component(entry.path(), entry.file_name()))
+        .collect();
+    components.sort_by(|a, b| a.id.cmp(&b.id));
+    components
+}
+
+/// List cached components, ordered by ID.
+#[must_use]
+pub fn list_cached() -> Vec<CachedComponent> {
+    components_dir().map_or_else(Vec::new, |dir| list_in(&dir))
+}
+
+/// Get the cached variants of one component.
+///
+/// Returns `None` if nothing is cached for `component_id`.
+#[must_use]
+pub fn cached_component(component_id: &str) -> Option<CachedComponent> {
+    if component_id.is_empty() || component_id.contains(['/', '\\']) || component_id == ".." {
+        return None;
+    }
+    let dir = components_dir()?.join(component_id);
+    dir.is_dir().then(|| read_component(&dir, component_id))
+}
+
+/// Files removed by a prune.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PruneStats {
+    /// Number of files removed
+    pub file_count: usize,
+    /// Total size removed in bytes
+    pub total_bytes: u64,
+    /// Number of entries left in place because they couldn't be read or removed
+    pub skipped: usize,
+}
+
+/// Remove files last written before `cutoff` from a two-level cache
+/// directory (`{dir}/{component-id}/{file}`), then any emptied component
+/// directories.
+///
+/// Entries that can't be read or removed are skipped and counted, so one
+/// bad entry doesn't stop the rest being pruned.
+pub(crate) fn prune_dir(dir: &Utf8Path, cutoff: SystemTime) -> PruneStats {
+    let mut stats = PruneStats::default();
+    let Ok(entries) = dir.read_dir_utf8() else {
+        return stats;
+    };
+    for entry in entries {
+        let Ok(entry) = entry else {
+            stats.skipped += 1;
+            continue;
+        };
+        if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+            continue;
+        }
+        let Ok(files) = entry.path().read_dir_utf8() else {
+            stats.skipped += 1;
+            continue;
+        };
+        let mut remaining = 0;
+        for file in files {
+            let Some((file, meta)) = file
+                .ok()
+                .and_then(|file| file.metadata().ok().map(|meta| (file, meta)))
+            else {
+                stats.skipped += 1;
+                remaining += 1;
+                continue;
+            };
+            let stale = meta.is_file() && meta.modified().is_ok_and(|time| time < cutoff);
+            if !stale {
+                remaining += 1;
+            } else if fs::remove_file(file.path()).is_ok() {
+                stats.file_count += 1;
+                stats.total_bytes += meta.len();
+            } else {
+                stats.skipped += 1;
+                remaining += 1;
+            }
+        }
+        if remaining == 0 && fs::remove_dir(entry.path()).is_err() {
+            stats.skipped += 1;
+        }
+    }
+    stats
+}
+
+/// Remove cached component files older than `max_age`.
+///
+/// Entries that can't be read or removed are counted in
+/// [`PruneStats::skipped`].
+#[must_use]
+pub fn prune_older_than(max_age: Duration) -> PruneStats {
+    let Some(dir) = components_dir() else {
+        return PruneStats::default();
+    };
+    let cutoff = SystemTime::now()
+        .checked_sub(max_age)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    prune_dir(&dir, cutoff)
+}
+
+/// What is wrong with a cache entry found by [`verify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheProblemKind {
+    /// A file whose name is not a known framework and mode
+    UnknownFile,
+    /// A component file with no content
+    EmptyFile,
+    /// A component file that cannot be read as UTF-8 text
+    Unreadable,
+    /// A preview file that is not a PNG image
+    NotAnImage,
+    /// A component directory with no files
+    EmptyDirectory,
+}
+
+impl fmt::Display for CacheProblemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownFile => write!(f, "unrecognized file"),
+            Self::EmptyFile => write!(f, "empty file"),
+            Self::Unreadable => write!(f, "unreadable or not UTF-8"),
+            Self::NotAnImage => write!(f, "not a PNG image"),
+            Self::EmptyDirectory => write!(f, "empty component directory"),
+        }
+    }
+}
+
+/// A damaged or stray cache entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheProblem {
+    /// Path of the entry
+    pub path: Utf8PathBuf,
+    /// What is wrong with it
+    pub kind: CacheProblemKind,
+}
+
+impl CacheProblem {
+    /// Delete the entry, so it is fetched again when next needed, along
+    /// with its component directory if that is left empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be removed.
+    pub fn remove(&self) -> io::Result<()> {
+        if self.path.is_dir() {
+            return fs::remove_dir_all(&self.path);
+        }
+        fs::remove_file(&self.path)?;
+        if let Some(parent) = self.path.parent() {
+            // Fails while other variants remain
+            let _ = fs::remove_dir(parent);
+        }
+        Ok(())
+    }
+}
+
+/// Check every file of a two-level cache directory
+/// (`{dir}/{component-id}/{file}`) with `check`, which gets the file name
+/// and path. Stray files and empty component directories are problems too.
+pub(crate) fn verify_dir(
+    dir: &Utf8Path,
+    check: impl Fn(&str, &Utf8Path) -> Option<CacheProblemKind>,
+) -> Vec<CacheProblem> {
+    let mut problems = Vec::new();
+    let mut report = |path: &Utf8Path, kind| {
+        problems.push(CacheProblem {
+            path: path.to_path_buf(),
+            kind,
+        });
+    };
+    let Ok(entries) = dir.read_dir_utf8() else {
+        return problems;
+    };
+    for entry in entries.flatten() {
+        if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+            report(entry.path(), CacheProblemKind::UnknownFile);
+            continue;
+        }
+        let mut files = 0;
+        for file in entry.path().read_dir_utf8().into_iter().flatten().flatten() {
+            files += 1;
+            let path = file.path();
+            let problem = if path.is_dir() {
+                Some(CacheProblemKind::UnknownFile)
+            } else {
+                check(file.file_name(), path)
+            };
+            if let Some(kind) = problem {
+                report(path, kind);
+            }
+        }
+        if files == 0 {
+            report(entry.path(), CacheProblemKind::EmptyDirectory);
+        }
+    }
+    problems.sort_by(|a, b| a.path.cmp(&b.path));
+    problems
+}
+
+fn verify_in(components_dir: &Utf8Path) -> Vec<CacheProblem> {
+    verify_dir(components_dir, |name, path| {
+        if parse_variant_file(name).is_none() {
+            return Some(CacheProblemKind::UnknownFile);
+        }
+        match fs::read_to_string(path) {
+            Ok(code) if code.trim().is_empty() => Some(CacheProblemKind::EmptyFile),
+            Ok(_) => None,
+            Err(_) => Some(CacheProblemKind::Unreadable),
+        }
+    })
+}
+
+/// Check every cached component file, returning the damaged or stray ones.
+#[must_use]
+pub fn verify() -> Vec<CacheProblem> {
+    components_dir().map_or_else(Vec::new, |dir| verify_in(&dir))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -220,6 +568,120 @@ mod tests {
         let _ = get_stats();
     }
 
+    /// A components directory with `files` written under `{id}/{name}`.
+    fn components_fixture(files: &[(&str, &str, &[u8])]) -> (tempfile::TempDir, Utf8PathBuf) {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = Utf8PathBuf::try_from(tmp.path().to_path_buf()).unwrap();
+        for (id, name, content) in files {
+            fs::create_dir_all(dir.join(id)).unwrap();
+            fs::write(dir.join(id).join(name), content).unwrap();
+        }
+        (tmp, dir)
+    }
+
+    #[test]
+    fn test_parse_variant_file() {
+        assert_eq!(
+            parse_variant_file("react-v4-dark.jsx"),
+            Some((Framework::React, Mode::Dark))
+        );
+        assert_eq!(
+            parse_variant_file("html-v4-light.html"),
+            Some((Framework::Html, Mode::Light))
+        );
+        assert_eq!(parse_variant_file("react-v4-dark.vue"), None);
+        assert_eq!(parse_variant_file("notes.txt"), None);
+    }
+
+    #[test]
+    fn test_list_in() {
+        let (_tmp, dir) = components_fixture(&[
+            ("b", "vue-v4-light.vue", b"<template />"),
+            ("a", "react-v4-light.jsx", b"export default 1"),
+            ("a", "react-v4-dark.jsx", b"export default 2"),
+        ]);
+        let components = list_in(&dir);
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].id, "a");
+        assert_eq!(components[0].variants.len(), 2);
+        assert_eq!(components[0].variants[0].mode, Mode::Dark);
+        assert_eq!(components[0].total_bytes(), 32);
+        assert_eq!(components[1].variants[0].framework, Framework::Vue);
+    }
+
+    #[test]
+    fn test_prune_dir() {
+        let (_tmp, dir) = components_fixture(&[
+            ("a", "react-v4-light.jsx", b"one"),
+            ("b", "vue-v4-light.vue", b"three"),
+        ]);
+
+        let stats = prune_dir(&dir, SystemTime::UNIX_EPOCH);
+        assert_eq!(stats, PruneStats::default());
+
+        let future = SystemTime::now() + Duration::from_secs(60);
+        let stats = prune_dir(&dir, future);
+        assert_eq!(stats.file_count, 2);
+        assert_eq!(stats.total_bytes, 8);
+        assert!(list_in(&dir).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_prune_dir_skips_unreadable_entries() {
+        let (_tmp, dir) = components_fixture(&[
+            ("a", "react-v4-light.jsx", b"one"),
+            ("b", "vue-v4-light.vue", b"three"),
+        ]);
+        // Not UTF-8, so it can't be listed
+        let name = <std::ffi::OsStr as std::os::unix::ffi::OsStrExt>::from_bytes(b"\xff.jsx");
+        fs::write(dir.join("a").as_std_path().join(name), "two").unwrap();
+
+        let future = SystemTime::now() + Duration::from_secs(60);
+        let stats = prune_dir(&dir, future);
+        assert_eq!(stats.file_count, 2);
+        assert_eq!(stats.skipped, 1);
+        assert!(dir.join("a").is_dir());
+        assert!(!dir.join("b").exists());
+    }
+
+    #[test]
+    fn test_verify_in() {
+        let (_tmp, dir) = components_fixture(&[
+            ("good", "react-v4-light.jsx", b"export default 1"),
+            ("empty", "react-v4-light.jsx", b"  \n"),
+            ("binary", "html-v4-dark.html", &[0xff, 0xfe]),
+            ("stray", "notes.txt", b"hi"),
+        ]);
+        fs::create_dir(dir.join("hollow")).unwrap();
+
+        let problems = verify_in(&dir);
+        let kinds: Vec<(&str, CacheProblemKind)> = problems
+            .iter()
+            .map(|p| (p.path.strip_prefix(&dir).unwrap().as_str(), p.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("binary/html-v4-dark.html", CacheProblemKind::Unreadable),
+                ("empty/react-v4-light.jsx", CacheProblemKind::EmptyFile),
+                ("hollow", CacheProblemKind::EmptyDirectory),
+                ("stray/notes.txt", CacheProblemKind::UnknownFile),
+            ]
+        );
+
+        for problem in &problems {
+            problem.remove().unwrap();
+        }
+        assert!(verify_in(&dir).is_empty());
+    }
+
+    #[test]
+    fn test_cached_component_rejects_paths() {
+        assert!(cached_component("../escape").is_none());
+        assert!(cached_component("..").is_none());
+    }
+
     #[test]
     fn test_cache_path_string() {
         if let Some(path) = cache_path_string() {
@@ -130,6 +130,18 @@ impl Mode {
             Self::None => "none",
         }
     }
+
+    /// Parse mode from string
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "light" => Some(Self::Light),
+            "dark" => Some(Self::Dark),
+            "system" => Some(Self::System),
+            "none" => Some(Self::None),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for Mode {
@@ -283,7 +295,7 @@ pub struct TokenInfo {
 }
 
 /// Tailwind CSS version compatibility information.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct TailwindCompatibility {
     /// v4-only features used (e.g., `data-closed`, `size-`, `inset-ring`)
@@ -298,6 +310,16 @@ const fn default_true() -> bool {
     true
 }
 
+// Matches the field-level serde default: unanalyzed components are assumed compatible.
+impl Default for TailwindCompatibility {
+    fn default() -> Self {
+        Self {
+            v4_only: Vec::new(),
+            v3_compatible: true,
+        }
+    }
+}
+
 // ============================================================================
 // Component Intelligence (for pattern matching and coherence checking)
 // ============================================================================
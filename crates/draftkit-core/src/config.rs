@@ -34,9 +34,11 @@
 use camino::{Utf8Path, Utf8PathBuf};
 use config::{ConfigBuilder, File, FileFormat, builder::DefaultState};
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use crate::error::{ConfigError, ConfigResult};
+use crate::scaffold::IconOptions;
 
 /// The configuration for draftkit.
 ///
@@ -51,6 +53,21 @@ pub struct Config {
     pub log_dir: Option<Utf8PathBuf>,
     /// OpenTelemetry OTLP endpoint; when set, enables trace export.
     pub otel_endpoint: Option<String>,
+    /// Icon library for generated pages, with per-icon overrides.
+    pub icons: IconOptions,
+    /// Profile to use when neither `--profile` nor `DRAFTKIT_PROFILE` is set.
+    pub profile: Option<String>,
+    /// Per-profile settings, keyed by profile name.
+    pub profiles: BTreeMap<String, ProfileConfig>,
+}
+
+/// Settings for one [profile](crate::profile).
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ProfileConfig {
+    /// TailwindPlus account the profile's licence belongs to, shown instead
+    /// of the manifest's `downloaded_by`.
+    pub licensed_to: Option<String>,
 }
 
 /// Log level configuration.
@@ -301,6 +318,65 @@ mod tests {
         assert_eq!(config.log_level, LogLevel::Info);
         assert!(config.log_dir.is_none());
         assert!(config.otel_endpoint.is_none());
+        assert_eq!(config.icons, IconOptions::default());
+    }
+
+    #[test]
+    fn test_icon_settings() {
+        let tmp = TempDir::new().unwrap();
+        let config_path = tmp.path().join("config.toml");
+        fs::write(
+            &config_path,
+            r#"[icons]
+library = "lucide"
+
+[icons.mappings]
+Bars3Icon = "AlignJustifyIcon"
+"#,
+        )
+        .unwrap();
+
+        let config = ConfigLoader::new()
+            .with_user_config(false)
+            .with_file(&config_path)
+            .load()
+            .unwrap();
+
+        assert_eq!(
+            config.icons.library,
+            Some(crate::scaffold::IconLibrary::Lucide)
+        );
+        assert_eq!(
+            config.icons.mappings.get("Bars3Icon").map(String::as_str),
+            Some("AlignJustifyIcon")
+        );
+    }
+
+    #[test]
+    fn test_profile_settings() {
+        let tmp = TempDir::new().unwrap();
+        let config_path = tmp.path().join("config.toml");
+        fs::write(
+            &config_path,
+            r#"profile = "client-a"
+
+[profiles.client-a]
+licensed_to = "design@client-a.example"
+"#,
+        )
+        .unwrap();
+
+        let config = ConfigLoader::new()
+            .with_user_config(false)
+            .with_file(&config_path)
+            .load()
+            .unwrap();
+
+        assert_eq!(config.profile.as_deref(), Some("client-a"));
+        assert_eq!(
+            config.profiles["client-a"].licensed_to.as_deref(),
+            Some("design@client-a.example")
+        );
     }
 
     #[test]
@@ -5,6 +5,8 @@
 //! - macOS: `~/Library/Application Support/draftkit/`
 //! - Windows: `%APPDATA%/draftkit/`
 //!
+//! Set `DRAFTKIT_DATA_DIR` to use another directory, e.g. for tests.
+//!
 //! The runtime directory structure mirrors the embedded cache:
 //! ```text
 //! ~/.local/share/draftkit/
@@ -32,16 +34,28 @@ use std::sync::OnceLock;
 /// App name for directory creation
 const APP_NAME: &str = "draftkit";
 
+/// Environment variable overriding the data directory
+pub const DATA_DIR_ENV: &str = "DRAFTKIT_DATA_DIR";
+
 /// Cached data directory path
 static DATA_DIR: OnceLock<Option<Utf8PathBuf>> = OnceLock::new();
 
 /// Get the platform-appropriate data directory for draftkit.
 ///
 /// Returns `None` if the data directory cannot be determined (e.g., no home directory).
+/// The path is resolved once per process.
 #[must_use]
 pub fn data_dir() -> Option<&'static Utf8Path> {
     DATA_DIR
-        .get_or_init(|| dirs::data_dir().and_then(|p| Utf8PathBuf::try_from(p.join(APP_NAME)).ok()))
+        .get_or_init(|| {
+            std::env::var(DATA_DIR_ENV)
+                .ok()
+                .filter(|dir| !dir.is_empty())
+                .map(Utf8PathBuf::from)
+                .or_else(|| {
+                    dirs::data_dir().and_then(|p| Utf8PathBuf::try_from(p.join(APP_NAME)).ok())
+                })
+        })
         .as_deref()
 }
 
@@ -56,8 +70,15 @@ pub fn has_runtime_data() -> bool {
 }
 
 /// Get the path to the runtime manifest file, if it exists.
+///
+/// A manifest in the active profile's directory takes precedence.
 #[must_use]
 pub fn runtime_manifest_path() -> Option<Utf8PathBuf> {
+    if let Some(path) = crate::profile::active_profile_dir().map(|d| d.join("manifest.json"))
+        && path.is_file()
+    {
+        return Some(path);
+    }
     let dir = data_dir()?;
     let path = dir.join("manifest.json");
     if path.is_file() { Some(path) } else { None }
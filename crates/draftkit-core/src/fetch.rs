@@ -2,21 +2,57 @@
 //!
 //! Fetches component code using authenticated session cookies.
 //! TailwindPlus uses Inertia.js, so we need specific headers to get JSON responses.
+//!
+//! Pages return each component in the framework, Tailwind version and theme
+//! mode the account last picked in the web UI. When a page comes back in
+//! other variants, the fetcher changes those preferences the way the UI's
+//! switchers do and fetches the page again.
+//!
+//! Every snippet on a fetched subcategory page is written to the disk cache,
+//! and parsed pages are memoized in the fetcher for [`DEFAULT_MEMO_TTL`], so
+//! several components from one page cost a single request. Concurrent
+//! requests for the same page wait for the first one instead of repeating it.
+//!
+//! Requests that fail transiently (rate limiting, 5xx responses, timeouts)
+//! are retried with exponential backoff and jitter per [`RetryPolicy`]. When
+//! TailwindPlus deploys a new build, Inertia answers 409; the fetcher then
+//! re-reads the version from the site and repeats the request.
+//!
+//! Requests go to [`DEFAULT_BASE_URL`] through a [`reqwest::Client`] unless
+//! another base URL or [`HttpClient`] is configured, e.g. to run against a
+//! local stand-in server.
 
 use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::time::{Duration, Instant};
 
-use reqwest::header::{HeaderMap, HeaderValue};
+use futures::lock::Mutex as AsyncMutex;
+
+use camino::Utf8PathBuf;
+use reqwest::header::{CONTENT_TYPE, COOKIE, HeaderMap, HeaderValue, RETRY_AFTER};
+use reqwest::{Method, Request, Response, StatusCode, Url};
 use serde::Deserialize;
+use serde_json::json;
 
 use crate::cache;
-use crate::components::{Framework, Mode};
+use crate::components::{Framework, Mode, TailwindVersion};
+use crate::http::{self, HttpClient, SharedHttpClient};
+use crate::slug::slugify;
 
 /// Base URL for TailwindPlus
-const BASE_URL: &str = "https://tailwindcss.com/plus";
+pub const DEFAULT_BASE_URL: &str = "https://tailwindcss.com/plus";
+
+/// How long a fetched subcategory page is reused before refetching
+pub const DEFAULT_MEMO_TTL: Duration = Duration::from_secs(10 * 60);
 
 /// User agent to use for requests
 const USER_AGENT: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/143.0.0.0 Safari/537.36";
 
+/// HTTP status Laravel uses for an expired CSRF token
+const STATUS_PAGE_EXPIRED: u16 = 419;
+
 /// Error type for fetch operations
 #[derive(Debug, thiserror::Error)]
 pub enum FetchError {
@@ -29,6 +65,15 @@ pub enum FetchError {
     #[error("Component not found: {0}")]
     ComponentNotFound(String),
 
+    #[error("Rate limited by TailwindPlus{}", retry_hint(*retry_after))]
+    RateLimited {
+        /// Wait requested by the server's `Retry-After` header
+        retry_after: Option<Duration>,
+    },
+
+    #[error("TailwindPlus was updated while fetching. Try again.")]
+    InertiaVersionChanged,
+
     #[error("HTTP error: {0}")]
     Http(#[from] reqwest::Error),
 
@@ -39,6 +84,78 @@ pub enum FetchError {
     Cache(#[from] std::io::Error),
 }
 
+/// " (retry after 90s)" for a rate limit that says how long to wait
+fn retry_hint(retry_after: Option<Duration>) -> String {
+    retry_after.map_or_else(String::new, |wait| {
+        format!(" (retry after {}s)", wait.as_secs())
+    })
+}
+
+impl FetchError {
+    /// Whether repeating the request may succeed.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RateLimited { .. } => true,
+            Self::Http(e) => {
+                e.is_timeout() || e.is_connect() || e.status().is_some_and(|s| s.is_server_error())
+            }
+            _ => false,
+        }
+    }
+}
+
+/// How failed requests to TailwindPlus are retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt
+    pub max_retries: u32,
+    /// Backoff before the first retry, doubled for each further one
+    pub base_delay: Duration,
+    /// Longest wait between attempts; a longer `Retry-After` fails the request
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Fail on the first error.
+    #[must_use]
+    pub const fn none() -> Self {
+        Self {
+            max_retries: 0,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        }
+    }
+
+    /// Wait before retry number `attempt` (starting at 0): exponential
+    /// backoff capped at `max_delay`, randomized down to half to spread out
+    /// concurrent clients.
+    #[must_use]
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        delay.mul_f64(0.5f64.mul_add(jitter(), 0.5))
+    }
+}
+
+/// Random fraction in `0.0..=1.0`
+fn jitter() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
 /// Inertia.js response structure (simplified)
 #[derive(Debug, Deserialize)]
 struct InertiaResponse {
@@ -58,7 +175,6 @@ struct SubcategoryData {
 #[derive(Debug, Deserialize)]
 struct ComponentData {
     uuid: String,
-    #[allow(dead_code)]
     name: String,
     snippet: SnippetData,
 }
@@ -67,17 +183,193 @@ struct ComponentData {
 #[serde(rename_all = "camelCase")]
 struct SnippetData {
     code: String,
-    #[allow(dead_code)]
     language: String,
-    #[allow(dead_code)]
     version: String,
     mode: String,
-    #[allow(dead_code)]
     supports_dark_mode: bool,
     #[allow(dead_code)]
     preview: Option<String>,
 }
 
+/// A component snippet returned by a subcategory page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchedSnippet {
+    /// Component UUID
+    pub uuid: String,
+    /// Component name
+    pub name: String,
+    /// Framework the snippet is written for
+    pub framework: Framework,
+    /// Theme mode of the snippet
+    pub mode: Mode,
+    /// Component source code
+    pub code: String,
+}
+
+/// Framework for a snippet's `language`, or `fallback` when unrecognized
+fn snippet_framework(language: &str, fallback: Framework) -> Framework {
+    match language.to_lowercase().as_str() {
+        "jsx" | "tsx" | "js" | "javascript" | "react" => Framework::React,
+        "vue" => Framework::Vue,
+        "html" => Framework::Html,
+        _ => fallback,
+    }
+}
+
+/// A snippet preference stored on the TailwindPlus account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Preference {
+    Language(Framework),
+    Version(TailwindVersion),
+    Mode(Mode),
+}
+
+impl Preference {
+    /// Endpoint the web UI's switcher sends this preference to
+    fn url(self, base_url: &str) -> String {
+        let path = match self {
+            Self::Language(_) => "language",
+            Self::Version(_) => "version",
+            Self::Mode(_) => "mode",
+        };
+        format!("{base_url}/ui-blocks/{path}")
+    }
+
+    /// Request body, naming the component the switcher was used on
+    fn body(self, uuid: &str) -> serde_json::Value {
+        match self {
+            Self::Language(framework) => {
+                json!({ "uuid": uuid, "snippet_lang": framework.as_str() })
+            }
+            Self::Version(version) => json!({ "uuid": uuid, "version": version.as_u8() }),
+            Self::Mode(mode) => json!({ "uuid": uuid, "mode": mode.as_str() }),
+        }
+    }
+}
+
+/// Preferences to change so a page returns the requested variants.
+///
+/// Mode is only negotiated for snippets that have a dark variant, and
+/// never for mode-less components.
+fn preference_changes(
+    components: &[ComponentData],
+    framework: Framework,
+    version: TailwindVersion,
+    mode: Mode,
+) -> Vec<Preference> {
+    let mut changes = Vec::new();
+    if components
+        .iter()
+        .any(|c| snippet_framework(&c.snippet.language, framework) != framework)
+    {
+        changes.push(Preference::Language(framework));
+    }
+    if components
+        .iter()
+        .any(|c| TailwindVersion::parse(&c.snippet.version).is_some_and(|v| v != version))
+    {
+        changes.push(Preference::Version(version));
+    }
+    if mode != Mode::None
+        && components.iter().any(|c| {
+            c.snippet.supports_dark_mode && Mode::parse(&c.snippet.mode).is_some_and(|m| m != mode)
+        })
+    {
+        changes.push(Preference::Mode(mode));
+    }
+    changes
+}
+
+/// The account's current value for the preference `change` sets, as
+/// shown by the snippets on `components`.
+fn current_preference(components: &[ComponentData], change: Preference) -> Option<Preference> {
+    match change {
+        Preference::Language(framework) => components
+            .iter()
+            .map(|c| snippet_framework(&c.snippet.language, framework))
+            .find(|&f| f != framework)
+            .map(Preference::Language),
+        Preference::Version(version) => components
+            .iter()
+            .filter_map(|c| TailwindVersion::parse(&c.snippet.version))
+            .find(|&v| v != version)
+            .map(Preference::Version),
+        Preference::Mode(mode) => components
+            .iter()
+            .filter(|c| c.snippet.supports_dark_mode)
+            .filter_map(|c| Mode::parse(&c.snippet.mode))
+            .find(|&m| m != mode)
+            .map(Preference::Mode),
+    }
+}
+
+/// Classify failure statuses into typed errors
+fn check_status(resp: Response) -> Result<Response, FetchError> {
+    match resp.status() {
+        StatusCode::TOO_MANY_REQUESTS => {
+            let retry_after = resp
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse().ok())
+                .map(Duration::from_secs);
+            Err(FetchError::RateLimited { retry_after })
+        }
+        StatusCode::UNAUTHORIZED => Err(FetchError::SessionExpired),
+        status if status.as_u16() == STATUS_PAGE_EXPIRED => Err(FetchError::SessionExpired),
+        StatusCode::CONFLICT => Err(FetchError::InertiaVersionChanged),
+        _ => Ok(resp.error_for_status()?),
+    }
+}
+
+/// XSRF token from a `Set-Cookie` header value
+fn xsrf_token(cookie: &str) -> Option<String> {
+    let start = cookie.find("XSRF-TOKEN=")? + "XSRF-TOKEN=".len();
+    let end = cookie[start..].find(';')?;
+    Some(
+        urlencoding::decode(&cookie[start..start + end])
+            .unwrap_or_default()
+            .into_owned(),
+    )
+}
+
+/// Lifetime in seconds from the `Max-Age` of a `laravel_session` `Set-Cookie` value
+fn session_max_age(cookie: &str) -> Option<i64> {
+    if !cookie.trim_start().starts_with("laravel_session=") {
+        return None;
+    }
+    cookie.split(';').find_map(|attr| {
+        let (name, value) = attr.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("max-age")
+            .then(|| value.trim().parse().ok())
+            .flatten()
+    })
+}
+
+/// Inertia page object from the `data-page` attribute of a page
+fn page_data(html: &str) -> Option<serde_json::Value> {
+    let start = html.find("data-page=\"")? + "data-page=\"".len();
+    let end = html[start..].find('"')?;
+    let unescaped = html[start..start + end].replace("&quot;", "\"");
+    serde_json::from_str(&unescaped).ok()
+}
+
+/// Whether a page shows a signed-in user, if it says
+fn signed_in(page: &serde_json::Value) -> Option<bool> {
+    let auth = page.get("props")?.get("auth")?;
+    Some(auth.get("user").is_some_and(|user| !user.is_null()))
+}
+
+/// What TailwindPlus reported about a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionCheck {
+    /// Whether the site shows the session as signed in; `None` if it doesn't say
+    pub signed_in: Option<bool>,
+    /// Unix timestamp when the session cookie expires, if the site set one
+    pub expires_at: Option<i64>,
+}
+
 /// Build headers for Inertia.js requests
 fn inertia_headers(xsrf_token: &str, inertia_version: &str) -> HeaderMap {
     let mut headers = HeaderMap::new();
@@ -106,133 +398,420 @@ fn inertia_headers(xsrf_token: &str, inertia_version: &str) -> HeaderMap {
     headers
 }
 
-/// Convert category path to URL slug
-/// e.g., "Application UI" -> "application-ui"
-fn slugify(s: &str) -> String {
-    s.to_lowercase()
-        .replace(' ', "-")
-        .chars()
-        .filter(|c| c.is_alphanumeric() || *c == '-')
-        .collect()
-}
-
 /// Build subcategory URL from category path
 /// e.g., ["Application UI", "Forms", "Input Groups"] -> "/plus/ui-blocks/application-ui/forms/input-groups"
 pub fn subcategory_url(category: &str, subcategory: &str, sub_subcategory: &str) -> String {
+    page_url(DEFAULT_BASE_URL, category, subcategory, sub_subcategory)
+}
+
+/// Subcategory URL under `base_url`
+fn page_url(base_url: &str, category: &str, subcategory: &str, sub_subcategory: &str) -> String {
     let cat_slug = slugify(category);
     let sub_slug = slugify(subcategory);
     let subsub_slug = slugify(sub_subcategory);
 
-    format!("{BASE_URL}/ui-blocks/{cat_slug}/{sub_slug}/{subsub_slug}")
+    format!("{base_url}/ui-blocks/{cat_slug}/{sub_slug}/{subsub_slug}")
+}
+
+/// Memo key: a subcategory page as requested for a framework, version and mode
+type MemoKey = (String, Framework, TailwindVersion, Mode);
+
+/// A parsed subcategory page and when it was fetched
+struct MemoEntry {
+    fetched_at: Instant,
+    snippets: Arc<Vec<FetchedSnippet>>,
+}
+
+/// Parsed subcategory pages, shared by clones of a fetcher.
+///
+/// Each key has its own async lock, held for the duration of a fetch, so
+/// concurrent callers for the same page queue behind one request and then
+/// read its result.
+#[derive(Default)]
+struct SubcategoryMemo {
+    pages: Mutex<HashMap<MemoKey, Arc<AsyncMutex<Option<MemoEntry>>>>>,
+}
+
+impl SubcategoryMemo {
+    fn slot(&self, key: MemoKey) -> Arc<AsyncMutex<Option<MemoEntry>>> {
+        self.pages
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(key)
+            .or_default()
+            .clone()
+    }
+}
+
+/// Tokens read from the site, refreshed when Inertia reports a new version
+#[derive(Debug, Default)]
+struct InertiaSession {
+    xsrf_token: Option<String>,
+    version: Option<String>,
 }
 
 /// Component fetcher with session management
 #[derive(Clone)]
 pub struct ComponentFetcher {
-    client: reqwest::Client,
+    client: SharedHttpClient,
+    base_url: String,
     session_cookie: String,
-    xsrf_token: Option<String>,
-    inertia_version: Option<String>,
+    inertia: Arc<RwLock<InertiaSession>>,
+    retry: RetryPolicy,
+    tailwind_version: TailwindVersion,
+    memo: Arc<SubcategoryMemo>,
+    memo_ttl: Duration,
+    /// Held while fetching a page, since preferences are account-wide
+    negotiation: Arc<AsyncMutex<()>>,
+    /// Overrides the active profile's cache directory
+    cache_dir: Option<Utf8PathBuf>,
 }
 
 impl ComponentFetcher {
     /// Create a new fetcher with the given session cookie
     pub fn new(session_cookie: String) -> Self {
-        let client = reqwest::Client::builder()
-            .cookie_store(true)
-            .user_agent(USER_AGENT)
-            .build()
-            .expect("Failed to create HTTP client");
-
         Self {
-            client,
+            client: http::default_client(USER_AGENT, true),
+            base_url: DEFAULT_BASE_URL.to_string(),
             session_cookie,
-            xsrf_token: None,
-            inertia_version: None,
+            inertia: Arc::default(),
+            retry: RetryPolicy::default(),
+            tailwind_version: TailwindVersion::default(),
+            memo: Arc::default(),
+            memo_ttl: DEFAULT_MEMO_TTL,
+            negotiation: Arc::default(),
+            cache_dir: None,
         }
     }
 
+    /// Set the Tailwind CSS version snippets are requested for.
+    #[must_use]
+    pub const fn with_tailwind_version(mut self, version: TailwindVersion) -> Self {
+        self.tailwind_version = version;
+        self
+    }
+
+    /// Set how long fetched subcategory pages are reused.
+    ///
+    /// `Duration::ZERO` disables memoization; concurrent requests for the
+    /// same page are still coalesced.
+    #[must_use]
+    pub const fn with_memo_ttl(mut self, ttl: Duration) -> Self {
+        self.memo_ttl = ttl;
+        self
+    }
+
+    /// Send requests to another TailwindPlus host, e.g. a local stand-in.
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Send requests through `client` instead of the default `reqwest` client.
+    ///
+    /// The client should keep cookies, as the XSRF token is set by cookie.
+    #[must_use]
+    pub fn with_http_client(mut self, client: impl HttpClient + 'static) -> Self {
+        self.client = Arc::new(client);
+        self
+    }
+
+    /// Cache snippets under `dir` instead of the active profile's cache.
+    #[must_use]
+    pub fn with_cache_dir(mut self, dir: impl Into<Utf8PathBuf>) -> Self {
+        self.cache_dir = Some(dir.into());
+        self
+    }
+
+    /// Set how failed requests are retried.
+    #[must_use]
+    pub const fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
     /// Initialize the fetcher by getting XSRF token and Inertia version
-    pub async fn init(&mut self) -> Result<(), FetchError> {
-        // Make initial request to get cookies and version
-        let resp = self
-            .client
-            .get(BASE_URL)
-            .header("cookie", format!("laravel_session={}", self.session_cookie))
-            .send()
-            .await?;
+    pub async fn init(&self) -> Result<(), FetchError> {
+        self.load_site().await.map(|_| ())
+    }
 
-        // Extract XSRF token from cookies
-        if let Some(cookies) = resp.headers().get("set-cookie")
-            && let Ok(cookie_str) = cookies.to_str()
-            && let Some(start) = cookie_str.find("XSRF-TOKEN=")
-        {
-            let token_start = start + 11;
-            if let Some(end) = cookie_str[token_start..].find(';') {
-                let token = &cookie_str[token_start..token_start + end];
-                // URL decode the token
-                self.xsrf_token = Some(urlencoding::decode(token).unwrap_or_default().into_owned());
-            }
+    /// Check the session against TailwindPlus.
+    ///
+    /// Loads the site like [`init`](Self::init) does, and reports whether
+    /// it shows a signed-in user and when the session cookie expires.
+    pub async fn check_session(&self) -> Result<SessionCheck, FetchError> {
+        match self.load_site().await {
+            Err(FetchError::SessionExpired) => Ok(SessionCheck {
+                signed_in: Some(false),
+                expires_at: None,
+            }),
+            result => result,
         }
+    }
 
-        // Extract Inertia version from HTML
-        let html = resp.text().await?;
-        if let Some(start) = html.find("data-page=\"") {
-            let json_start = start + 11;
-            if let Some(end) = html[json_start..].find('"') {
-                let escaped = &html[json_start..json_start + end];
-                let unescaped = escaped.replace("&quot;", "\"");
-                if let Ok(page) = serde_json::from_str::<serde_json::Value>(&unescaped)
-                    && let Some(version) = page.get("version").and_then(|v| v.as_str())
-                {
-                    self.inertia_version = Some(version.to_string());
-                }
-            }
+    /// Load the site's front page, picking up the XSRF token and Inertia version
+    async fn load_site(&self) -> Result<SessionCheck, FetchError> {
+        let mut request = Request::new(Method::GET, self.parse_url(&self.base_url)?);
+        request.headers_mut().insert(COOKIE, self.cookie());
+        let resp = self.client.execute(request).await?;
+        if matches!(resp.status().as_u16(), 401 | STATUS_PAGE_EXPIRED) {
+            return Err(FetchError::SessionExpired);
         }
 
-        Ok(())
+        let cookies: Vec<&str> = resp
+            .headers()
+            .get_all("set-cookie")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .collect();
+        let token = cookies.iter().find_map(|cookie| xsrf_token(cookie));
+        let expires_at = cookies
+            .iter()
+            .find_map(|cookie| session_max_age(cookie))
+            .map(|max_age| {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map_or(0, |d| d.as_secs().cast_signed());
+                now + max_age
+            });
+        // Redirected to the login page when the session is not valid
+        let at_login = resp.url().path().ends_with("/login");
+        let page = page_data(&resp.text().await?);
+
+        let mut inertia = self.inertia.write().unwrap_or_else(PoisonError::into_inner);
+        if token.is_some() {
+            inertia.xsrf_token = token;
+        }
+        if let Some(version) = page.as_ref().and_then(|page| page.get("version")?.as_str()) {
+            inertia.version = Some(version.to_string());
+        }
+        drop(inertia);
+
+        Ok(SessionCheck {
+            signed_in: if at_login {
+                Some(false)
+            } else {
+                page.as_ref().and_then(signed_in)
+            },
+            expires_at,
+        })
     }
 
-    /// Fetch a subcategory page and return all components
-    async fn fetch_subcategory(
+    fn parse_url(&self, url: &str) -> Result<Url, FetchError> {
+        Url::parse(url).map_err(|e| FetchError::Parse(format!("Invalid URL {url}: {e}")))
+    }
+
+    fn cookie(&self) -> HeaderValue {
+        HeaderValue::from_str(&format!("laravel_session={}", self.session_cookie))
+            .unwrap_or_else(|_| HeaderValue::from_static(""))
+    }
+
+    /// Build an Inertia request carrying the session and current tokens
+    fn request(
         &self,
+        method: Method,
         url: &str,
-    ) -> Result<HashMap<String, ComponentData>, FetchError> {
-        let xsrf = self.xsrf_token.as_deref().unwrap_or("");
-        let version = self.inertia_version.as_deref().unwrap_or("");
-
-        let resp = self
-            .client
-            .get(url)
-            .header("cookie", format!("laravel_session={}", self.session_cookie))
-            .headers(inertia_headers(xsrf, version))
-            .send()
-            .await?;
+        body: Option<&serde_json::Value>,
+    ) -> Result<Request, FetchError> {
+        let mut request = Request::new(method, self.parse_url(url)?);
+        let inertia = self.inertia.read().unwrap_or_else(PoisonError::into_inner);
+        *request.headers_mut() = inertia_headers(
+            inertia.xsrf_token.as_deref().unwrap_or(""),
+            inertia.version.as_deref().unwrap_or(""),
+        );
+        drop(inertia);
+        request.headers_mut().insert(COOKIE, self.cookie());
+        if let Some(body) = body {
+            request
+                .headers_mut()
+                .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+            *request.body_mut() = Some(body.to_string().into());
+        }
+        Ok(request)
+    }
+
+    /// Send a request, retrying per the retry policy.
+    ///
+    /// An Inertia version change re-initializes the fetcher and repeats
+    /// the request once, without counting as a retry.
+    async fn send(
+        &self,
+        build: impl Fn() -> Result<Request, FetchError>,
+    ) -> Result<Response, FetchError> {
+        let mut attempt = 0;
+        let mut reinitialized = false;
+        loop {
+            let error = match self
+                .client
+                .execute(build()?)
+                .await
+                .map_err(FetchError::from)
+            {
+                Ok(resp) => match check_status(resp) {
+                    Ok(resp) => return Ok(resp),
+                    Err(e) => e,
+                },
+                Err(e) => e,
+            };
 
-        if !resp.status().is_success() {
-            return Err(FetchError::Http(resp.error_for_status().unwrap_err()));
+            let wait = match error {
+                FetchError::InertiaVersionChanged if !reinitialized => {
+                    reinitialized = true;
+                    self.init().await?;
+                    continue;
+                }
+                FetchError::RateLimited {
+                    retry_after: Some(wait),
+                } => wait,
+                ref e if e.is_retryable() => self.retry.backoff(attempt),
+                e => return Err(e),
+            };
+            if attempt >= self.retry.max_retries || wait > self.retry.max_delay {
+                return Err(error);
+            }
+            attempt += 1;
+            tokio::time::sleep(wait).await;
         }
+    }
 
-        let data: InertiaResponse = resp
+    /// Fetch a subcategory page and return all components
+    async fn fetch_subcategory(&self, url: &str) -> Result<Vec<ComponentData>, FetchError> {
+        let data: InertiaResponse = self
+            .send(|| self.request(Method::GET, url, None))
+            .await?
             .json()
             .await
             .map_err(|e| FetchError::Parse(e.to_string()))?;
 
-        let components = data
+        Ok(data
             .props
             .subcategory
             .ok_or_else(|| FetchError::Parse("No subcategory data in response".into()))?
-            .components;
+            .components)
+    }
+
+    /// Change an account preference, as the web UI's switchers do.
+    async fn set_preference(&self, uuid: &str, preference: Preference) -> Result<(), FetchError> {
+        let url = preference.url(&self.base_url);
+        let body = preference.body(uuid);
+        self.send(|| self.request(Method::PUT, &url, Some(&body)))
+            .await?;
+        Ok(())
+    }
 
-        let mut map = HashMap::new();
+    /// Fetch a subcategory page in the requested variants.
+    ///
+    /// If the page comes back in other variants, the differing preferences
+    /// are changed and the page is fetched once more. The account's own
+    /// preferences are put back afterwards, whether or not that worked.
+    async fn fetch_negotiated(
+        &self,
+        url: &str,
+        framework: Framework,
+        mode: Mode,
+    ) -> Result<Vec<ComponentData>, FetchError> {
+        let _negotiation = self.negotiation.lock().await;
+
+        let components = self.fetch_subcategory(url).await?;
+        let changes = preference_changes(&components, framework, self.tailwind_version, mode);
+        let Some(uuid) = components.first().map(|c| c.uuid.clone()) else {
+            return Ok(components);
+        };
+        if changes.is_empty() {
+            return Ok(components);
+        }
+
+        let originals: Vec<Preference> = changes
+            .iter()
+            .filter_map(|&change| current_preference(&components, change))
+            .collect();
+        let mut fetched = Ok(());
+        for preference in changes {
+            fetched = self.set_preference(&uuid, preference).await;
+            if fetched.is_err() {
+                break;
+            }
+        }
+        let fetched = match fetched {
+            Ok(()) => self.fetch_subcategory(url).await,
+            Err(e) => Err(e),
+        };
+
+        let mut restored = Ok(());
+        for original in originals {
+            if let Err(e) = self.set_preference(&uuid, original).await {
+                restored = Err(e);
+            }
+        }
+        let components = fetched?;
+        restored.map(|()| components)
+    }
+
+    /// Fetch every component snippet on a subcategory page and cache them.
+    ///
+    /// The page is requested in `framework` and `mode`. Snippets are cached
+    /// under the framework their language names; `framework` is assumed
+    /// when the language is not recognized. A page
+    /// fetched for the same framework and mode within the memo TTL is
+    /// returned without another request.
+    pub async fn fetch_snippets(
+        &self,
+        category: &str,
+        subcategory: &str,
+        sub_subcategory: &str,
+        framework: Framework,
+        mode: Mode,
+    ) -> Result<Arc<Vec<FetchedSnippet>>, FetchError> {
+        let url = page_url(&self.base_url, category, subcategory, sub_subcategory);
+        let slot = self
+            .memo
+            .slot((url.clone(), framework, self.tailwind_version, mode));
+        let mut entry = slot.lock().await;
+        if let Some(memo) = entry.as_ref()
+            && memo.fetched_at.elapsed() < self.memo_ttl
+        {
+            return Ok(Arc::clone(&memo.snippets));
+        }
+
+        let components = self.fetch_negotiated(&url, framework, mode).await?;
+        let mut snippets = Vec::with_capacity(components.len());
         for comp in components {
-            map.insert(comp.uuid.clone(), comp);
+            let Some(mode) = Mode::parse(&comp.snippet.mode) else {
+                continue;
+            };
+            let snippet = FetchedSnippet {
+                framework: snippet_framework(&comp.snippet.language, framework),
+                mode,
+                uuid: comp.uuid,
+                name: comp.name,
+                code: comp.snippet.code,
+            };
+            match &self.cache_dir {
+                Some(dir) => cache::store_cached_in(
+                    dir,
+                    &snippet.uuid,
+                    snippet.framework,
+                    mode,
+                    &snippet.code,
+                ),
+                None => cache::store_cached(&snippet.uuid, snippet.framework, mode, &snippet.code),
+            }?;
+            snippets.push(snippet);
         }
 
-        Ok(map)
+        let snippets = Arc::new(snippets);
+        *entry = Some(MemoEntry {
+            fetched_at: Instant::now(),
+            snippets: Arc::clone(&snippets),
+        });
+        Ok(snippets)
     }
 
     /// Fetch a specific component by UUID from a subcategory
+    ///
+    /// The rest of the page's snippets are cached along the way, so sibling
+    /// components don't cost another request.
     pub async fn fetch_component(
         &self,
         uuid: &str,
@@ -243,34 +822,39 @@ impl ComponentFetcher {
         mode: Mode,
     ) -> Result<String, FetchError> {
         // Check cache first
-        if let Some(code) = cache::get_cached(uuid, framework, mode) {
+        let cached = self.cache_dir.as_ref().map_or_else(
+            || cache::get_cached(uuid, framework, mode),
+            |dir| cache::get_cached_in(dir, uuid, framework, mode),
+        );
+        if let Some(code) = cached {
             return Ok(code);
         }
 
-        // Build URL and fetch
-        let url = subcategory_url(category, subcategory, sub_subcategory);
-        let components = self.fetch_subcategory(&url).await?;
+        let snippets = self
+            .fetch_snippets(category, subcategory, sub_subcategory, framework, mode)
+            .await?;
 
         // Find the component
-        let comp = components
-            .get(uuid)
+        let comp = snippets
+            .iter()
+            .find(|s| s.uuid == uuid)
             .ok_or_else(|| FetchError::ComponentNotFound(uuid.to_string()))?;
 
-        // Check mode matches
-        let mode_str = mode.as_str();
-        if comp.snippet.mode != mode_str {
+        // Check framework and mode match
+        if comp.framework != framework {
             return Err(FetchError::ComponentNotFound(format!(
-                "{uuid} mode {mode_str} (found {})",
-                comp.snippet.mode
+                "{uuid} framework {framework} (found {})",
+                comp.framework
+            )));
+        }
+        if comp.mode != mode {
+            return Err(FetchError::ComponentNotFound(format!(
+                "{uuid} mode {mode} (found {})",
+                comp.mode
             )));
         }
 
-        let code = comp.snippet.code.clone();
-
-        // Cache the result
-        cache::store_cached(uuid, framework, mode, &code)?;
-
-        Ok(code)
+        Ok(comp.code.clone())
     }
 }
 
@@ -286,6 +870,193 @@ mod tests {
         assert_eq!(slugify("E-commerce"), "e-commerce");
     }
 
+    #[test]
+    fn test_session_cookie_attributes() {
+        assert_eq!(
+            session_max_age(
+                "laravel_session=abc; expires=Sat, 01 Jan 2000 00:00:00 GMT; Max-Age=7200; path=/; httponly"
+            ),
+            Some(7200)
+        );
+        assert_eq!(session_max_age("XSRF-TOKEN=abc; Max-Age=7200"), None);
+        assert_eq!(session_max_age("laravel_session=abc; path=/"), None);
+        assert_eq!(xsrf_token("XSRF-TOKEN=a%3Db; path=/"), Some("a=b".into()));
+    }
+
+    #[test]
+    fn test_signed_in() {
+        let page = |props: serde_json::Value| json!({ "props": props });
+        assert_eq!(
+            signed_in(&page(json!({ "auth": { "user": { "name": "Ada" } } }))),
+            Some(true)
+        );
+        assert_eq!(
+            signed_in(&page(json!({ "auth": { "user": null } }))),
+            Some(false)
+        );
+        assert_eq!(signed_in(&page(json!({}))), None);
+    }
+
+    #[test]
+    fn test_snippet_framework() {
+        assert_eq!(snippet_framework("jsx", Framework::Vue), Framework::React);
+        assert_eq!(snippet_framework("Vue", Framework::React), Framework::Vue);
+        assert_eq!(snippet_framework("html", Framework::React), Framework::Html);
+        assert_eq!(snippet_framework("svelte", Framework::Vue), Framework::Vue);
+    }
+
+    fn component(language: &str, version: &str, mode: &str, dark: bool) -> ComponentData {
+        serde_json::from_value(json!({
+            "uuid": "hero-1",
+            "name": "Simple centered",
+            "snippet": {
+                "code": "<div />",
+                "language": language,
+                "version": version,
+                "mode": mode,
+                "supportsDarkMode": dark,
+                "preview": null,
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_preference_changes() {
+        let react_light = [component("jsx", "4", "light", true)];
+        let changes = |components: &[ComponentData], framework, version, mode| {
+            preference_changes(components, framework, version, mode)
+        };
+
+        assert!(
+            changes(
+                &react_light,
+                Framework::React,
+                TailwindVersion::V4,
+                Mode::Light
+            )
+            .is_empty()
+        );
+        assert_eq!(
+            changes(
+                &react_light,
+                Framework::Vue,
+                TailwindVersion::V3,
+                Mode::Dark
+            ),
+            [
+                Preference::Language(Framework::Vue),
+                Preference::Version(TailwindVersion::V3),
+                Preference::Mode(Mode::Dark),
+            ]
+        );
+
+        // Light-only snippets have no dark variant to switch to
+        let light_only = [component("vue", "4", "light", false)];
+        assert!(changes(&light_only, Framework::Vue, TailwindVersion::V4, Mode::Dark).is_empty());
+    }
+
+    #[test]
+    fn test_current_preference() {
+        let components = [
+            component("vue", "3", "light", false),
+            component("vue", "3", "dark", true),
+        ];
+        let current = |change| current_preference(&components, change);
+
+        assert_eq!(
+            current(Preference::Language(Framework::React)),
+            Some(Preference::Language(Framework::Vue))
+        );
+        assert_eq!(
+            current(Preference::Version(TailwindVersion::V4)),
+            Some(Preference::Version(TailwindVersion::V3))
+        );
+        // Only snippets with a dark variant show the mode preference
+        assert_eq!(
+            current(Preference::Mode(Mode::Light)),
+            Some(Preference::Mode(Mode::Dark))
+        );
+        assert_eq!(current(Preference::Mode(Mode::Dark)), None);
+    }
+
+    #[test]
+    fn test_preference_requests() {
+        let language = Preference::Language(Framework::Vue);
+        assert_eq!(
+            language.url(DEFAULT_BASE_URL),
+            "https://tailwindcss.com/plus/ui-blocks/language"
+        );
+        assert_eq!(
+            language.body("abc"),
+            json!({ "uuid": "abc", "snippet_lang": "vue" })
+        );
+        assert_eq!(
+            Preference::Version(TailwindVersion::V4).body("abc"),
+            json!({ "uuid": "abc", "version": 4 })
+        );
+        assert_eq!(
+            Preference::Mode(Mode::Dark).body("abc"),
+            json!({ "uuid": "abc", "mode": "dark" })
+        );
+    }
+
+    #[test]
+    fn test_fetch_snippets_reuses_memoized_page() {
+        let fetcher = ComponentFetcher::new("session".into());
+        let url = subcategory_url("Marketing", "Sections", "Heroes");
+        let snippet = FetchedSnippet {
+            uuid: "hero-1".into(),
+            name: "Simple centered".into(),
+            framework: Framework::React,
+            mode: Mode::Light,
+            code: "<div />".into(),
+        };
+        let slot = fetcher.memo.slot((
+            url.clone(),
+            Framework::React,
+            TailwindVersion::V4,
+            Mode::Light,
+        ));
+        *futures::executor::block_on(slot.lock()) = Some(MemoEntry {
+            fetched_at: Instant::now(),
+            snippets: Arc::new(vec![snippet.clone()]),
+        });
+
+        // Clones share the memo, so no request is made here
+        let clone = fetcher.clone();
+        let snippets = futures::executor::block_on(clone.fetch_snippets(
+            "Marketing",
+            "Sections",
+            "Heroes",
+            Framework::React,
+            Mode::Light,
+        ))
+        .unwrap();
+        assert_eq!(*snippets, [snippet]);
+
+        // Other frameworks and modes are separate pages
+        let other = fetcher
+            .memo
+            .slot((url, Framework::Vue, TailwindVersion::V4, Mode::Light));
+        assert!(!Arc::ptr_eq(&slot, &other));
+        assert!(futures::executor::block_on(other.lock()).is_none());
+    }
+
+    #[test]
+    fn test_backoff_grows_and_is_capped() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+        };
+        for attempt in 0..5 {
+            let expected = (Duration::from_millis(100) * 2u32.pow(attempt)).min(policy.max_delay);
+            let delay = policy.backoff(attempt);
+            assert!(delay >= expected / 2 && delay <= expected, "{delay:?}");
+        }
+    }
+
     #[test]
     fn test_subcategory_url() {
         let url = subcategory_url("Application UI", "Forms", "Input Groups");
@@ -0,0 +1,48 @@
+//! Pluggable HTTP backend for network access.
+//!
+//! [`ComponentFetcher`](crate::fetch::ComponentFetcher) and
+//! [`CompositePreview`](crate::preview::CompositePreview) send every request
+//! through an [`HttpClient`]. The default is a [`reqwest::Client`]; tests and
+//! embedders can inject their own to record, replay or redirect traffic.
+
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use reqwest::{Request, Response};
+
+/// Sends HTTP requests.
+pub trait HttpClient: Send + Sync {
+    /// Send `request` and return the response, whatever its status.
+    fn execute(&self, request: Request) -> BoxFuture<'_, reqwest::Result<Response>>;
+}
+
+impl HttpClient for reqwest::Client {
+    fn execute(&self, request: Request) -> BoxFuture<'_, reqwest::Result<Response>> {
+        Box::pin(Self::execute(self, request))
+    }
+}
+
+impl<T: HttpClient + ?Sized> HttpClient for Arc<T> {
+    fn execute(&self, request: Request) -> BoxFuture<'_, reqwest::Result<Response>> {
+        (**self).execute(request)
+    }
+}
+
+/// An [`HttpClient`] shared between clones of its owner.
+pub type SharedHttpClient = Arc<dyn HttpClient>;
+
+/// Build a `reqwest` client sending `user_agent`, optionally keeping cookies.
+///
+/// # Panics
+///
+/// Panics if the TLS backend cannot be initialized.
+#[must_use]
+pub fn default_client(user_agent: &str, cookie_store: bool) -> SharedHttpClient {
+    Arc::new(
+        reqwest::Client::builder()
+            .cookie_store(cookie_store)
+            .user_agent(user_agent)
+            .build()
+            .expect("Failed to create HTTP client"),
+    )
+}
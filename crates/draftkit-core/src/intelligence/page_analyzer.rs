@@ -185,6 +185,48 @@ impl PageType {
         Self::Unknown
     }
 
+    /// Parse a page type from a user-facing name (e.g., "landing", "plans").
+    ///
+    /// Unrecognized names map to [`PageType::Content`].
+    #[must_use]
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "home" | "landing" | "index" => Self::Home,
+            "about" | "team" | "company" => Self::About,
+            "pricing" | "plans" => Self::Pricing,
+            "blog" | "articles" | "posts" => Self::Blog,
+            "docs" | "documentation" | "guide" | "guides" => Self::Docs,
+            "contact" => Self::Contact,
+            "legal" | "privacy" | "terms" => Self::Legal,
+            "changelog" | "releases" => Self::Changelog,
+            "api" | "api-reference" => Self::ApiReference,
+            "portfolio" | "projects" | "work" => Self::Portfolio,
+            "auth" | "login" | "signin" | "register" => Self::Auth,
+            "dashboard" | "app" | "settings" => Self::Dashboard,
+            "media" | "podcast" | "videos" => Self::Media,
+            "error" | "404" | "500" => Self::Error,
+            "resources" | "downloads" => Self::Resources,
+            _ => Self::Content,
+        }
+    }
+
+    /// Section types typically needed for this kind of page, in page order.
+    #[must_use]
+    pub const fn typical_sections(&self) -> &'static [&'static str] {
+        match self {
+            Self::Home => &["header", "hero", "feature", "testimonial", "cta", "footer"],
+            Self::Pricing => &["header", "pricing", "faq", "cta", "footer"],
+            Self::Blog => &["header", "blog", "footer"],
+            Self::Docs => &["header", "content", "footer"],
+            Self::About => &["header", "hero", "team", "footer"],
+            Self::Contact => &["header", "contact", "footer"],
+            Self::Portfolio => &["header", "hero", "portfolio", "footer"],
+            Self::Auth => &["auth"],
+            Self::Dashboard => &["sidebar", "content"],
+            _ => &["header", "content", "footer"],
+        }
+    }
+
     /// Normalize route by stripping Next.js route groups like /(main), /(auth).
     fn normalize_route(route: &str) -> String {
         // Remove route groups: /(...) patterns
@@ -375,6 +417,15 @@ impl PageAnalyzer {
         Ok(self.analyses.get(&name).expect("just inserted"))
     }
 
+    /// Analyze a single page file outside of a template.
+    ///
+    /// The page's own directory is treated as the app root, so the route is
+    /// always `/`. Returns `None` if the page uses no components.
+    pub fn analyze_page(&self, path: &Utf8Path) -> Result<Option<PageAnalysis>, PageAnalysisError> {
+        let app_root = path.parent().ok_or(PageAnalysisError::InvalidPath)?;
+        self.analyze_page_file(path, app_root, "local", &mut HashMap::new())
+    }
+
     /// Find the source root directory, handling variant subdirectories.
     fn find_source_root(&self, path: &Utf8Path) -> Result<Utf8PathBuf, PageAnalysisError> {
         // Check for direct src directory
@@ -11,12 +11,16 @@ pub mod docs;
 pub mod elements;
 pub mod error;
 pub mod fetch;
+pub mod http;
 pub mod intelligence;
 pub mod manifest;
 pub mod patterns;
 pub mod preset;
 pub mod preview;
+pub mod profile;
 pub mod scaffold;
+pub mod slug;
+pub mod sync;
 
 pub use catalog::Catalog;
 pub use catalyst::{
@@ -28,7 +32,7 @@ pub use components::{
     CategoryNode, Component, ComponentMeta, ComponentReader, ComponentRecord, ComponentResponse,
     Framework, Mode, SearchResult, Snippet, TailwindVersion,
 };
-pub use config::{Config, ConfigLoader, LogLevel};
+pub use config::{Config, ConfigLoader, LogLevel, ProfileConfig};
 pub use data_dir::{DataSource, data_dir, has_runtime_data};
 pub use docs::{TopicInfo, get_docs, list_all_topics, list_topics, search_topics};
 pub use elements::{ElementInfo, get_element_docs, get_full_docs, get_overview, list_elements};
@@ -42,6 +46,8 @@ pub use preview::{
     RenderedPreview, clear_preview_cache, get_preview_cache_stats, previews_cache_dir,
 };
 pub use scaffold::{
-    FrameworkTarget, GenerateError, GenerateOptions, GeneratedPage, PackageManager, PageGenerator,
-    ProjectConfig, SlotValue, TemplateEngine, TemplateError,
+    FrameworkTarget, GenerateError, GenerateLock, GenerateOptions, GeneratedPage, GeneratedSection,
+    GeneratedSite, PackageManager, PageGenerator, ProjectConfig, ProjectInspection, SitePlan,
+    SlotValue, TailwindConflict, TemplateEngine, TemplateError, TemplateValue, VirtualFs,
+    WritePlan,
 };
@@ -0,0 +1,626 @@
+//! Pattern inference from existing pages.
+//!
+//! Turns a page that already exists (a React component or an HTML file)
+//! into a reusable [`Pattern`]:
+//!
+//! 1. React pages are parsed with [`PageAnalyzer`] to find the section
+//!    components they render, in render order. HTML pages are split on
+//!    their top-level `<header>`, `<nav>`, `<section>` and `<footer>` tags.
+//! 2. Each section is classified with [`SectionType::from_name`], which
+//!    maps onto the catalog's page-section sub-subcategories.
+//! 3. Each section's markup is run through [`StyleExtractor`], and the
+//!    measured spread becomes the pattern's [`StyleConstraints`].
+
+use std::collections::HashMap;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use regex_lite::Regex;
+
+use crate::components::StyleProfile;
+use crate::intelligence::{ComponentUsage, PageAnalyzer, SectionType, StyleExtractor};
+use crate::patterns::loader::PatternError;
+use crate::patterns::schema::{
+    CompositionRules, Pattern, PatternFile, SectionMerge, SectionSpec, StyleConstraints,
+    VariantSpec,
+};
+use crate::slug::slugify;
+
+/// Page entry points checked when inferring from a project directory.
+const PAGE_CANDIDATES: &[&str] = &[
+    "src/App.tsx",
+    "src/App.jsx",
+    "app/page.tsx",
+    "app/page.jsx",
+    "src/app/page.tsx",
+    "src/app/page.jsx",
+    "src/pages/index.tsx",
+    "pages/index.tsx",
+    "index.html",
+    "src/index.html",
+];
+
+/// Slack added around measured style values so the source page (and
+/// close alternatives) pass the inferred constraints.
+const STYLE_MARGIN: f32 = 0.1;
+
+/// A section found on an existing page.
+#[derive(Debug, Clone)]
+pub struct InferredSection {
+    /// Component or element name the section was detected from
+    pub name: String,
+    /// Classified section type
+    pub section_type: SectionType,
+    /// Measured style profile
+    pub style: StyleProfile,
+}
+
+/// Result of inferring a pattern from an existing page.
+#[derive(Debug, Clone)]
+pub struct PatternInference {
+    /// The inferred pattern
+    pub pattern: Pattern,
+    /// Page file that was analyzed
+    pub page_path: Utf8PathBuf,
+    /// Sections in page order
+    pub sections: Vec<InferredSection>,
+    /// Components that could not be mapped to a section type
+    pub skipped: Vec<String>,
+}
+
+impl PatternInference {
+    /// Render the inferred pattern as a pattern TOML file.
+    ///
+    /// # Errors
+    /// Returns an error if the pattern cannot be serialized.
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        let file = PatternFile {
+            pattern: self.pattern.clone(),
+        };
+        Ok(format!(
+            "# Inferred by draftkit from {}\n\n{}",
+            self.page_path,
+            toml::to_string_pretty(&file)?
+        ))
+    }
+}
+
+/// Infer a pattern from a page file or a project directory.
+///
+/// For a directory, the first known entry point (`src/App.tsx`,
+/// `app/page.tsx`, `index.html`, ...) is analyzed.
+///
+/// # Errors
+/// Returns `NotFound` if no page can be located, `AnalysisError` if the
+/// page cannot be parsed, and `ValidationError` if no sections are found.
+pub fn infer_pattern(path: &Utf8Path, id: &str) -> Result<PatternInference, PatternError> {
+    let page_path = find_page(path)?;
+    let code = std::fs::read_to_string(&page_path)?;
+
+    let is_html = page_path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"));
+
+    let (detected, skipped) = if is_html {
+        html_sections(&code)
+    } else {
+        react_sections(&page_path, &code)?
+    };
+
+    // Collapse consecutive duplicates (e.g. a <nav> nested in a <header>)
+    let mut sections: Vec<InferredSection> = Vec::new();
+    for section in detected {
+        if sections
+            .last()
+            .is_some_and(|last| last.section_type == section.section_type)
+        {
+            continue;
+        }
+        sections.push(section);
+    }
+
+    if sections.is_empty() {
+        return Err(PatternError::ValidationError(format!(
+            "no recognizable sections found in {page_path}"
+        )));
+    }
+
+    let pattern = build_pattern(id, &page_path, &sections);
+
+    Ok(PatternInference {
+        pattern,
+        page_path,
+        sections,
+        skipped,
+    })
+}
+
+/// Locate the page to analyze.
+fn find_page(path: &Utf8Path) -> Result<Utf8PathBuf, PatternError> {
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+
+    if path.is_dir() {
+        for candidate in PAGE_CANDIDATES {
+            let page = path.join(candidate);
+            if page.is_file() {
+                return Ok(page);
+            }
+        }
+    }
+
+    Err(PatternError::NotFound(format!("no page found at {path}")))
+}
+
+/// Detect sections in a React page, in render order.
+fn react_sections(
+    page_path: &Utf8Path,
+    code: &str,
+) -> Result<(Vec<InferredSection>, Vec<String>), PatternError> {
+    let analyzer = PageAnalyzer::new();
+    let analysis = analyzer
+        .analyze_page(page_path)
+        .map_err(|e| PatternError::AnalysisError(e.to_string()))?;
+
+    let Some(analysis) = analysis else {
+        return Ok((Vec::new(), Vec::new()));
+    };
+
+    let project_root = find_project_root(page_path);
+    let page_dir = page_path.parent().unwrap_or(page_path);
+
+    let mut sections = Vec::new();
+    let mut skipped = Vec::new();
+
+    for (tag, usage) in render_order(code, &analysis.components) {
+        let section_type = SectionType::from_name(&slugify(&tag));
+        if section_type == SectionType::Unknown {
+            skipped.push(tag);
+            continue;
+        }
+
+        // Prefer the component's own source; fall back to the page markup
+        let imported = usage
+            .import_path
+            .as_deref()
+            .and_then(|import_path| resolve_import(import_path, page_dir, &project_root))
+            .and_then(|file| std::fs::read_to_string(file).ok());
+        let style = StyleExtractor::extract(
+            imported
+                .as_deref()
+                .or_else(|| inline_component_source(code, &tag))
+                .unwrap_or(code),
+        );
+
+        sections.push(InferredSection {
+            name: tag,
+            section_type,
+            style,
+        });
+    }
+
+    Ok((sections, skipped))
+}
+
+/// Order component usages by their first JSX occurrence in the page.
+///
+/// Usages that are imported but never rendered are dropped.
+fn render_order(code: &str, usages: &[ComponentUsage]) -> Vec<(String, ComponentUsage)> {
+    let tag_re = Regex::new(r"<([A-Z][a-zA-Z0-9]*)").expect("valid regex");
+    let mut ordered: Vec<(String, ComponentUsage)> = Vec::new();
+
+    for cap in tag_re.captures_iter(code) {
+        let tag = &cap[1];
+        if ordered.iter().any(|(seen, _)| seen == tag) {
+            continue;
+        }
+
+        let kebab = slugify(tag);
+        let lower = tag.to_lowercase();
+        let usage = usages.iter().find(|u| {
+            u.id == kebab
+                || u.id == lower
+                || u.import_path
+                    .as_deref()
+                    .and_then(|p| p.rsplit('/').next())
+                    .is_some_and(|file| file == tag || file == kebab)
+        });
+
+        // The analyzer only spots `const X =` inline components, so also
+        // accept function components defined in the page itself.
+        let usage = usage.cloned().or_else(|| {
+            inline_component_source(code, tag).map(|_| ComponentUsage {
+                id: lower,
+                import_path: None,
+                is_inline: true,
+                style: None,
+            })
+        });
+
+        if let Some(usage) = usage {
+            ordered.push((tag.to_string(), usage));
+        }
+    }
+
+    ordered
+}
+
+/// Resolve a relative or `@/` import to a source file on disk.
+fn resolve_import(
+    import_path: &str,
+    page_dir: &Utf8Path,
+    project_root: &Utf8Path,
+) -> Option<Utf8PathBuf> {
+    let base = if let Some(rest) = import_path.strip_prefix("@/") {
+        project_root.join("src").join(rest)
+    } else if import_path.starts_with("./") || import_path.starts_with("../") {
+        page_dir.join(import_path)
+    } else {
+        return None;
+    };
+
+    if base.is_file() {
+        return Some(base);
+    }
+
+    ["tsx", "jsx", "ts", "js"]
+        .iter()
+        .map(|ext| Utf8PathBuf::from(format!("{base}.{ext}")))
+        .chain(
+            ["index.tsx", "index.jsx"]
+                .iter()
+                .map(|index| base.join(index)),
+        )
+        .find(|candidate| candidate.is_file())
+}
+
+/// Walk up from the page to the nearest directory with a package.json.
+fn find_project_root(page_path: &Utf8Path) -> Utf8PathBuf {
+    page_path
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join("package.json").is_file())
+        .or_else(|| page_path.parent())
+        .unwrap_or(page_path)
+        .to_path_buf()
+}
+
+/// Extract the body of a component defined in the page file itself.
+fn inline_component_source<'a>(code: &'a str, name: &str) -> Option<&'a str> {
+    let start = code
+        .find(&format!("function {name}("))
+        .or_else(|| code.find(&format!("const {name} =")))?;
+
+    let rest = &code[start..];
+    let end = ["\nfunction ", "\nexport ", "\nconst "]
+        .iter()
+        .filter_map(|marker| rest[1..].find(marker).map(|i| i + 1))
+        .min()
+        .unwrap_or(rest.len());
+
+    Some(&rest[..end])
+}
+
+/// Detect sections in an HTML page from its landmark tags.
+fn html_sections(code: &str) -> (Vec<InferredSection>, Vec<String>) {
+    let tag_re = Regex::new(r"(?i)<(header|nav|section|footer)\b([^>]*)>").expect("valid regex");
+    let matches: Vec<_> = tag_re.captures_iter(code).collect();
+
+    let mut sections = Vec::new();
+    let mut skipped = Vec::new();
+
+    for (i, cap) in matches.iter().enumerate() {
+        let whole = cap.get(0).expect("group 0 always matches");
+        let tag = cap[1].to_lowercase();
+        let attrs = &cap[2];
+
+        let end = matches
+            .get(i + 1)
+            .and_then(|next| next.get(0))
+            .map_or(code.len(), |m| m.start());
+        let markup = &code[whole.start()..end];
+
+        let name = html_section_name(&tag, attrs);
+        let section_type = match tag.as_str() {
+            "header" | "nav" => SectionType::Header,
+            "footer" => SectionType::Footer,
+            _ => SectionType::from_name(&name),
+        };
+
+        if section_type == SectionType::Unknown {
+            skipped.push(name);
+            continue;
+        }
+
+        sections.push(InferredSection {
+            name,
+            section_type,
+            style: StyleExtractor::extract(markup),
+        });
+    }
+
+    (sections, skipped)
+}
+
+/// Build a descriptive name for an HTML section from its id/aria-label.
+fn html_section_name(tag: &str, attrs: &str) -> String {
+    let attr_re =
+        Regex::new(r#"(id|aria-label|class)\s*=\s*["']([^"']*)["']"#).expect("valid regex");
+
+    let mut values: HashMap<&str, &str> = HashMap::new();
+    for cap in attr_re.captures_iter(attrs) {
+        if let (Some(key), Some(value)) = (cap.get(1), cap.get(2)) {
+            values.insert(key.as_str(), value.as_str());
+        }
+    }
+
+    // Prefer explicit identifiers; fall back to classes, which often carry
+    // names like "hero" or "pricing-table".
+    values
+        .get("id")
+        .or_else(|| values.get("aria-label"))
+        .or_else(|| values.get("class"))
+        .map_or_else(|| tag.to_string(), |value| format!("{tag} {value}"))
+}
+
+/// Assemble the pattern from the detected sections.
+fn build_pattern(id: &str, page_path: &Utf8Path, sections: &[InferredSection]) -> Pattern {
+    let specs = sections
+        .iter()
+        .enumerate()
+        .map(|(position, section)| SectionSpec {
+            section_type: section.section_type.as_str().to_string(),
+            required: true,
+            position: u8::try_from(position).unwrap_or(u8::MAX),
+            count: None,
+            variants: vec![VariantSpec {
+                id: variant_hint(section.section_type, &section.name),
+                weight: 1.0,
+                recommended: true,
+            }],
+            slots: vec![],
+            constraints: vec![],
+            merge: SectionMerge::default(),
+        })
+        .collect();
+
+    Pattern {
+        id: id.to_string(),
+        name: title_case(id),
+        description: format!("Inferred from {page_path}"),
+        tags: vec!["inferred".to_string()],
+        author: String::new(),
+        version: "1.0.0".to_string(),
+        extends: None,
+        include: vec![],
+        style_constraints: measure_constraints(sections),
+        sections: specs,
+        schemas: HashMap::new(),
+        rules: CompositionRules::default(),
+    }
+}
+
+/// Measure style constraints from the spread of section profiles.
+fn measure_constraints(sections: &[InferredSection]) -> StyleConstraints {
+    let range = |f: fn(&StyleProfile) -> f32| {
+        let values = sections.iter().map(|s| f(&s.style));
+        let min = values.clone().fold(f32::MAX, f32::min);
+        let max = values.fold(f32::MIN, f32::max);
+        (min, max)
+    };
+
+    let spread = |(min, max): (f32, f32)| round2((max - min + STYLE_MARGIN).min(1.0));
+    let widen = |(min, max): (f32, f32)| {
+        (
+            round2((min - STYLE_MARGIN).max(0.0)),
+            round2((max + STYLE_MARGIN).min(1.0)),
+        )
+    };
+
+    StyleConstraints {
+        visual_weight_variance: spread(range(|s| s.visual_weight)),
+        spacing_density_variance: spread(range(|s| s.spacing_density)),
+        formality_range: widen(range(|s| s.formality)),
+        color_intensity_range: widen(range(|s| s.color_intensity)),
+    }
+}
+
+/// Build a variant hint like "hero-split-image" from a component name.
+///
+/// Words already implied by the section type are dropped so the remaining
+/// keywords are useful to the component matcher.
+fn variant_hint(section_type: SectionType, name: &str) -> String {
+    let type_words: Vec<&str> = section_type.as_str().split('-').collect();
+    let words: Vec<String> = slugify(name)
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .filter(|word| {
+            !["section", "sections", "header", "nav", "footer", "div"].contains(word)
+                && !type_words
+                    .iter()
+                    .any(|t| word.starts_with(t) || t.starts_with(*word))
+        })
+        .map(ToString::to_string)
+        .collect();
+
+    if words.is_empty() {
+        section_type.as_str().to_string()
+    } else {
+        format!("{}-{}", section_type.as_str(), words.join("-"))
+    }
+}
+
+/// Convert a kebab-case ID to a title ("saas-landing" → "Saas Landing").
+fn title_case(id: &str) -> String {
+    id.split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn round2(value: f32) -> f32 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &Utf8Path, path: &str, content: &str) {
+        let file = dir.join(path);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(file, content).unwrap();
+    }
+
+    #[test]
+    fn infer_react_project() {
+        let temp = TempDir::new().unwrap();
+        let root = Utf8Path::from_path(temp.path()).unwrap();
+
+        write(root, "package.json", r#"{"name": "site"}"#);
+        write(
+            root,
+            "src/App.tsx",
+            r#"
+import { Navbar } from './components/Navbar'
+import { HeroSplitImage } from './components/HeroSplitImage'
+import { PricingThreeTier } from '@/components/PricingThreeTier'
+
+export default function App() {
+  return (
+    <>
+      <Navbar />
+      <HeroSplitImage />
+      <PricingThreeTier />
+      <SiteFooter />
+    </>
+  )
+}
+
+function SiteFooter() {
+  return <footer className="bg-gray-900 py-12 text-white">Footer</footer>
+}
+"#,
+        );
+        write(
+            root,
+            "src/components/HeroSplitImage.tsx",
+            r#"export function HeroSplitImage() { return <div className="bg-indigo-600 shadow-2xl rounded-3xl p-16" /> }"#,
+        );
+
+        let inference = infer_pattern(root, "my-site").unwrap();
+        let types: Vec<&str> = inference
+            .pattern
+            .sections
+            .iter()
+            .map(|s| s.section_type.as_str())
+            .collect();
+
+        assert_eq!(inference.page_path, root.join("src/App.tsx"));
+        assert_eq!(types, ["header", "hero", "pricing", "footer"]);
+        assert_eq!(
+            inference.pattern.sections[1].variants[0].id,
+            "hero-split-image"
+        );
+        assert_eq!(
+            inference.pattern.sections[2].variants[0].id,
+            "pricing-three-tier"
+        );
+        assert!(
+            inference.sections[1].style.visual_weight > inference.sections[0].style.visual_weight
+        );
+    }
+
+    #[test]
+    fn infer_html_page() {
+        let temp = TempDir::new().unwrap();
+        let root = Utf8Path::from_path(temp.path()).unwrap();
+
+        write(
+            root,
+            "index.html",
+            r#"
+<header class="bg-white"><nav class="p-4">Links</nav></header>
+<section id="hero" class="py-24 text-5xl">Hello</section>
+<section id="features" class="py-16 gap-8">Features</section>
+<section class="mystery">???</section>
+<footer class="bg-gray-100 p-8">Bye</footer>
+"#,
+        );
+
+        let inference = infer_pattern(&root.join("index.html"), "static").unwrap();
+        let types: Vec<&str> = inference
+            .pattern
+            .sections
+            .iter()
+            .map(|s| s.section_type.as_str())
+            .collect();
+
+        assert_eq!(types, ["header", "hero", "features", "footer"]);
+        assert_eq!(inference.skipped, ["section mystery"]);
+        assert_eq!(inference.pattern.name, "Static");
+    }
+
+    #[test]
+    fn inferred_toml_round_trips() {
+        let temp = TempDir::new().unwrap();
+        let root = Utf8Path::from_path(temp.path()).unwrap();
+        write(
+            root,
+            "index.html",
+            r#"<section id="hero">Hi</section><section id="pricing">$</section>"#,
+        );
+
+        let inference = infer_pattern(root, "round-trip").unwrap();
+        let parsed: PatternFile = toml::from_str(&inference.to_toml().unwrap()).unwrap();
+
+        assert_eq!(parsed.pattern.id, "round-trip");
+        assert_eq!(parsed.pattern.sections.len(), 2);
+        assert_eq!(parsed.pattern.sections[1].section_type, "pricing");
+        assert_eq!(parsed.pattern.sections[1].merge, SectionMerge::default());
+    }
+
+    #[test]
+    fn measured_constraints_cover_sections() {
+        let section = |visual_weight: f32, formality: f32| InferredSection {
+            name: "x".to_string(),
+            section_type: SectionType::Hero,
+            style: StyleProfile {
+                visual_weight,
+                formality,
+                ..Default::default()
+            },
+        };
+
+        let constraints = measure_constraints(&[section(0.2, 0.5), section(0.5, 0.9)]);
+        assert!((constraints.visual_weight_variance - 0.4).abs() < 0.001);
+        assert_eq!(constraints.formality_range, (0.4, 1.0));
+    }
+
+    #[test]
+    fn variant_hints_drop_type_words() {
+        assert_eq!(variant_hint(SectionType::Hero, "SimpleHero"), "hero-simple");
+        assert_eq!(variant_hint(SectionType::Faq, "FaqSection"), "faq");
+        assert_eq!(
+            variant_hint(SectionType::Features, "FeaturesGrid"),
+            "features-grid"
+        );
+    }
+
+    #[test]
+    fn missing_page_is_not_found() {
+        let temp = TempDir::new().unwrap();
+        let root = Utf8Path::from_path(temp.path()).unwrap();
+        assert!(matches!(
+            infer_pattern(root, "empty"),
+            Err(PatternError::NotFound(_))
+        ));
+    }
+}
@@ -6,11 +6,17 @@
 //! 1. Built-in patterns (embedded in binary)
 //! 2. User patterns (~/.config/draftkit/patterns/)
 //! 3. Project patterns (./.draftkit/patterns/)
+//!
+//! Section fragments are loaded the same way from `fragments/` directories.
+//! Once every source is loaded, `extends` chains and `include` lists are
+//! resolved into flat patterns.
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-use crate::patterns::schema::{Pattern, PatternFile};
+use crate::patterns::schema::{
+    Fragment, FragmentFile, Pattern, PatternFile, SectionMerge, SectionSpec,
+};
 
 /// Error type for pattern loading operations.
 #[derive(Debug, thiserror::Error)]
@@ -18,6 +24,9 @@ pub enum PatternError {
     #[error("Pattern not found: {0}")]
     NotFound(String),
 
+    #[error("Fragment not found: {0}")]
+    FragmentNotFound(String),
+
     #[error("Failed to read pattern file: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -26,6 +35,12 @@ pub enum PatternError {
 
     #[error("Pattern validation failed: {0}")]
     ValidationError(String),
+
+    #[error("Circular inheritance detected: {0}")]
+    CircularInheritance(String),
+
+    #[error("Failed to analyze page: {0}")]
+    AnalysisError(String),
 }
 
 /// Pattern source for tracking where a pattern came from.
@@ -53,8 +68,12 @@ pub struct LoadedPattern {
 /// Pattern loader with directory precedence.
 #[derive(Debug)]
 pub struct PatternLoader {
-    /// Loaded patterns by ID (later sources override earlier)
+    /// Resolved patterns by ID (inheritance and fragments applied)
     patterns: HashMap<String, LoadedPattern>,
+    /// Patterns as written, before inheritance is resolved
+    raw: HashMap<String, LoadedPattern>,
+    /// Section fragments by ID
+    fragments: HashMap<String, Fragment>,
 }
 
 impl PatternLoader {
@@ -63,14 +82,13 @@ impl PatternLoader {
     /// # Errors
     /// Returns error if pattern directories cannot be read.
     pub fn new() -> Result<Self, PatternError> {
-        let mut loader = Self {
-            patterns: HashMap::new(),
-        };
+        let mut loader = Self::empty();
 
         // Load in precedence order (later overrides earlier)
         loader.load_builtin_patterns();
         loader.load_user_patterns()?;
         loader.load_project_patterns()?;
+        loader.resolve_all();
 
         Ok(loader)
     }
@@ -78,19 +96,34 @@ impl PatternLoader {
     /// Create a loader with only built-in patterns.
     #[must_use]
     pub fn builtin_only() -> Self {
-        let mut loader = Self {
-            patterns: HashMap::new(),
-        };
+        let mut loader = Self::empty();
         loader.load_builtin_patterns();
+        loader.resolve_all();
         loader
     }
 
+    fn empty() -> Self {
+        Self {
+            patterns: HashMap::new(),
+            raw: HashMap::new(),
+            fragments: HashMap::new(),
+        }
+    }
+
     /// Get a pattern by ID.
+    ///
+    /// The returned pattern has `extends` and `include` already resolved.
     #[must_use]
     pub fn get(&self, id: &str) -> Option<&LoadedPattern> {
         self.patterns.get(id)
     }
 
+    /// Get a section fragment by ID.
+    #[must_use]
+    pub fn get_fragment(&self, id: &str) -> Option<&Fragment> {
+        self.fragments.get(id)
+    }
+
     /// List all available pattern IDs.
     #[must_use]
     pub fn list_ids(&self) -> Vec<&str> {
@@ -103,12 +136,87 @@ impl PatternLoader {
         self.patterns.values().collect()
     }
 
+    /// Resolve a pattern's `extends` chain and fragments into a flat pattern.
+    ///
+    /// Sections are layered in order: parent, then each included fragment,
+    /// then the pattern's own sections. Positions are renumbered to match
+    /// the merged order.
+    ///
+    /// # Errors
+    /// Returns `NotFound` if the pattern or a parent is missing,
+    /// `FragmentNotFound` for an unknown include, `CircularInheritance`
+    /// if the `extends` chain loops, and `ValidationError` if a merge
+    /// directive refers to a section type that is not present.
+    pub fn resolve(&self, id: &str) -> Result<Pattern, PatternError> {
+        self.resolve_chain(id, &mut Vec::new())
+    }
+
+    fn resolve_chain(&self, id: &str, chain: &mut Vec<String>) -> Result<Pattern, PatternError> {
+        if chain.iter().any(|seen| seen == id) {
+            chain.push(id.to_string());
+            return Err(PatternError::CircularInheritance(chain.join(" -> ")));
+        }
+
+        let loaded = self
+            .raw
+            .get(id)
+            .ok_or_else(|| PatternError::NotFound(id.to_string()))?;
+        let pattern = &loaded.pattern;
+
+        if pattern.extends.is_none() && pattern.include.is_empty() {
+            return Ok(pattern.clone());
+        }
+
+        chain.push(id.to_string());
+        let parent = match pattern.extends {
+            Some(ref parent_id) => Some(self.resolve_chain(parent_id, chain)?),
+            None => None,
+        };
+        chain.pop();
+
+        let fragments = pattern
+            .include
+            .iter()
+            .map(|fragment_id| {
+                self.fragments
+                    .get(fragment_id)
+                    .ok_or_else(|| PatternError::FragmentNotFound(fragment_id.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        merge_pattern(parent, &fragments, pattern)
+    }
+
+    /// Resolve every raw pattern into `patterns`.
+    fn resolve_all(&mut self) {
+        self.patterns.clear();
+
+        for (id, loaded) in &self.raw {
+            match self.resolve(id) {
+                Ok(pattern) => {
+                    self.patterns.insert(
+                        id.clone(),
+                        LoadedPattern {
+                            pattern,
+                            source: loaded.source,
+                            path: loaded.path.clone(),
+                        },
+                    );
+                }
+                Err(e) => {
+                    // Log warning but keep the other patterns usable
+                    eprintln!("Warning: Failed to resolve pattern {id}: {e}");
+                }
+            }
+        }
+    }
+
     /// Load built-in patterns embedded in the binary.
     fn load_builtin_patterns(&mut self) {
         // Built-in patterns are defined inline for now
         // In the future, these could be embedded via include_str!
         for pattern in builtin_patterns() {
-            self.patterns.insert(
+            self.raw.insert(
                 pattern.id.clone(),
                 LoadedPattern {
                     pattern,
@@ -119,23 +227,32 @@ impl PatternLoader {
         }
     }
 
-    /// Load patterns from user config directory.
+    /// Load patterns and fragments from user config directory.
     fn load_user_patterns(&mut self) -> Result<(), PatternError> {
         if let Some(config_dir) = dirs::config_dir() {
-            let patterns_dir = config_dir.join("draftkit").join("patterns");
+            let draftkit_dir = config_dir.join("draftkit");
+            let patterns_dir = draftkit_dir.join("patterns");
             if patterns_dir.exists() {
                 self.load_from_directory(&patterns_dir, PatternSource::User)?;
             }
+            let fragments_dir = draftkit_dir.join("fragments");
+            if fragments_dir.exists() {
+                self.load_fragments_from_directory(&fragments_dir)?;
+            }
         }
         Ok(())
     }
 
-    /// Load patterns from project-local .draftkit directory.
+    /// Load patterns and fragments from project-local .draftkit directory.
     fn load_project_patterns(&mut self) -> Result<(), PatternError> {
         let project_dir = PathBuf::from(".draftkit").join("patterns");
         if project_dir.exists() {
             self.load_from_directory(&project_dir, PatternSource::Project)?;
         }
+        let fragments_dir = PathBuf::from(".draftkit").join("fragments");
+        if fragments_dir.exists() {
+            self.load_fragments_from_directory(&fragments_dir)?;
+        }
         Ok(())
     }
 
@@ -152,7 +269,7 @@ impl PatternLoader {
             if path.extension().is_some_and(|ext| ext == "toml") {
                 match self.load_pattern_file(&path, source) {
                     Ok(loaded) => {
-                        self.patterns.insert(loaded.pattern.id.clone(), loaded);
+                        self.raw.insert(loaded.pattern.id.clone(), loaded);
                     }
                     Err(e) => {
                         // Log warning but continue loading other patterns
@@ -164,6 +281,26 @@ impl PatternLoader {
         Ok(())
     }
 
+    /// Load all fragment .toml files from a directory.
+    fn load_fragments_from_directory(&mut self, dir: &Path) -> Result<(), PatternError> {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if path.extension().is_some_and(|ext| ext == "toml") {
+                match load_fragment_file(&path) {
+                    Ok(fragment) => {
+                        self.fragments.insert(fragment.id.clone(), fragment);
+                    }
+                    Err(e) => {
+                        eprintln!("Warning: Failed to load fragment {}: {e}", path.display());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Load a single pattern file.
     fn load_pattern_file(
         &self,
@@ -185,14 +322,174 @@ impl PatternLoader {
     /// # Errors
     /// Returns error if pattern directories cannot be read.
     pub fn reload(&mut self) -> Result<(), PatternError> {
-        self.patterns.clear();
+        self.raw.clear();
+        self.fragments.clear();
         self.load_builtin_patterns();
         self.load_user_patterns()?;
         self.load_project_patterns()?;
+        self.resolve_all();
         Ok(())
     }
 }
 
+/// Load a single fragment file.
+fn load_fragment_file(path: &Path) -> Result<Fragment, PatternError> {
+    let content = std::fs::read_to_string(path)?;
+    let fragment_file: FragmentFile = toml::from_str(&content)?;
+    Ok(fragment_file.fragment)
+}
+
+/// Layer fragments and a child pattern's own sections onto its resolved parent.
+fn merge_pattern(
+    parent: Option<Pattern>,
+    fragments: &[&Fragment],
+    child: &Pattern,
+) -> Result<Pattern, PatternError> {
+    let mut resolved = child.clone();
+    let mut sections = Vec::new();
+
+    if let Some(parent) = parent {
+        sections = parent.sections;
+
+        let mut tags = parent.tags;
+        for tag in &child.tags {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        resolved.tags = tags;
+
+        if resolved.description.is_empty() {
+            resolved.description = parent.description;
+        }
+        if child.style_constraints.is_unspecified() {
+            resolved.style_constraints = parent.style_constraints;
+        }
+
+        let mut schemas = parent.schemas;
+        schemas.extend(child.schemas.clone());
+        resolved.schemas = schemas;
+
+        let mut rules = parent.rules;
+        extend_unique(&mut rules.coherence, &child.rules.coherence);
+        extend_unique(&mut rules.flow, &child.rules.flow);
+        extend_unique(&mut rules.accessibility, &child.rules.accessibility);
+        extend_unique(&mut rules.responsive, &child.rules.responsive);
+        resolved.rules = rules;
+    }
+
+    // Sections anchored on a type that isn't present yet are retried once
+    // everything else has been applied.
+    let mut deferred = Vec::new();
+
+    for fragment in fragments {
+        for section in &fragment.sections {
+            apply_section(&mut sections, section, &mut deferred)?;
+        }
+        for (name, schema) in &fragment.schemas {
+            resolved
+                .schemas
+                .entry(name.clone())
+                .or_insert_with(|| schema.clone());
+        }
+    }
+
+    let mut own = child.sections.clone();
+    own.sort_by_key(|s| s.position);
+    for section in &own {
+        apply_section(&mut sections, section, &mut deferred)?;
+    }
+
+    for section in deferred {
+        if !insert_at_anchor(&mut sections, &section) {
+            let (anchor, _) = section.merge.anchor().unwrap_or_default();
+            return Err(PatternError::ValidationError(format!(
+                "section '{}' in pattern '{}' is anchored on '{anchor}', which is not in the pattern",
+                section.section_type, child.id
+            )));
+        }
+    }
+
+    for (index, section) in sections.iter_mut().enumerate() {
+        section.position = u8::try_from(index).unwrap_or(u8::MAX);
+        section.merge = SectionMerge::default();
+    }
+    resolved.sections = sections;
+
+    Ok(resolved)
+}
+
+/// Apply one section's merge directives to the sections resolved so far.
+fn apply_section(
+    sections: &mut Vec<SectionSpec>,
+    section: &SectionSpec,
+    deferred: &mut Vec<SectionSpec>,
+) -> Result<(), PatternError> {
+    if section.merge.before.is_some() && section.merge.after.is_some() {
+        return Err(PatternError::ValidationError(format!(
+            "section '{}' sets both 'before' and 'after'",
+            section.section_type
+        )));
+    }
+
+    if section.merge.remove {
+        let count = sections.len();
+        sections.retain(|s| s.section_type != section.section_type);
+        if sections.len() == count {
+            return Err(PatternError::ValidationError(format!(
+                "cannot remove section '{}': it is not in the inherited pattern",
+                section.section_type
+            )));
+        }
+        return Ok(());
+    }
+
+    let existing = sections
+        .iter()
+        .position(|s| s.section_type == section.section_type);
+
+    match (existing, section.merge.anchor()) {
+        // Override in place
+        (Some(index), None) => sections[index] = section.clone(),
+        // Append a new section
+        (None, None) => sections.push(section.clone()),
+        // Insert or move next to an anchor
+        (existing, Some(_)) => {
+            if let Some(index) = existing {
+                sections.remove(index);
+            }
+            if !insert_at_anchor(sections, section) {
+                deferred.push(section.clone());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Insert a section next to its anchor. Returns false if the anchor is missing.
+fn insert_at_anchor(sections: &mut Vec<SectionSpec>, section: &SectionSpec) -> bool {
+    let Some((anchor, after)) = section.merge.anchor() else {
+        sections.push(section.clone());
+        return true;
+    };
+
+    let Some(index) = sections.iter().position(|s| s.section_type == anchor) else {
+        return false;
+    };
+    sections.insert(if after { index + 1 } else { index }, section.clone());
+    true
+}
+
+/// Append items from `source` that `dest` doesn't already contain.
+fn extend_unique(dest: &mut Vec<String>, source: &[String]) {
+    for item in source {
+        if !dest.contains(item) {
+            dest.push(item.clone());
+        }
+    }
+}
+
 impl Default for PatternLoader {
     fn default() -> Self {
         Self::builtin_only()
@@ -226,6 +523,8 @@ fn saas_landing_pattern() -> Pattern {
         ],
         author: "draftkit-core".to_string(),
         version: "1.0.0".to_string(),
+        extends: None,
+        include: vec![],
         style_constraints: StyleConstraints {
             visual_weight_variance: 0.3,
             spacing_density_variance: 0.2,
@@ -273,6 +572,7 @@ fn saas_landing_pattern() -> Pattern {
                     },
                 ],
                 constraints: vec![],
+                merge: SectionMerge::default(),
             },
             SectionSpec {
                 section_type: "hero".into(),
@@ -319,6 +619,7 @@ fn saas_landing_pattern() -> Pattern {
                     },
                 ],
                 constraints: vec!["must_include_cta".into()],
+                merge: SectionMerge::default(),
             },
             SectionSpec {
                 section_type: "features".into(),
@@ -356,6 +657,7 @@ fn saas_landing_pattern() -> Pattern {
                     },
                 ],
                 constraints: vec![],
+                merge: SectionMerge::default(),
             },
             SectionSpec {
                 section_type: "pricing".into(),
@@ -383,6 +685,7 @@ fn saas_landing_pattern() -> Pattern {
                     ..Default::default()
                 }],
                 constraints: vec![],
+                merge: SectionMerge::default(),
             },
             SectionSpec {
                 section_type: "cta".into(),
@@ -396,6 +699,7 @@ fn saas_landing_pattern() -> Pattern {
                 }],
                 slots: vec![],
                 constraints: vec![],
+                merge: SectionMerge::default(),
             },
             SectionSpec {
                 section_type: "footer".into(),
@@ -409,6 +713,7 @@ fn saas_landing_pattern() -> Pattern {
                 }],
                 slots: vec![],
                 constraints: vec![],
+                merge: SectionMerge::default(),
             },
         ],
         schemas: HashMap::from([(
@@ -460,6 +765,8 @@ fn marketing_pattern() -> Pattern {
         tags: vec!["marketing".into(), "product".into(), "showcase".into()],
         author: "draftkit-core".to_string(),
         version: "1.0.0".to_string(),
+        extends: None,
+        include: vec![],
         style_constraints: StyleConstraints {
             visual_weight_variance: 0.4,
             spacing_density_variance: 0.3,
@@ -479,6 +786,7 @@ fn marketing_pattern() -> Pattern {
                 }],
                 slots: vec![],
                 constraints: vec![],
+                merge: SectionMerge::default(),
             },
             SectionSpec {
                 section_type: "hero".into(),
@@ -492,6 +800,7 @@ fn marketing_pattern() -> Pattern {
                 }],
                 slots: vec![],
                 constraints: vec![],
+                merge: SectionMerge::default(),
             },
             SectionSpec {
                 section_type: "features".into(),
@@ -505,6 +814,7 @@ fn marketing_pattern() -> Pattern {
                 }],
                 slots: vec![],
                 constraints: vec![],
+                merge: SectionMerge::default(),
             },
             SectionSpec {
                 section_type: "testimonial".into(),
@@ -518,6 +828,7 @@ fn marketing_pattern() -> Pattern {
                 }],
                 slots: vec![],
                 constraints: vec![],
+                merge: SectionMerge::default(),
             },
             SectionSpec {
                 section_type: "cta".into(),
@@ -531,6 +842,7 @@ fn marketing_pattern() -> Pattern {
                 }],
                 slots: vec![],
                 constraints: vec![],
+                merge: SectionMerge::default(),
             },
             SectionSpec {
                 section_type: "footer".into(),
@@ -544,6 +856,7 @@ fn marketing_pattern() -> Pattern {
                 }],
                 slots: vec![],
                 constraints: vec![],
+                merge: SectionMerge::default(),
             },
         ],
         schemas: HashMap::new(),
@@ -562,6 +875,8 @@ fn portfolio_pattern() -> Pattern {
         tags: vec!["portfolio".into(), "personal".into(), "agency".into()],
         author: "draftkit-core".to_string(),
         version: "1.0.0".to_string(),
+        extends: None,
+        include: vec![],
         style_constraints: StyleConstraints {
             visual_weight_variance: 0.5,
             spacing_density_variance: 0.4,
@@ -581,6 +896,7 @@ fn portfolio_pattern() -> Pattern {
                 }],
                 slots: vec![],
                 constraints: vec![],
+                merge: SectionMerge::default(),
             },
             SectionSpec {
                 section_type: "hero".into(),
@@ -594,6 +910,7 @@ fn portfolio_pattern() -> Pattern {
                 }],
                 slots: vec![],
                 constraints: vec![],
+                merge: SectionMerge::default(),
             },
             SectionSpec {
                 section_type: "content".into(),
@@ -607,6 +924,7 @@ fn portfolio_pattern() -> Pattern {
                 }],
                 slots: vec![],
                 constraints: vec![],
+                merge: SectionMerge::default(),
             },
             SectionSpec {
                 section_type: "footer".into(),
@@ -620,6 +938,7 @@ fn portfolio_pattern() -> Pattern {
                 }],
                 slots: vec![],
                 constraints: vec![],
+                merge: SectionMerge::default(),
             },
         ],
         schemas: HashMap::new(),
@@ -666,4 +985,178 @@ mod tests {
         assert_eq!(saas.source, PatternSource::BuiltIn);
         assert!(saas.path.is_none());
     }
+
+    fn loader_with(patterns: &[(&str, &str)], fragments: &[(&str, &str)]) -> PatternLoader {
+        let dir = tempfile::TempDir::new().unwrap();
+        let patterns_dir = dir.path().join("patterns");
+        let fragments_dir = dir.path().join("fragments");
+        std::fs::create_dir_all(&patterns_dir).unwrap();
+        std::fs::create_dir_all(&fragments_dir).unwrap();
+        for (name, content) in patterns {
+            std::fs::write(patterns_dir.join(format!("{name}.toml")), content).unwrap();
+        }
+        for (name, content) in fragments {
+            std::fs::write(fragments_dir.join(format!("{name}.toml")), content).unwrap();
+        }
+
+        let mut loader = PatternLoader::empty();
+        loader.load_builtin_patterns();
+        loader
+            .load_from_directory(&patterns_dir, PatternSource::Project)
+            .unwrap();
+        loader
+            .load_fragments_from_directory(&fragments_dir)
+            .unwrap();
+        loader.resolve_all();
+        loader
+    }
+
+    fn section_types(pattern: &Pattern) -> Vec<&str> {
+        pattern
+            .sections
+            .iter()
+            .map(|s| s.section_type.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn extends_merges_sections() {
+        let loader = loader_with(
+            &[(
+                "saas-team",
+                r#"
+[pattern]
+id = "saas-team"
+name = "SaaS With Team"
+extends = "saas-landing"
+tags = ["team"]
+
+[[pattern.sections]]
+type = "team"
+after = "features"
+
+[[pattern.sections]]
+type = "hero"
+required = true
+
+[[pattern.sections.variants]]
+id = "hero-centered"
+
+[[pattern.sections]]
+type = "cta"
+remove = true
+"#,
+            )],
+            &[],
+        );
+
+        let resolved = &loader.get("saas-team").unwrap().pattern;
+        assert_eq!(
+            section_types(resolved),
+            ["header", "hero", "features", "team", "pricing", "footer"]
+        );
+        assert_eq!(resolved.sections[1].variants[0].id, "hero-centered");
+        let positions: Vec<u8> = resolved.sections.iter().map(|s| s.position).collect();
+        assert_eq!(positions, [0, 1, 2, 3, 4, 5]);
+
+        // Inherited metadata
+        assert!(resolved.tags.contains(&"saas".to_string()));
+        assert!(resolved.tags.contains(&"team".to_string()));
+        assert_eq!(resolved.style_constraints.formality_range, (0.6, 1.0));
+        assert!(resolved.schemas.contains_key("feature_item"));
+    }
+
+    #[test]
+    fn fragments_are_included() {
+        let loader = loader_with(
+            &[(
+                "landing",
+                r#"
+[pattern]
+id = "landing"
+name = "Landing"
+include = ["proof"]
+
+[[pattern.sections]]
+type = "hero"
+
+[[pattern.sections]]
+type = "features"
+"#,
+            )],
+            &[(
+                "proof",
+                r#"
+[fragment]
+id = "proof"
+
+[[fragment.sections]]
+type = "logos"
+after = "hero"
+"#,
+            )],
+        );
+
+        assert!(loader.get_fragment("proof").is_some());
+        let resolved = &loader.get("landing").unwrap().pattern;
+        assert_eq!(section_types(resolved), ["hero", "logos", "features"]);
+    }
+
+    #[test]
+    fn circular_inheritance_detected() {
+        let loader = loader_with(
+            &[
+                (
+                    "a",
+                    "[pattern]\nid = \"a\"\nname = \"A\"\nextends = \"b\"\n",
+                ),
+                (
+                    "b",
+                    "[pattern]\nid = \"b\"\nname = \"B\"\nextends = \"a\"\n",
+                ),
+            ],
+            &[],
+        );
+
+        assert!(loader.get("a").is_none());
+        assert!(matches!(
+            loader.resolve("a"),
+            Err(PatternError::CircularInheritance(chain)) if chain == "a -> b -> a"
+        ));
+    }
+
+    #[test]
+    fn unknown_fragment_and_anchor_rejected() {
+        let loader = loader_with(
+            &[
+                (
+                    "missing-fragment",
+                    "[pattern]\nid = \"missing-fragment\"\nname = \"M\"\ninclude = [\"nope\"]\n",
+                ),
+                (
+                    "bad-anchor",
+                    r#"
+[pattern]
+id = "bad-anchor"
+name = "Bad Anchor"
+extends = "saas-landing"
+
+[[pattern.sections]]
+type = "team"
+before = "gallery"
+"#,
+                ),
+            ],
+            &[],
+        );
+
+        assert!(matches!(
+            loader.resolve("missing-fragment"),
+            Err(PatternError::FragmentNotFound(id)) if id == "nope"
+        ));
+        assert!(matches!(
+            loader.resolve("bad-anchor"),
+            Err(PatternError::ValidationError(_))
+        ));
+    }
 }
@@ -13,6 +13,35 @@
 //! 2. **User** - `~/.config/draftkit/patterns/`
 //! 3. **Project** - `./.draftkit/patterns/`
 //!
+//! # Inheritance and Fragments
+//!
+//! A pattern can build on another with `extends = "saas-landing"`. Its
+//! sections are merged onto the parent's by section type: a matching type
+//! replaces the inherited section, `before`/`after` insert next to another
+//! section, and `remove = true` drops one. Reusable section fragments live
+//! in `fragments/` next to `patterns/` and are pulled in with `include`.
+//!
+//! ```toml
+//! [pattern]
+//! id = "saas-with-team"
+//! name = "SaaS With Team"
+//! extends = "saas-landing"
+//! include = ["social-proof"]
+//!
+//! [[pattern.sections]]
+//! type = "team"
+//! after = "features"
+//!
+//! [[pattern.sections]]
+//! type = "cta"
+//! remove = true
+//! ```
+//!
+//! # Inference
+//!
+//! [`infer_pattern`] reads an existing React or HTML page and produces a
+//! pattern whose sections, variant hints and style constraints mirror it.
+//!
 //! # Example Pattern Usage
 //!
 //! ```rust,no_run
@@ -34,11 +63,13 @@
 //! }
 //! ```
 
+mod infer;
 mod loader;
 mod schema;
 
+pub use infer::{InferredSection, PatternInference, infer_pattern};
 pub use loader::{LoadedPattern, PatternError, PatternLoader, PatternSource};
 pub use schema::{
-    CompositionRules, FieldSpec, Pattern, PatternFile, RepeatCount, SectionSpec, SlotSchema,
-    SlotSpec, SlotType, StyleConstraints, VariantSpec,
+    CompositionRules, FieldSpec, Fragment, FragmentFile, Pattern, PatternFile, RepeatCount,
+    SectionMerge, SectionSpec, SlotSchema, SlotSpec, SlotType, StyleConstraints, VariantSpec,
 };
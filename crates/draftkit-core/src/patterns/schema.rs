@@ -44,6 +44,14 @@ pub struct Pattern {
     #[serde(default = "default_version")]
     pub version: String,
 
+    /// Parent pattern to inherit sections from (e.g., "saas-landing")
+    #[serde(default)]
+    pub extends: Option<String>,
+
+    /// Section fragments merged into this pattern (by fragment ID)
+    #[serde(default)]
+    pub include: Vec<String>,
+
     /// Visual coherence constraints
     #[serde(default)]
     pub style_constraints: StyleConstraints,
@@ -66,7 +74,7 @@ fn default_version() -> String {
 }
 
 /// Visual constraints for style coherence validation.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct StyleConstraints {
     /// Maximum allowed variance in visual weight across sections (0.0-1.0)
@@ -86,6 +94,13 @@ pub struct StyleConstraints {
     pub color_intensity_range: (f32, f32),
 }
 
+impl StyleConstraints {
+    /// Whether the `[pattern.style_constraints]` table was omitted.
+    pub(crate) fn is_unspecified(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 const fn default_variance() -> f32 {
     0.3
 }
@@ -129,6 +144,10 @@ pub struct SectionSpec {
     /// Section-specific constraints (as string expressions)
     #[serde(default)]
     pub constraints: Vec<String>,
+
+    /// Merge directives used when layering onto an inherited pattern
+    #[serde(flatten)]
+    pub merge: SectionMerge,
 }
 
 impl SectionSpec {
@@ -152,6 +171,66 @@ impl SectionSpec {
     }
 }
 
+/// Merge directives for a section in a pattern that uses `extends` or `include`.
+///
+/// A section whose type already exists in the inherited sections replaces
+/// it in place. `before`/`after` insert (or move) the section next to another
+/// section type, and `remove` drops the inherited section of this type.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct SectionMerge {
+    /// Insert this section before the section of the given type
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+
+    /// Insert this section after the section of the given type
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+
+    /// Remove the inherited section of this type
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub remove: bool,
+}
+
+impl SectionMerge {
+    /// Get the anchor section type and whether to insert after it.
+    #[must_use]
+    pub fn anchor(&self) -> Option<(&str, bool)> {
+        self.before
+            .as_deref()
+            .map(|before| (before, false))
+            .or_else(|| self.after.as_deref().map(|after| (after, true)))
+    }
+}
+
+/// Root structure of a section fragment TOML file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct FragmentFile {
+    /// Fragment definition
+    pub fragment: Fragment,
+}
+
+/// A reusable group of sections that patterns pull in via `include`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct Fragment {
+    /// Unique identifier (e.g., "social-proof")
+    pub id: String,
+
+    /// Description of what this fragment adds
+    #[serde(default)]
+    pub description: String,
+
+    /// Sections merged into the including pattern
+    #[serde(default)]
+    pub sections: Vec<SectionSpec>,
+
+    /// Slot schemas used by the fragment's sections
+    #[serde(default)]
+    pub schemas: std::collections::HashMap<String, SlotSchema>,
+}
+
 /// How many times a section can repeat.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -368,7 +447,52 @@ formality_range = [0.7, 1.0]
             variants: vec![],
             slots: vec![],
             constraints: vec![],
+            merge: SectionMerge::default(),
         };
         assert_eq!(section.page_position(), Some(PagePosition::Hero));
     }
+
+    #[test]
+    fn parse_pattern_with_extends_and_merge() {
+        let toml = r#"
+[pattern]
+id = "saas-with-team"
+name = "SaaS With Team"
+extends = "saas-landing"
+include = ["social-proof"]
+
+[[pattern.sections]]
+type = "team"
+after = "features"
+
+[[pattern.sections]]
+type = "faq"
+remove = true
+"#;
+        let parsed: PatternFile = toml::from_str(toml).unwrap();
+        let pattern = parsed.pattern;
+        assert_eq!(pattern.extends.as_deref(), Some("saas-landing"));
+        assert_eq!(pattern.include, vec!["social-proof".to_string()]);
+        assert_eq!(pattern.sections[0].merge.anchor(), Some(("features", true)));
+        assert!(pattern.sections[1].merge.remove);
+        assert!(pattern.style_constraints.is_unspecified());
+    }
+
+    #[test]
+    fn parse_fragment() {
+        let toml = r#"
+[fragment]
+id = "social-proof"
+
+[[fragment.sections]]
+type = "logos"
+before = "features"
+"#;
+        let parsed: FragmentFile = toml::from_str(toml).unwrap();
+        assert_eq!(parsed.fragment.id, "social-proof");
+        assert_eq!(
+            parsed.fragment.sections[0].merge.anchor(),
+            Some(("features", false))
+        );
+    }
 }
@@ -13,6 +13,7 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use crate::preset::schema::{Preset, PresetFile, StyleOverrides};
+use crate::scaffold::IconOptions;
 
 /// Error type for preset operations.
 #[derive(Debug, thiserror::Error)]
@@ -247,6 +248,36 @@ impl PresetLoader {
         resolved
     }
 
+    /// Get the merged icon settings from all active presets.
+    ///
+    /// Later presets in the stack override earlier ones.
+    #[must_use]
+    pub fn merged_icon_options(&self) -> IconOptions {
+        let mut merged = IconOptions::default();
+
+        for name in &self.active_stack {
+            if let Some(loaded) = self.presets.get(name) {
+                merged.merge(&self.resolve_icon_options(&loaded.preset));
+            }
+        }
+
+        merged
+    }
+
+    /// Resolve icon settings for a preset, following inheritance chain.
+    fn resolve_icon_options(&self, preset: &Preset) -> IconOptions {
+        let mut resolved = if let Some(ref parent_name) = preset.extends
+            && let Some(parent) = self.presets.get(parent_name)
+        {
+            self.resolve_icon_options(&parent.preset)
+        } else {
+            IconOptions::default()
+        };
+
+        resolved.merge(&preset.icons);
+        resolved
+    }
+
     /// Check if a component is blacklisted by any active preset.
     #[must_use]
     pub fn is_component_blacklisted(&self, component_id: &str) -> bool {
@@ -478,6 +509,7 @@ fn minimalist_preset() -> Preset {
             categories: vec![],
         },
         whitelist: Whitelist::default(),
+        icons: IconOptions::default(),
     }
 }
 
@@ -516,6 +548,7 @@ fn neubrutalism_preset() -> Preset {
             tags: vec!["bold".into(), "shadow".into()],
             ..Default::default()
         },
+        icons: IconOptions::default(),
     }
 }
 
@@ -551,6 +584,7 @@ fn corporate_preset() -> Preset {
             ..Default::default()
         },
         whitelist: Whitelist::default(),
+        icons: IconOptions::default(),
     }
 }
 
@@ -628,6 +662,43 @@ mod tests {
         assert_eq!(merged.get("hero"), Some(&"hero-centered-cta".to_string()));
     }
 
+    #[test]
+    fn merged_icon_options_follow_inheritance() {
+        let mut loader = PresetLoader::builtin_only();
+        let file: PresetFile = toml::from_str(
+            r#"[preset]
+name = "Outline"
+extends = "Minimalist"
+
+[preset.icons]
+library = "tabler"
+
+[preset.icons.mappings]
+Bars3Icon = "IconMenu"
+"#,
+        )
+        .unwrap();
+        loader.presets.insert(
+            "Outline".to_string(),
+            LoadedPreset {
+                preset: file.preset,
+                source: PresetSource::Project,
+                path: None,
+            },
+        );
+
+        loader.activate("Outline").unwrap();
+        let icons = loader.merged_icon_options();
+        assert_eq!(icons.library, Some(crate::scaffold::IconLibrary::Tabler));
+        assert_eq!(
+            icons.mappings.get("Bars3Icon").map(String::as_str),
+            Some("IconMenu")
+        );
+
+        loader.set_stack(vec!["Corporate".to_string()]).unwrap();
+        assert_eq!(loader.merged_icon_options(), IconOptions::default());
+    }
+
     #[test]
     fn blacklist_check() {
         let mut loader = PresetLoader::builtin_only();
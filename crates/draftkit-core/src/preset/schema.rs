@@ -9,6 +9,8 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::scaffold::IconOptions;
+
 /// Root structure of a preset TOML file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -23,6 +25,7 @@ pub struct PresetFile {
 /// - Style overrides constrain component selection by design DNA
 /// - Variant preferences specify which component variants to prefer
 /// - Blacklists exclude specific components entirely
+/// - Icon settings swap Heroicons for another icon library
 /// - Inheritance allows presets to extend others
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -65,6 +68,10 @@ pub struct Preset {
     /// Components to always prefer (opposite of blacklist)
     #[serde(default)]
     pub whitelist: Whitelist,
+
+    /// Icon library for generated code, with per-icon overrides
+    #[serde(default)]
+    pub icons: IconOptions,
 }
 
 fn default_version() -> String {
@@ -84,6 +91,7 @@ impl Default for Preset {
             variant_preferences: HashMap::new(),
             blacklist: Blacklist::default(),
             whitelist: Whitelist::default(),
+            icons: IconOptions::default(),
         }
     }
 }
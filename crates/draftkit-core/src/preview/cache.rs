@@ -13,9 +13,11 @@
 //! Images are cached per-component, per-mode to enable incremental caching.
 
 use std::fs;
+use std::time::{Duration, SystemTime};
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 
+use crate::cache::{CacheProblem, CacheProblemKind, PruneStats, prune_dir, verify_dir};
 use crate::components::Mode;
 use crate::data_dir::data_dir;
 
@@ -28,9 +30,13 @@ pub fn previews_cache_dir() -> Option<Utf8PathBuf> {
 /// Get the path for a cached preview image.
 #[must_use]
 pub fn preview_cache_path(component_id: &str, mode: Mode) -> Option<Utf8PathBuf> {
-    let dir = previews_cache_dir()?;
-    let filename = format!("{}.png", mode.as_str());
-    Some(dir.join(component_id).join(filename))
+    Some(image_path(&previews_cache_dir()?, component_id, mode))
+}
+
+/// Path of a preview image under the previews directory `dir`.
+fn image_path(dir: &Utf8Path, component_id: &str, mode: Mode) -> Utf8PathBuf {
+    dir.join(component_id)
+        .join(format!("{}.png", mode.as_str()))
 }
 
 /// Check if a preview image is cached.
@@ -45,8 +51,13 @@ pub fn is_cached(component_id: &str, mode: Mode) -> bool {
 /// Get cached preview image bytes if it exists.
 #[must_use]
 pub fn get_cached(component_id: &str, mode: Mode) -> Option<Vec<u8>> {
-    let path = preview_cache_path(component_id, mode)?;
-    fs::read(path.as_std_path()).ok()
+    get_cached_in(&previews_cache_dir()?, component_id, mode)
+}
+
+/// Get cached preview image bytes from the previews directory `dir`.
+#[must_use]
+pub fn get_cached_in(dir: &Utf8Path, component_id: &str, mode: Mode) -> Option<Vec<u8>> {
+    fs::read(image_path(dir, component_id, mode)).ok()
 }
 
 /// Store preview image bytes in the cache.
@@ -57,13 +68,27 @@ pub fn get_cached(component_id: &str, mode: Mode) -> Option<Vec<u8>> {
 ///
 /// Returns an error if the cache directory cannot be created or the file cannot be written.
 pub fn store_cached(component_id: &str, mode: Mode, data: &[u8]) -> std::io::Result<Utf8PathBuf> {
-    let path = preview_cache_path(component_id, mode).ok_or_else(|| {
+    let dir = previews_cache_dir().ok_or_else(|| {
         std::io::Error::new(
             std::io::ErrorKind::NotFound,
             "Cannot determine previews cache directory",
         )
     })?;
+    store_cached_in(&dir, component_id, mode, data)
+}
 
+/// Store preview image bytes in the previews directory `dir`.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be created or the file cannot be written.
+pub fn store_cached_in(
+    dir: &Utf8Path,
+    component_id: &str,
+    mode: Mode,
+    data: &[u8],
+) -> std::io::Result<Utf8PathBuf> {
+    let path = image_path(dir, component_id, mode);
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent.as_std_path())?;
     }
@@ -141,6 +166,47 @@ pub fn clear_cache() -> std::io::Result<u64> {
     Ok(bytes_freed)
 }
 
+/// Remove cached preview images older than `max_age`.
+///
+/// Entries that can't be read or removed are counted in
+/// [`PruneStats::skipped`].
+#[must_use]
+pub fn prune_older_than(max_age: Duration) -> PruneStats {
+    let Some(dir) = previews_cache_dir() else {
+        return PruneStats::default();
+    };
+    let cutoff = SystemTime::now()
+        .checked_sub(max_age)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    prune_dir(&dir, cutoff)
+}
+
+/// PNG file signature
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+fn verify_in(dir: &Utf8Path) -> Vec<CacheProblem> {
+    verify_dir(dir, |name, path| {
+        let known = name
+            .strip_suffix(".png")
+            .is_some_and(|stem| Mode::parse(stem).is_some_and(|mode| mode.as_str() == stem));
+        if !known {
+            return Some(CacheProblemKind::UnknownFile);
+        }
+        match fs::read(path) {
+            Ok(data) if data.is_empty() => Some(CacheProblemKind::EmptyFile),
+            Ok(data) if !data.starts_with(PNG_SIGNATURE) => Some(CacheProblemKind::NotAnImage),
+            Ok(_) => None,
+            Err(_) => Some(CacheProblemKind::Unreadable),
+        }
+    })
+}
+
+/// Check every cached preview image, returning the damaged or stray ones.
+#[must_use]
+pub fn verify() -> Vec<CacheProblem> {
+    previews_cache_dir().map_or_else(Vec::new, |dir| verify_in(&dir))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,4 +245,34 @@ mod tests {
         assert_eq!(stats.total_bytes, 0);
         assert_eq!(stats.component_count, 0);
     }
+
+    #[test]
+    fn test_verify_in() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = Utf8Path::from_path(tmp.path()).unwrap();
+        let mut png = PNG_SIGNATURE.to_vec();
+        png.extend_from_slice(b"IHDR");
+        for (id, name, data) in [
+            ("good", "light.png", png.as_slice()),
+            ("empty", "dark.png", b"".as_slice()),
+            ("html", "light.png", b"<html>".as_slice()),
+            ("stray", "Light.png", png.as_slice()),
+        ] {
+            fs::create_dir_all(dir.join(id)).unwrap();
+            fs::write(dir.join(id).join(name), data).unwrap();
+        }
+
+        let kinds: Vec<(String, CacheProblemKind)> = verify_in(dir)
+            .into_iter()
+            .map(|p| (p.path.strip_prefix(dir).unwrap().to_string(), p.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("empty/dark.png".into(), CacheProblemKind::EmptyFile),
+                ("html/light.png".into(), CacheProblemKind::NotAnImage),
+                ("stray/Light.png".into(), CacheProblemKind::UnknownFile),
+            ]
+        );
+    }
 }
@@ -6,13 +6,18 @@
 use std::io::Cursor;
 
 use base64::Engine;
+use camino::Utf8PathBuf;
 use image::codecs::png::PngEncoder;
 use image::{DynamicImage, ImageEncoder, ImageReader};
-use reqwest::Client;
+use reqwest::{Method, Request, Url};
 
 use super::cache;
 use super::error::PreviewError;
 use crate::components::Mode;
+use crate::http::{self, HttpClient, SharedHttpClient};
+
+/// User agent for preview downloads
+const USER_AGENT: &str = "draftkit-preview/1.0";
 
 /// A source for a preview image.
 #[derive(Debug, Clone)]
@@ -49,7 +54,8 @@ impl PreviewImage {
 ///
 /// Downloads component preview images and stitches them vertically.
 pub struct CompositePreview {
-    client: Client,
+    client: SharedHttpClient,
+    cache_dir: Option<Utf8PathBuf>,
 }
 
 impl Default for CompositePreview {
@@ -62,12 +68,24 @@ impl CompositePreview {
     /// Create a new composite preview generator.
     #[must_use]
     pub fn new() -> Self {
-        let client = Client::builder()
-            .user_agent("draftkit-preview/1.0")
-            .build()
-            .expect("Failed to create HTTP client");
+        Self {
+            client: http::default_client(USER_AGENT, false),
+            cache_dir: None,
+        }
+    }
 
-        Self { client }
+    /// Download images through `client` instead of the default `reqwest` client.
+    #[must_use]
+    pub fn with_http_client(mut self, client: impl HttpClient + 'static) -> Self {
+        self.client = std::sync::Arc::new(client);
+        self
+    }
+
+    /// Cache images under `dir/previews` instead of the data directory.
+    #[must_use]
+    pub fn with_cache_dir(mut self, dir: impl Into<Utf8PathBuf>) -> Self {
+        self.cache_dir = Some(dir.into());
+        self
     }
 
     /// Generate a composite preview from multiple component previews.
@@ -127,7 +145,12 @@ impl CompositePreview {
         mode: Mode,
     ) -> Result<DynamicImage, PreviewError> {
         // Check cache first
-        if let Some(cached_bytes) = cache::get_cached(&source.component_id, mode) {
+        let previews_dir = self.cache_dir.as_ref().map(|dir| dir.join("previews"));
+        let cached = previews_dir.as_ref().map_or_else(
+            || cache::get_cached(&source.component_id, mode),
+            |dir| cache::get_cached_in(dir, &source.component_id, mode),
+        );
+        if let Some(cached_bytes) = cached {
             return self.decode_image(&cached_bytes);
         }
 
@@ -135,7 +158,11 @@ impl CompositePreview {
         let bytes = self.fetch_image(&source.preview_url).await?;
 
         // Cache the downloaded image
-        if let Err(e) = cache::store_cached(&source.component_id, mode, &bytes) {
+        let stored = previews_dir.as_ref().map_or_else(
+            || cache::store_cached(&source.component_id, mode, &bytes),
+            |dir| cache::store_cached_in(dir, &source.component_id, mode, &bytes),
+        );
+        if let Err(e) = stored {
             // Log but don't fail on cache errors
             eprintln!(
                 "Warning: Failed to cache preview for {}: {}",
@@ -148,10 +175,11 @@ impl CompositePreview {
 
     /// Fetch image bytes from a URL.
     async fn fetch_image(&self, url: &str) -> Result<Vec<u8>, PreviewError> {
+        let parsed = Url::parse(url)
+            .map_err(|e| PreviewError::Download(format!("Invalid URL {url}: {e}")))?;
         let response = self
             .client
-            .get(url)
-            .send()
+            .execute(Request::new(Method::GET, parsed))
             .await
             .map_err(PreviewError::Http)?;
 
@@ -39,7 +39,7 @@ mod rendered;
 
 pub use cache::{
     PreviewCacheStats, clear_cache as clear_preview_cache, get_stats as get_preview_cache_stats,
-    previews_cache_dir,
+    previews_cache_dir, prune_older_than as prune_preview_cache, verify as verify_preview_cache,
 };
 pub use composite::{CompositePreview, PreviewImage, PreviewSource};
 pub use error::PreviewError;
@@ -0,0 +1,203 @@
+//! Named profiles for working with several TailwindPlus accounts.
+//!
+//! Each profile has its own session and component cache. The `default`
+//! profile uses the data directory itself, so installs from before profiles
+//! existed keep working; other profiles live under `profiles/{name}/`:
+//! ```text
+//! ~/.local/share/draftkit/
+//! ├── cache/                  # default profile
+//! └── profiles/
+//!     └── client-a/
+//!         ├── manifest.json   # optional, overrides the shared manifest
+//!         └── cache/
+//! ```
+//!
+//! A profile's directory is created when its session is first stored, so
+//! [`list_profiles`] finds profiles whose session lives in the OS keyring.
+//!
+//! The active profile comes from the `--profile` flag, then
+//! `DRAFTKIT_PROFILE`, then the `profile` config key; see [`select_profile`].
+
+use std::sync::OnceLock;
+
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::data_dir::data_dir;
+
+/// Name of the profile used when none is selected
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Environment variable selecting the profile
+pub const PROFILE_ENV: &str = "DRAFTKIT_PROFILE";
+
+/// Directory within the data directory holding non-default profiles
+const PROFILES_DIR: &str = "profiles";
+
+/// Profile selected for this process
+static ACTIVE: OnceLock<String> = OnceLock::new();
+
+/// Errors selecting a profile.
+#[derive(Debug, thiserror::Error)]
+pub enum ProfileError {
+    /// Profile names become directory names, so they are restricted.
+    #[error(
+        "Invalid profile name '{0}': use letters, digits, '-' and '_', starting with a letter or digit"
+    )]
+    InvalidName(String),
+}
+
+/// Check that `name` is usable as a profile name.
+///
+/// # Errors
+///
+/// Returns [`ProfileError::InvalidName`] unless `name` is ASCII letters,
+/// digits, `-` and `_`, starting with a letter or digit.
+pub fn validate_name(name: &str) -> Result<(), ProfileError> {
+    let valid = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphanumeric())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(ProfileError::InvalidName(name.to_string()))
+    }
+}
+
+/// Select the active profile for this process.
+///
+/// `flag` wins over `DRAFTKIT_PROFILE`, which wins over `configured`. The
+/// first call decides; later calls return the profile already selected.
+///
+/// # Errors
+///
+/// Returns [`ProfileError::InvalidName`] if the chosen name is invalid.
+pub fn select_profile(
+    flag: Option<&str>,
+    configured: Option<&str>,
+) -> Result<&'static str, ProfileError> {
+    let name = flag
+        .map(str::to_string)
+        .or_else(|| std::env::var(PROFILE_ENV).ok().filter(|p| !p.is_empty()))
+        .or_else(|| configured.map(str::to_string))
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+    validate_name(&name)?;
+    Ok(ACTIVE.get_or_init(|| name))
+}
+
+/// The active profile, or [`DEFAULT_PROFILE`] if none was selected.
+#[must_use]
+pub fn active_profile() -> &'static str {
+    ACTIVE.get().map_or(DEFAULT_PROFILE, String::as_str)
+}
+
+/// Directory holding the session and cache of `profile`.
+///
+/// Returns `None` if the data directory cannot be determined.
+#[must_use]
+pub fn profile_dir(profile: &str) -> Option<Utf8PathBuf> {
+    data_dir().map(|dir| profile_dir_in(dir, profile))
+}
+
+/// Directory of `profile` within the data directory `data`.
+#[must_use]
+pub fn profile_dir_in(data: &Utf8Path, profile: &str) -> Utf8PathBuf {
+    if profile == DEFAULT_PROFILE {
+        data.to_path_buf()
+    } else {
+        data.join(PROFILES_DIR).join(profile)
+    }
+}
+
+/// Create the directory of `profile` if it doesn't exist yet.
+///
+/// # Errors
+///
+/// Returns an error if the data directory cannot be determined or the
+/// directory cannot be created.
+pub fn create_profile_dir(profile: &str) -> std::io::Result<Utf8PathBuf> {
+    let dir = profile_dir(profile).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "Could not determine data directory",
+        )
+    })?;
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Directory of the active profile.
+#[must_use]
+pub fn active_profile_dir() -> Option<Utf8PathBuf> {
+    profile_dir(active_profile())
+}
+
+/// Known profiles: the default one, then those with a directory, by name.
+#[must_use]
+pub fn list_profiles() -> Vec<String> {
+    list_profiles_in(data_dir())
+}
+
+fn list_profiles_in(data: Option<&Utf8Path>) -> Vec<String> {
+    let mut named: Vec<String> = data
+        .and_then(|dir| dir.join(PROFILES_DIR).read_dir_utf8().ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|entry| entry.file_name().to_string())
+        .filter(|name| name != DEFAULT_PROFILE && validate_name(name).is_ok())
+        .collect();
+    named.sort();
+    std::iter::once(DEFAULT_PROFILE.to_string())
+        .chain(named)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        for name in ["default", "client-a", "team_2", "A1"] {
+            assert!(validate_name(name).is_ok(), "{name}");
+        }
+        for name in ["", "-leading", "../escape", "with space", "dot.ted"] {
+            assert!(validate_name(name).is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_profile_dir() {
+        let data = Utf8Path::new("/data/draftkit");
+        assert_eq!(profile_dir_in(data, DEFAULT_PROFILE), data);
+        assert_eq!(
+            profile_dir_in(data, "client-a"),
+            data.join("profiles/client-a")
+        );
+    }
+
+    #[test]
+    fn test_list_profiles_starts_with_default() {
+        assert_eq!(list_profiles()[0], DEFAULT_PROFILE);
+        assert_eq!(list_profiles_in(None), [DEFAULT_PROFILE]);
+    }
+
+    #[test]
+    fn test_list_profiles_reads_profile_dirs() {
+        let temp = tempfile::tempdir().unwrap();
+        let data = Utf8Path::from_path(temp.path()).unwrap();
+        for name in ["client-b", "client-a", "dot.ted"] {
+            std::fs::create_dir_all(profile_dir_in(data, name)).unwrap();
+        }
+        std::fs::write(data.join("profiles/notes.txt"), "").unwrap();
+        assert_eq!(
+            list_profiles_in(Some(data)),
+            [DEFAULT_PROFILE, "client-a", "client-b"]
+        );
+    }
+}
@@ -0,0 +1,826 @@
+//! Adopting existing projects.
+//!
+//! `init` scaffolds into an empty directory and `generate` owns the
+//! framework's main page. Adoption works the other way around: it inspects a
+//! project that already exists, reports the packages draftkit needs that it
+//! lacks, and places generated pages in new files only.
+//!
+//! Inspection covers:
+//! - `package.json` (name, framework, dependencies)
+//! - Tailwind version and whether its build plugin or config is present
+//! - `tsconfig` path aliases, so section imports match the project's style
+//! - `src/` layout and the enclosing monorepo workspace, if any
+
+use std::collections::BTreeSet;
+use std::fs;
+
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
+
+use super::dependencies::{ProjectVersions, compatible_version, package_name, stage_dependencies};
+use super::{FrameworkTarget, GenerateError, PackageManager, ProjectConfig, VirtualFs, Workspace};
+use crate::components::TailwindVersion;
+
+/// Config files that may declare `compilerOptions.paths`, in lookup order.
+const TSCONFIG_FILES: &[&str] = &["tsconfig.json", "tsconfig.app.json", "jsconfig.json"];
+
+/// Tailwind v3 config file names.
+const TAILWIND_V3_CONFIGS: &[&str] = &[
+    "tailwind.config.js",
+    "tailwind.config.cjs",
+    "tailwind.config.mjs",
+    "tailwind.config.ts",
+];
+
+/// Packages that wire Tailwind v4 into a build.
+const TAILWIND_V4_PLUGINS: &[&str] = &[
+    "@tailwindcss/vite",
+    "@tailwindcss/postcss",
+    "@tailwindcss/cli",
+    "@nuxtjs/tailwindcss",
+];
+
+/// A `tsconfig` path alias, such as `@/*` → `./src/*`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportAlias {
+    /// Specifier prefix including the trailing slash (e.g., "@/")
+    pub prefix: String,
+    /// Absolute directory the prefix maps to
+    pub dir: Utf8PathBuf,
+}
+
+impl ImportAlias {
+    /// Create an alias mapping `prefix` to `dir`.
+    pub fn new(prefix: impl Into<String>, dir: impl Into<Utf8PathBuf>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            dir: dir.into(),
+        }
+    }
+
+    /// Module specifier for `file` through this alias, without extension.
+    ///
+    /// Returns `None` when the file is outside the aliased directory.
+    #[must_use]
+    pub fn specifier(&self, file: &Utf8Path) -> Option<String> {
+        let target = file.with_extension("");
+        let rest = target.strip_prefix(&self.dir).ok()?;
+        let parts: Vec<&str> = rest.components().map(|c| c.as_str()).collect();
+        Some(format!("{}{}", self.prefix, parts.join("/")))
+    }
+}
+
+/// A package the project needs before generated pages will build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingPackage {
+    /// NPM package name
+    pub name: String,
+    /// Version range compatible with the project
+    pub version: String,
+    /// Install as a dev dependency
+    pub dev: bool,
+}
+
+impl MissingPackage {
+    /// `name@version`, as passed to a package manager's add command.
+    #[must_use]
+    pub fn spec(&self) -> String {
+        format!("{}@{}", self.name, self.version)
+    }
+}
+
+/// What draftkit found in an existing project.
+#[derive(Debug, Clone)]
+pub struct ProjectInspection {
+    /// Project root (the directory holding `package.json`)
+    pub root: Utf8PathBuf,
+    /// Package name, falling back to the directory name
+    pub name: String,
+    /// Detected framework
+    pub framework: FrameworkTarget,
+    /// Package manager, detected at the workspace root for monorepo packages
+    pub package_manager: PackageManager,
+    /// Installed Tailwind version (None when Tailwind is not a dependency)
+    pub tailwind: Option<TailwindVersion>,
+    /// Whether Tailwind is wired into the build (v4 plugin or v3 config)
+    pub tailwind_configured: bool,
+    /// Path alias from `tsconfig`, used for section imports
+    pub import_alias: Option<ImportAlias>,
+    /// Whether sources live under `src/`
+    pub src_layout: bool,
+    /// Enclosing monorepo when the project is a workspace package
+    pub workspace: Option<Workspace>,
+    /// Declared dependencies, including the workspace root's
+    pub dependencies: BTreeSet<String>,
+    /// React, Vue and Tailwind versions that decide compatible pins
+    pub versions: ProjectVersions,
+}
+
+impl ProjectInspection {
+    /// Inspect the project rooted at `root`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotAProject` when `root` has no `package.json`, and `Io` when
+    /// it cannot be read or parsed.
+    pub fn inspect(root: &Utf8Path) -> Result<Self, GenerateError> {
+        let package_json = read_package_json(&root.join("package.json"))?
+            .ok_or_else(|| GenerateError::NotAProject(root.to_path_buf()))?;
+
+        let name = package_json["name"]
+            .as_str()
+            .or_else(|| root.file_name())
+            .unwrap_or("project")
+            .to_string();
+        let framework = FrameworkTarget::detect(root, &package_json);
+
+        let workspace = root.parent().and_then(Workspace::find);
+        let root_json = match &workspace {
+            Some(workspace) => read_package_json(&workspace.root.join("package.json"))?,
+            None => None,
+        };
+        let package_manager = PackageManager::detect(root, None);
+
+        let mut dependencies = dependency_names(&package_json);
+        if let Some(json) = &root_json {
+            dependencies.extend(dependency_names(json));
+        }
+
+        let mut versions = ProjectVersions::from_package_json(&package_json);
+        if let Some(json) = &root_json {
+            versions = versions.or(ProjectVersions::from_package_json(json));
+        }
+
+        let tailwind = tailwind_dependency_version(&package_json)
+            .or_else(|| root_json.as_ref().and_then(tailwind_dependency_version));
+        let tailwind_configured = match tailwind {
+            Some(TailwindVersion::V4) => TAILWIND_V4_PLUGINS
+                .iter()
+                .any(|plugin| dependencies.contains(*plugin)),
+            Some(TailwindVersion::V3) => TAILWIND_V3_CONFIGS
+                .iter()
+                .any(|file| root.join(file).exists()),
+            None => false,
+        };
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            name,
+            framework,
+            package_manager,
+            tailwind,
+            tailwind_configured,
+            import_alias: find_import_alias(root),
+            src_layout: root.join("src").is_dir(),
+            workspace,
+            dependencies,
+            versions,
+        })
+    }
+
+    /// Whether `name` is declared in the project or its workspace root.
+    #[must_use]
+    pub fn has_dependency(&self, name: &str) -> bool {
+        self.dependencies.contains(name)
+    }
+
+    /// Command adding `packages` to the project, run from [`Self::command_dir`].
+    #[must_use]
+    pub fn add_cmd(&self, packages: &[&str], dev: bool) -> Vec<String> {
+        self.workspace.as_ref().map_or_else(
+            || self.package_manager.add_cmd(packages, dev),
+            |workspace| {
+                self.package_manager.workspace_add_cmd(
+                    packages,
+                    dev,
+                    &self.name,
+                    &workspace.member_path(&self.root).unwrap_or_default(),
+                )
+            },
+        )
+    }
+
+    /// Where package manager commands run: the workspace root for monorepo
+    /// packages, so installs update the shared lockfile.
+    #[must_use]
+    pub fn command_dir(&self) -> &Utf8Path {
+        self.workspace
+            .as_ref()
+            .map_or(&self.root, |workspace| &workspace.root)
+    }
+
+    /// A project config pointing at the inspected project.
+    #[must_use]
+    pub fn project_config(&self) -> ProjectConfig {
+        let mut config = ProjectConfig::new(&self.name, self.root.parent().unwrap_or(&self.root))
+            .with_framework(self.framework)
+            .with_package_manager(self.package_manager)
+            .with_tailwind_version(self.tailwind.unwrap_or_default());
+        config.path = self.root.clone();
+        config
+    }
+
+    /// Packages generated pages need that the project does not declare.
+    ///
+    /// Covers Headless UI and Heroicons for the framework, Tailwind itself,
+    /// and the Tailwind v4 build plugin when none is installed.
+    #[must_use]
+    pub fn missing_packages(&self) -> Vec<MissingPackage> {
+        let mut wanted: Vec<(&str, bool)> = match self.framework {
+            FrameworkTarget::Html => vec![],
+            FrameworkTarget::ViteVue | FrameworkTarget::Nuxt => {
+                vec![("@headlessui/vue", false), ("@heroicons/vue", false)]
+            }
+            FrameworkTarget::ViteReact
+            | FrameworkTarget::NextJs
+            | FrameworkTarget::ReactRouter
+            | FrameworkTarget::Astro => {
+                vec![("@headlessui/react", false), ("@heroicons/react", false)]
+            }
+        };
+
+        if self.tailwind.is_none() {
+            wanted.push(("tailwindcss", true));
+        }
+        if self.tailwind.unwrap_or_default() == TailwindVersion::V4 && !self.tailwind_configured {
+            wanted.push((self.tailwind_plugin(), true));
+        }
+
+        wanted
+            .into_iter()
+            .filter(|(name, _)| !self.has_dependency(name))
+            .map(|(name, dev)| self.pin(name, dev))
+            .collect()
+    }
+
+    /// Packages imported by generated code that the project does not
+    /// declare, deduplicated and sorted by name.
+    ///
+    /// `imports` are import specifiers from component metadata; subpaths
+    /// and relative imports are handled by [`package_name`].
+    #[must_use]
+    pub fn missing_dependencies(&self, imports: &[String]) -> Vec<MissingPackage> {
+        imports
+            .iter()
+            .filter_map(|import| package_name(import))
+            .filter(|name| !self.has_dependency(name))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|name| self.pin(name, false))
+            .collect()
+    }
+
+    /// Stage this project's `package.json` with `packages` declared.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Io` when `package.json` cannot be read or updated.
+    pub fn stage_dependencies(
+        &self,
+        packages: &[MissingPackage],
+        vfs: &mut VirtualFs,
+    ) -> Result<bool, GenerateError> {
+        stage_dependencies(&self.root.join("package.json"), packages, vfs)
+    }
+
+    /// `name` pinned to the range compatible with this project.
+    fn pin(&self, name: &str, dev: bool) -> MissingPackage {
+        MissingPackage {
+            name: name.to_string(),
+            version: compatible_version(name, &self.versions).to_string(),
+            dev,
+        }
+    }
+
+    /// Manual steps the project still needs, since adoption never edits
+    /// existing files.
+    #[must_use]
+    pub fn setup_hints(&self) -> Vec<String> {
+        let mut hints = Vec::new();
+
+        match self.tailwind {
+            Some(TailwindVersion::V3) if !self.tailwind_configured => {
+                hints.push(
+                    "Create a Tailwind config covering the generated files (npx tailwindcss init -p)"
+                        .to_string(),
+                );
+            }
+            Some(TailwindVersion::V3) => {}
+            Some(TailwindVersion::V4) | None if !self.tailwind_configured => {
+                hints.push(match self.framework {
+                    FrameworkTarget::Html => {
+                        "Build your CSS with npx @tailwindcss/cli -i input.css -o output.css"
+                            .to_string()
+                    }
+                    FrameworkTarget::NextJs => {
+                        "Add '@tailwindcss/postcss' to the plugins in postcss.config.mjs"
+                            .to_string()
+                    }
+                    FrameworkTarget::Nuxt => {
+                        "Add tailwindcss() from '@tailwindcss/vite' to vite.plugins in nuxt.config.ts"
+                            .to_string()
+                    }
+                    FrameworkTarget::Astro => {
+                        "Add tailwindcss() from '@tailwindcss/vite' to vite.plugins in astro.config.mjs"
+                            .to_string()
+                    }
+                    FrameworkTarget::ViteReact
+                    | FrameworkTarget::ViteVue
+                    | FrameworkTarget::ReactRouter => {
+                        "Add tailwindcss() from '@tailwindcss/vite' to the plugins in your Vite config"
+                            .to_string()
+                    }
+                });
+                if self.tailwind.is_none() {
+                    hints.push(
+                        "Import Tailwind in your global stylesheet: @import \"tailwindcss\";"
+                            .to_string(),
+                    );
+                }
+            }
+            Some(TailwindVersion::V4) | None => {}
+        }
+
+        if self.framework == FrameworkTarget::Astro
+            && !self.root.join("src/layouts/Layout.astro").exists()
+        {
+            hints.push(
+                "Generated Astro pages use src/layouts/Layout.astro; create it or change the import"
+                    .to_string(),
+            );
+        }
+
+        hints
+    }
+
+    /// Where a new page for `route` goes, following the framework's routing
+    /// conventions.
+    #[must_use]
+    pub fn page_path(&self, route: &str) -> Utf8PathBuf {
+        let segments = route_segments(route);
+        let slug = segments.join("/");
+        let file = |ext: &str, index: &str| {
+            if slug.is_empty() {
+                format!("{index}.{ext}")
+            } else {
+                format!("{slug}.{ext}")
+            }
+        };
+
+        match self.framework {
+            FrameworkTarget::Html => self.root.join(file("html", "index")),
+            FrameworkTarget::ViteReact => self
+                .source_dir()
+                .join("pages")
+                .join(format!("{}Page.tsx", page_component_name(&segments))),
+            FrameworkTarget::ViteVue => self
+                .source_dir()
+                .join("pages")
+                .join(format!("{}Page.vue", page_component_name(&segments))),
+            FrameworkTarget::NextJs => {
+                let app_dir = if self.root.join("src/app").is_dir() {
+                    self.root.join("src/app")
+                } else {
+                    self.root.join("app")
+                };
+                segments
+                    .iter()
+                    .fold(app_dir, |dir, segment| dir.join(segment))
+                    .join("page.tsx")
+            }
+            FrameworkTarget::Nuxt => self.root.join("pages").join(file("vue", "index")),
+            FrameworkTarget::ReactRouter => {
+                let name = if segments.is_empty() {
+                    "home".to_string()
+                } else {
+                    segments.join(".")
+                };
+                self.root.join("app/routes").join(format!("{name}.tsx"))
+            }
+            FrameworkTarget::Astro => self.root.join("src/pages").join(file("astro", "index")),
+        }
+    }
+
+    /// Directory for the section files of the page at `route`.
+    ///
+    /// Each page gets its own subdirectory so adding a second page never
+    /// collides with the sections of the first.
+    #[must_use]
+    pub fn sections_dir(&self, route: &str) -> Utf8PathBuf {
+        let segments = route_segments(route);
+        let page_dir = if segments.is_empty() {
+            "home".to_string()
+        } else {
+            segments.join("-")
+        };
+
+        let base = if self.framework == FrameworkTarget::NextJs && self.src_layout {
+            self.root.join("src/components/sections")
+        } else {
+            self.root.join(self.framework.sections_dir())
+        };
+        base.join(page_dir)
+    }
+
+    /// How to make a new page reachable, for frameworks without file-based
+    /// routing.
+    #[must_use]
+    pub fn route_hint(&self, route: &str, page_path: &Utf8Path) -> Option<String> {
+        let relative = page_path.strip_prefix(&self.root).unwrap_or(page_path);
+        match self.framework {
+            FrameworkTarget::ViteReact | FrameworkTarget::ViteVue => {
+                Some(format!("Register {relative} in your router for '{route}'"))
+            }
+            FrameworkTarget::ReactRouter => {
+                let file = page_path
+                    .strip_prefix(self.root.join("app"))
+                    .unwrap_or(page_path);
+                let path = route_segments(route).join("/");
+                Some(if path.is_empty() {
+                    format!("Add index(\"{file}\") to app/routes.ts")
+                } else {
+                    format!("Add route(\"{path}\", \"{file}\") to app/routes.ts")
+                })
+            }
+            FrameworkTarget::Html
+            | FrameworkTarget::NextJs
+            | FrameworkTarget::Nuxt
+            | FrameworkTarget::Astro => None,
+        }
+    }
+
+    /// Directory holding application sources.
+    fn source_dir(&self) -> Utf8PathBuf {
+        if self.src_layout {
+            self.root.join("src")
+        } else {
+            self.root.clone()
+        }
+    }
+
+    /// The package that wires Tailwind v4 into this framework's build.
+    const fn tailwind_plugin(&self) -> &'static str {
+        match self.framework {
+            FrameworkTarget::Html => "@tailwindcss/cli",
+            FrameworkTarget::NextJs => "@tailwindcss/postcss",
+            FrameworkTarget::ViteReact
+            | FrameworkTarget::ViteVue
+            | FrameworkTarget::Nuxt
+            | FrameworkTarget::ReactRouter
+            | FrameworkTarget::Astro => "@tailwindcss/vite",
+        }
+    }
+}
+
+/// Route for a page name ("index" → "/", "about" → "/about").
+#[must_use]
+pub fn route_for_page(page_name: &str) -> String {
+    match page_name.trim_matches('/') {
+        "" | "index" | "home" => "/".to_string(),
+        name => format!("/{name}"),
+    }
+}
+
+/// Tailwind major version declared in `package.json`, if any.
+///
+/// Ranges that don't name a major version ("latest", "workspace:*") are
+/// treated as v4.
+#[must_use]
+pub fn tailwind_dependency_version(package_json: &serde_json::Value) -> Option<TailwindVersion> {
+    let version = ["devDependencies", "dependencies"]
+        .iter()
+        .find_map(|key| package_json.get(key)?.get("tailwindcss")?.as_str())?;
+
+    let version = version.trim_start_matches(['^', '~', '>', '=', '<', 'v', ' ']);
+    Some(if version.starts_with('3') {
+        TailwindVersion::V3
+    } else {
+        TailwindVersion::V4
+    })
+}
+
+/// Read and parse a `package.json`, or `None` if it does not exist.
+fn read_package_json(path: &Utf8Path) -> Result<Option<serde_json::Value>, GenerateError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)?;
+    let json = serde_json::from_str(&content)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    Ok(Some(json))
+}
+
+/// Names from `dependencies` and `devDependencies`.
+fn dependency_names(package_json: &serde_json::Value) -> BTreeSet<String> {
+    ["dependencies", "devDependencies"]
+        .iter()
+        .filter_map(|key| package_json.get(key)?.as_object())
+        .flat_map(|deps| deps.keys().cloned())
+        .collect()
+}
+
+/// First wildcard path alias declared in the project's `tsconfig` files.
+fn find_import_alias(root: &Utf8Path) -> Option<ImportAlias> {
+    TSCONFIG_FILES.iter().find_map(|file| {
+        let content = fs::read_to_string(root.join(file)).ok()?;
+        let json: serde_json::Value = serde_json::from_str(&strip_jsonc(&content)).ok()?;
+        let options = json.get("compilerOptions")?;
+        let base_url = options
+            .get("baseUrl")
+            .and_then(|v| v.as_str())
+            .unwrap_or(".");
+
+        options
+            .get("paths")?
+            .as_object()?
+            .iter()
+            .find_map(|(key, targets)| {
+                let prefix = key.strip_suffix('*')?;
+                let target = targets.get(0)?.as_str()?.strip_suffix('*')?;
+                let dir = normalize(&root.join(base_url).join(target));
+                Some(ImportAlias::new(prefix, dir))
+            })
+    })
+}
+
+/// Strip comments and trailing commas so `tsconfig` files parse as JSON.
+fn strip_jsonc(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push(c);
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+
+    // Drop commas that directly precede a closing bracket
+    let mut cleaned = String::with_capacity(out.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in out.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' && out[i + 1..].trim_start().starts_with(['}', ']']) {
+            continue;
+        }
+        cleaned.push(c);
+    }
+    cleaned
+}
+
+/// Resolve `.` and `..` components without touching the filesystem.
+fn normalize(path: &Utf8Path) -> Utf8PathBuf {
+    let mut normalized = Utf8PathBuf::new();
+    for component in path.components() {
+        match component {
+            Utf8Component::CurDir => {}
+            Utf8Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Non-empty segments of a route.
+fn route_segments(route: &str) -> Vec<&str> {
+    route.split('/').filter(|s| !s.is_empty()).collect()
+}
+
+/// Component name for a page route ("/blog/first-post" → "BlogFirstPost").
+fn page_component_name(segments: &[&str]) -> String {
+    if segments.is_empty() {
+        return "Home".to_string();
+    }
+    segments
+        .iter()
+        .flat_map(|segment| segment.split(['-', '_']))
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project(files: &[(&str, &str)]) -> (TempDir, Utf8PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let root = Utf8PathBuf::try_from(dir.path().to_path_buf()).unwrap();
+        for (path, content) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        (dir, root)
+    }
+
+    #[test]
+    fn inspect_requires_package_json() {
+        let (_dir, root) = project(&[]);
+        assert!(matches!(
+            ProjectInspection::inspect(&root),
+            Err(GenerateError::NotAProject(_))
+        ));
+    }
+
+    #[test]
+    fn inspect_vite_project() {
+        let (_dir, root) = project(&[
+            (
+                "package.json",
+                r#"{"name": "shop", "dependencies": {"react": "^19.0.0", "@heroicons/react": "^2.2.0"},
+                    "devDependencies": {"vite": "^6.0.0", "tailwindcss": "^4.0.0"}}"#,
+            ),
+            ("vite.config.ts", ""),
+            ("src/main.tsx", ""),
+            ("pnpm-lock.yaml", ""),
+            (
+                "tsconfig.app.json",
+                "{\n  // Path aliases\n  \"compilerOptions\": {\n    \"paths\": { \"@/*\": [\"./src/*\"], },\n  },\n}\n",
+            ),
+        ]);
+
+        let inspection = ProjectInspection::inspect(&root).unwrap();
+        assert_eq!(inspection.name, "shop");
+        assert_eq!(inspection.framework, FrameworkTarget::ViteReact);
+        assert_eq!(inspection.package_manager, PackageManager::Pnpm);
+        assert_eq!(inspection.tailwind, Some(TailwindVersion::V4));
+        assert!(!inspection.tailwind_configured);
+        assert!(inspection.src_layout);
+        assert_eq!(
+            inspection.import_alias,
+            Some(ImportAlias::new("@/", root.join("src")))
+        );
+
+        let missing: Vec<_> = inspection
+            .missing_packages()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(missing, ["@headlessui/react", "@tailwindcss/vite"]);
+
+        let imports = [
+            "@heroicons/react/24/outline".to_string(),
+            "@headlessui/react".to_string(),
+            "./HeroSection".to_string(),
+            "@headlessui/react".to_string(),
+        ];
+        assert_eq!(
+            inspection.missing_dependencies(&imports),
+            [MissingPackage {
+                name: "@headlessui/react".to_string(),
+                version: "^2.2.0".to_string(),
+                dev: false,
+            }]
+        );
+
+        assert_eq!(
+            inspection.page_path("/pricing-plans"),
+            root.join("src/pages/PricingPlansPage.tsx")
+        );
+        assert_eq!(
+            inspection.sections_dir("/pricing-plans"),
+            root.join("src/components/sections/pricing-plans")
+        );
+    }
+
+    #[test]
+    fn inspect_workspace_package() {
+        let (_dir, root) = project(&[
+            (
+                "package.json",
+                r#"{"name": "monorepo", "workspaces": ["apps/*"], "devDependencies": {"tailwindcss": "3.4.17"}}"#,
+            ),
+            ("yarn.lock", ""),
+            (
+                "apps/web/package.json",
+                r#"{"name": "web", "dependencies": {"next": "^15.1.0"}}"#,
+            ),
+            ("apps/web/src/app/page.tsx", ""),
+        ]);
+        let web = root.join("apps/web");
+
+        let inspection = ProjectInspection::inspect(&web).unwrap();
+        assert_eq!(inspection.framework, FrameworkTarget::NextJs);
+        assert_eq!(inspection.workspace.as_ref().map(|w| &w.root), Some(&root));
+        assert_eq!(inspection.command_dir(), root);
+        assert_eq!(inspection.package_manager, PackageManager::Yarn);
+        assert_eq!(
+            inspection.add_cmd(&["@headlessui/react"], false),
+            ["yarn", "workspace", "web", "add", "@headlessui/react"]
+        );
+        assert_eq!(inspection.tailwind, Some(TailwindVersion::V3));
+        assert!(
+            !inspection
+                .missing_packages()
+                .iter()
+                .any(|p| p.name == "tailwindcss")
+        );
+        assert_eq!(
+            inspection.page_path("/about"),
+            web.join("src/app/about/page.tsx")
+        );
+        assert_eq!(
+            inspection.sections_dir("/about"),
+            web.join("src/components/sections/about")
+        );
+    }
+
+    #[test]
+    fn react_router_route_hint() {
+        let (_dir, root) = project(&[(
+            "package.json",
+            r#"{"devDependencies": {"@react-router/dev": "^7.1.0"}}"#,
+        )]);
+
+        let inspection = ProjectInspection::inspect(&root).unwrap();
+        let path = inspection.page_path("/blog/launch");
+        assert_eq!(path, root.join("app/routes/blog.launch.tsx"));
+        assert_eq!(
+            inspection.route_hint("/blog/launch", &path).unwrap(),
+            "Add route(\"blog/launch\", \"routes/blog.launch.tsx\") to app/routes.ts"
+        );
+    }
+
+    #[test]
+    fn alias_specifier() {
+        let alias = ImportAlias::new("@/", "/project/src");
+        assert_eq!(
+            alias.specifier(Utf8Path::new(
+                "/project/src/components/sections/about/Hero.tsx"
+            )),
+            Some("@/components/sections/about/Hero".to_string())
+        );
+        assert_eq!(
+            alias.specifier(Utf8Path::new("/project/app/Hero.tsx")),
+            None
+        );
+    }
+
+    #[test]
+    fn tailwind_versions() {
+        let version = |range: &str| {
+            tailwind_dependency_version(&serde_json::json!({
+                "dependencies": { "tailwindcss": range }
+            }))
+        };
+        assert_eq!(version("^4.0.0"), Some(TailwindVersion::V4));
+        assert_eq!(version("~3.4.0"), Some(TailwindVersion::V3));
+        assert_eq!(version("latest"), Some(TailwindVersion::V4));
+        assert_eq!(tailwind_dependency_version(&serde_json::json!({})), None);
+    }
+
+    #[test]
+    fn routes_for_pages() {
+        assert_eq!(route_for_page("index"), "/");
+        assert_eq!(route_for_page("about"), "/about");
+    }
+}
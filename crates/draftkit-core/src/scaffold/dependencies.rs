@@ -0,0 +1,316 @@
+//! Dependency resolution for generated pages.
+//!
+//! Component metadata names the npm packages each section imports. This
+//! module turns those imports into the packages a project is missing, pins
+//! each to a range compatible with the project's React, Vue and Tailwind
+//! majors, and writes them into `package.json` when installs are skipped.
+//!
+//! Pins come from a bundled compatibility table rather than the registry,
+//! so the same project always resolves to the same ranges.
+
+use std::fs;
+
+use camino::Utf8Path;
+use indexmap::IndexMap;
+
+use super::package_json::PackageJson;
+use super::{GenerateError, MissingPackage, VirtualFs};
+use crate::components::TailwindVersion;
+
+/// Range used for packages the compatibility table does not list.
+pub const UNPINNED_VERSION: &str = "latest";
+
+/// When a compatibility table entry applies.
+#[derive(Debug, Clone, Copy)]
+enum Requires {
+    /// Any project
+    Any,
+    /// Projects on a React major below this one
+    ReactBelow(u64),
+    /// Projects on a Vue major below this one
+    VueBelow(u64),
+    /// Projects on Tailwind v3
+    TailwindV3,
+}
+
+/// Package ranges by project versions. The first matching entry for a
+/// package wins, so narrower entries come before the `Any` fallback.
+const COMPATIBILITY: &[(&str, Requires, &str)] = &[
+    // Headless UI v2 needs React 18
+    ("@headlessui/react", Requires::ReactBelow(18), "^1.7.19"),
+    ("@headlessui/react", Requires::Any, "^2.2.0"),
+    ("@headlessui/vue", Requires::Any, "^1.7.23"),
+    ("@headlessui/tailwindcss", Requires::Any, "^0.2.1"),
+    ("@heroicons/react", Requires::Any, "^2.2.0"),
+    ("@heroicons/vue", Requires::Any, "^2.2.0"),
+    ("@phosphor-icons/react", Requires::Any, "^2.1.7"),
+    ("@phosphor-icons/vue", Requires::Any, "^2.2.1"),
+    ("@tabler/icons-react", Requires::Any, "^3.26.0"),
+    ("@tabler/icons-vue", Requires::Any, "^3.26.0"),
+    ("@tailwindcss/forms", Requires::Any, "^0.5.10"),
+    ("@tailwindcss/typography", Requires::Any, "^0.5.16"),
+    ("@tailwindcss/postcss", Requires::Any, "^4.0.0"),
+    ("@tailwindcss/vite", Requires::Any, "^4.0.0"),
+    // VueUse 12 dropped Vue 2
+    ("@vueuse/core", Requires::VueBelow(3), "^11.3.0"),
+    ("@vueuse/core", Requires::Any, "^13.0.0"),
+    ("autoprefixer", Requires::Any, "^10.4.20"),
+    ("clsx", Requires::Any, "^2.1.1"),
+    ("framer-motion", Requires::ReactBelow(18), "^10.18.0"),
+    ("framer-motion", Requires::Any, "^12.0.0"),
+    ("lucide-react", Requires::Any, "^0.469.0"),
+    ("lucide-vue-next", Requires::Any, "^0.469.0"),
+    ("motion", Requires::Any, "^12.0.0"),
+    ("postcss", Requires::Any, "^8.4.49"),
+    ("react", Requires::Any, "^19.0.0"),
+    ("react-dom", Requires::Any, "^19.0.0"),
+    // tailwind-merge 3 targets Tailwind v4 class names
+    ("tailwind-merge", Requires::TailwindV3, "^2.6.0"),
+    ("tailwind-merge", Requires::Any, "^3.0.0"),
+    ("tailwindcss", Requires::TailwindV3, "^3.4.17"),
+    ("tailwindcss", Requires::Any, "^4.0.0"),
+    ("vue", Requires::Any, "^3.5.0"),
+];
+
+/// Major versions of the libraries that decide compatible pins.
+///
+/// `None` means the library is not declared (or its range names no major),
+/// in which case the newest compatible pins are used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProjectVersions {
+    /// React major version
+    pub react: Option<u64>,
+    /// Vue major version
+    pub vue: Option<u64>,
+    /// Tailwind version
+    pub tailwind: Option<TailwindVersion>,
+}
+
+impl ProjectVersions {
+    /// Versions declared in a `package.json`.
+    #[must_use]
+    pub fn from_package_json(package_json: &serde_json::Value) -> Self {
+        Self {
+            react: declared_major(package_json, "react"),
+            vue: declared_major(package_json, "vue"),
+            tailwind: super::tailwind_dependency_version(package_json),
+        }
+    }
+
+    /// Fill versions missing here from `other`, e.g. a workspace root.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            react: self.react.or(other.react),
+            vue: self.vue.or(other.vue),
+            tailwind: self.tailwind.or(other.tailwind),
+        }
+    }
+
+    const fn satisfies(&self, requires: Requires) -> bool {
+        match requires {
+            Requires::Any => true,
+            Requires::ReactBelow(major) => matches!(self.react, Some(v) if v < major),
+            Requires::VueBelow(major) => matches!(self.vue, Some(v) if v < major),
+            Requires::TailwindV3 => matches!(self.tailwind, Some(TailwindVersion::V3)),
+        }
+    }
+}
+
+/// Range for `package` compatible with `versions`, or
+/// [`UNPINNED_VERSION`] when the package is not in the bundled table.
+#[must_use]
+pub fn compatible_version(package: &str, versions: &ProjectVersions) -> &'static str {
+    COMPATIBILITY
+        .iter()
+        .find(|(name, requires, _)| *name == package && versions.satisfies(*requires))
+        .map_or(UNPINNED_VERSION, |(_, _, version)| version)
+}
+
+/// The npm package an import specifier resolves to.
+///
+/// Strips subpaths (`@heroicons/react/24/outline` → `@heroicons/react`) and
+/// returns `None` for relative, absolute and `node:` imports.
+#[must_use]
+pub fn package_name(specifier: &str) -> Option<&str> {
+    if specifier.is_empty()
+        || specifier.starts_with(['.', '/', '~', '#'])
+        || specifier.starts_with("node:")
+    {
+        return None;
+    }
+    let parts = if specifier.starts_with('@') { 2 } else { 1 };
+    let end = specifier
+        .match_indices('/')
+        .nth(parts - 1)
+        .map_or(specifier.len(), |(i, _)| i);
+    let name = &specifier[..end];
+    (parts == 1 || name.contains('/')).then_some(name)
+}
+
+/// Stage `package_json` with `packages` added to `dependencies` or
+/// `devDependencies`.
+///
+/// Each touched section is sorted by name, as npm writes it, so repeated
+/// runs produce the same file. Packages already declared keep their range.
+/// Returns `false` when there is nothing to add.
+///
+/// # Errors
+///
+/// Returns `Io` when `package.json` cannot be read or is not a JSON object.
+pub fn stage_dependencies(
+    package_json: &Utf8Path,
+    packages: &[MissingPackage],
+    vfs: &mut VirtualFs,
+) -> Result<bool, GenerateError> {
+    if packages.is_empty() {
+        return Ok(false);
+    }
+
+    let content = match vfs.read(package_json) {
+        Some(staged) => staged.to_string(),
+        None => fs::read_to_string(package_json)?,
+    };
+    let invalid = |e: String| std::io::Error::new(std::io::ErrorKind::InvalidData, e);
+    let mut json = PackageJson::parse(&content)?;
+    let object = json
+        .as_object_mut()
+        .ok_or_else(|| invalid(format!("{package_json} is not a JSON object")))?;
+
+    for (key, dev) in [("dependencies", false), ("devDependencies", true)] {
+        let wanted: Vec<_> = packages.iter().filter(|p| p.dev == dev).collect();
+        if wanted.is_empty() {
+            continue;
+        }
+        let section = object
+            .entry(key.to_string())
+            .or_insert_with(|| PackageJson::Object(IndexMap::new()))
+            .as_object_mut()
+            .ok_or_else(|| invalid(format!("\"{key}\" in {package_json} is not an object")))?;
+
+        for package in wanted {
+            if !section.contains_key(&package.name) {
+                section.insert(package.name.clone(), PackageJson::string(&package.version));
+            }
+        }
+        section.sort_keys();
+    }
+
+    let content = json.to_pretty_string()?;
+    vfs.write(package_json, content);
+    Ok(true)
+}
+
+/// Major version from a declared range like `^18.3.1` or `~3.5`.
+fn declared_major(package_json: &serde_json::Value, package: &str) -> Option<u64> {
+    let range = ["dependencies", "devDependencies", "peerDependencies"]
+        .iter()
+        .find_map(|key| package_json.get(key)?.get(package)?.as_str())?;
+    let digits: String = range
+        .trim_start_matches(['^', '~', '>', '=', '<', 'v', ' '])
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use camino::Utf8PathBuf;
+    use tempfile::TempDir;
+
+    fn missing(name: &str, version: &str, dev: bool) -> MissingPackage {
+        MissingPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+            dev,
+        }
+    }
+
+    #[test]
+    fn package_names_from_specifiers() {
+        assert_eq!(
+            package_name("@heroicons/react/24/outline"),
+            Some("@heroicons/react")
+        );
+        assert_eq!(package_name("@headlessui/vue"), Some("@headlessui/vue"));
+        assert_eq!(package_name("motion/react"), Some("motion"));
+        assert_eq!(package_name("clsx"), Some("clsx"));
+        assert_eq!(package_name("./Hero"), None);
+        assert_eq!(package_name("~/components/Hero.vue"), None);
+        assert_eq!(package_name("node:path"), None);
+        assert_eq!(package_name("@scope"), None);
+    }
+
+    #[test]
+    fn versions_follow_project_majors() {
+        let json = serde_json::json!({
+            "dependencies": {"react": "^17.0.2"},
+            "devDependencies": {"tailwindcss": "~3.4.0"}
+        });
+        let old = ProjectVersions::from_package_json(&json);
+        assert_eq!(old.react, Some(17));
+        assert_eq!(old.tailwind, Some(TailwindVersion::V3));
+        assert_eq!(compatible_version("@headlessui/react", &old), "^1.7.19");
+        assert_eq!(compatible_version("tailwind-merge", &old), "^2.6.0");
+
+        let current = ProjectVersions::from_package_json(&serde_json::json!({
+            "dependencies": {"react": "19.0.0", "vue": "workspace:*"}
+        }));
+        assert_eq!(current.vue, None);
+        assert_eq!(compatible_version("@headlessui/react", &current), "^2.2.0");
+        assert_eq!(compatible_version("tailwind-merge", &current), "^3.0.0");
+        assert_eq!(compatible_version("left-pad", &current), UNPINNED_VERSION);
+
+        let vue2 = ProjectVersions {
+            vue: Some(2),
+            ..Default::default()
+        }
+        .or(current);
+        assert_eq!(vue2.react, Some(19));
+        assert_eq!(compatible_version("@vueuse/core", &vue2), "^11.3.0");
+    }
+
+    #[test]
+    fn stage_dependencies_sorts_sections() {
+        let dir = TempDir::new().unwrap();
+        let root = Utf8PathBuf::try_from(dir.path().to_path_buf()).unwrap();
+        let path = root.join("package.json");
+        fs::write(
+            &path,
+            r#"{"name": "shop", "dependencies": {"react": "^19.0.0", "clsx": "^2.0.0"}, "scripts": {}}"#,
+        )
+        .unwrap();
+
+        let packages = [
+            missing("@heroicons/react", "^2.2.0", false),
+            missing("clsx", "^2.1.1", false),
+            missing("@tailwindcss/vite", "^4.0.0", true),
+        ];
+        let mut vfs = VirtualFs::new(&root);
+        assert!(stage_dependencies(&path, &packages, &mut vfs).unwrap());
+        let staged = vfs.read(&path).unwrap().to_string();
+        assert_eq!(
+            staged,
+            r#"{
+  "name": "shop",
+  "dependencies": {
+    "@heroicons/react": "^2.2.0",
+    "clsx": "^2.0.0",
+    "react": "^19.0.0"
+  },
+  "scripts": {},
+  "devDependencies": {
+    "@tailwindcss/vite": "^4.0.0"
+  }
+}
+"#
+        );
+
+        // Staging again builds on the staged file and changes nothing
+        assert!(stage_dependencies(&path, &packages, &mut vfs).unwrap());
+        assert_eq!(vfs.read(&path).unwrap(), staged);
+        assert!(!stage_dependencies(&path, &[], &mut vfs).unwrap());
+    }
+}
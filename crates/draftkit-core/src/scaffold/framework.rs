@@ -1,11 +1,15 @@
 //! Framework targets and project configuration.
 //!
 //! Framework tiers:
-//! | Framework    | Build Step       | Complexity   | Target User       |
-//! |--------------|------------------|--------------|-------------------|
-//! | `html`       | Tailwind CLI     | Simplest     | Total beginners   |
-//! | `vite-react` | Vite             | Modern       | Most users        |
-//! | `nextjs`     | Next.js          | Full-featured| Apps with routing |
+//! | Framework      | Build Step     | Complexity    | Target User       |
+//! |----------------|----------------|---------------|-------------------|
+//! | `html`         | Tailwind CLI   | Simplest      | Total beginners   |
+//! | `vite-react`   | Vite           | Modern        | Most users        |
+//! | `vite-vue`     | Vite           | Modern        | Vue users         |
+//! | `nextjs`       | Next.js        | Full-featured | Apps with routing |
+//! | `nuxt`         | Nuxt           | Full-featured | Vue apps          |
+//! | `react-router` | React Router 7 | Full-featured | React apps        |
+//! | `astro`        | Astro          | Content-first | Marketing sites   |
 
 use camino::{Utf8Path, Utf8PathBuf};
 use serde::{Deserialize, Serialize};
@@ -24,6 +28,14 @@ pub enum FrameworkTarget {
     ViteReact,
     /// Next.js App Router
     NextJs,
+    /// Vite + Vue 3 (TypeScript)
+    ViteVue,
+    /// Nuxt 3
+    Nuxt,
+    /// React Router 7 framework mode (formerly Remix)
+    ReactRouter,
+    /// Astro with React islands
+    Astro,
 }
 
 impl FrameworkTarget {
@@ -34,6 +46,10 @@ impl FrameworkTarget {
             Self::Html => "html",
             Self::ViteReact => "vite-react",
             Self::NextJs => "nextjs",
+            Self::ViteVue => "vite-vue",
+            Self::Nuxt => "nuxt",
+            Self::ReactRouter => "react-router",
+            Self::Astro => "astro",
         }
     }
 
@@ -44,6 +60,10 @@ impl FrameworkTarget {
             "html" => Some(Self::Html),
             "vite-react" | "vitereact" | "vite" | "react" => Some(Self::ViteReact),
             "nextjs" | "next" => Some(Self::NextJs),
+            "vite-vue" | "vitevue" | "vue" => Some(Self::ViteVue),
+            "nuxt" | "nuxtjs" => Some(Self::Nuxt),
+            "react-router" | "reactrouter" | "remix" => Some(Self::ReactRouter),
+            "astro" => Some(Self::Astro),
             _ => None,
         }
     }
@@ -53,16 +73,32 @@ impl FrameworkTarget {
     pub const fn uses_typescript(&self) -> bool {
         match self {
             Self::Html => false,
-            Self::ViteReact | Self::NextJs => true,
+            Self::ViteReact
+            | Self::NextJs
+            | Self::ViteVue
+            | Self::Nuxt
+            | Self::ReactRouter
+            | Self::Astro => true,
         }
     }
 
+    /// Whether pages are Vue single-file components.
+    #[must_use]
+    pub const fn is_vue(&self) -> bool {
+        matches!(self, Self::ViteVue | Self::Nuxt)
+    }
+
     /// Whether this framework requires a build step.
     #[must_use]
     pub const fn requires_build(&self) -> bool {
         match self {
             Self::Html => false, // Tailwind CLI watches
-            Self::ViteReact | Self::NextJs => true,
+            Self::ViteReact
+            | Self::NextJs
+            | Self::ViteVue
+            | Self::Nuxt
+            | Self::ReactRouter
+            | Self::Astro => true,
         }
     }
 
@@ -70,9 +106,13 @@ impl FrameworkTarget {
     #[must_use]
     pub const fn default_port(&self) -> u16 {
         match self {
-            Self::Html => 3000,      // Simple HTTP server
-            Self::ViteReact => 5173, // Vite default
-            Self::NextJs => 3000,    // Next.js default
+            Self::Html => 3000,        // Simple HTTP server
+            Self::ViteReact => 5173,   // Vite default
+            Self::NextJs => 3000,      // Next.js default
+            Self::ViteVue => 5173,     // Vite default
+            Self::Nuxt => 3000,        // Nuxt default
+            Self::ReactRouter => 5173, // Vite default
+            Self::Astro => 4321,       // Astro default
         }
     }
 
@@ -83,7 +123,69 @@ impl FrameworkTarget {
             Self::Html => "index.html",
             Self::ViteReact => "src/App.tsx",
             Self::NextJs => "app/page.tsx",
+            Self::ViteVue => "src/App.vue",
+            Self::Nuxt => "pages/index.vue",
+            Self::ReactRouter => "app/routes/home.tsx",
+            Self::Astro => "src/pages/index.astro",
+        }
+    }
+
+    /// Directory for per-section component files.
+    #[must_use]
+    pub const fn sections_dir(&self) -> &'static str {
+        match self {
+            Self::Html => "sections",
+            Self::ViteReact | Self::ViteVue | Self::Astro => "src/components/sections",
+            Self::NextJs | Self::Nuxt => "components/sections",
+            Self::ReactRouter => "app/components/sections",
+        }
+    }
+
+    /// Detect the framework of an existing project from its config files
+    /// and `package.json` dependencies.
+    ///
+    /// Meta-frameworks are checked before the libraries they build on, and
+    /// projects with no recognizable setup fall back to plain HTML.
+    #[must_use]
+    pub fn detect(project_path: &Utf8Path, package_json: &serde_json::Value) -> Self {
+        let has_dependency = |name: &str| {
+            ["dependencies", "devDependencies"].iter().any(|key| {
+                package_json
+                    .get(key)
+                    .and_then(|deps| deps.get(name))
+                    .is_some()
+            })
+        };
+        let has_file = |names: &[&str]| names.iter().any(|name| project_path.join(name).exists());
+
+        if has_file(&["nuxt.config.ts", "nuxt.config.js"]) || has_dependency("nuxt") {
+            return Self::Nuxt;
+        }
+        if has_file(&["astro.config.mjs", "astro.config.ts"]) || has_dependency("astro") {
+            return Self::Astro;
+        }
+        // React Router framework mode runs on Vite, so check it first
+        if has_file(&["react-router.config.ts"]) || has_dependency("@react-router/dev") {
+            return Self::ReactRouter;
+        }
+        if has_file(&["next.config.js", "next.config.ts", "next.config.mjs"])
+            || has_dependency("next")
+        {
+            return Self::NextJs;
+        }
+        if has_dependency("vue") || has_file(&["src/App.vue"]) {
+            return Self::ViteVue;
+        }
+        if has_file(&[
+            "vite.config.ts",
+            "vite.config.js",
+            "src/App.tsx",
+            "src/App.jsx",
+        ]) {
+            return Self::ViteReact;
         }
+
+        Self::Html
     }
 
     /// Get the main page/route path for generated content.
@@ -94,6 +196,10 @@ impl FrameworkTarget {
             Self::Html => "index.html",
             Self::ViteReact => "src/App.tsx",
             Self::NextJs => "app/page.tsx",
+            Self::ViteVue => "src/App.vue",
+            Self::Nuxt => "pages/index.vue",
+            Self::ReactRouter => "app/routes/home.tsx",
+            Self::Astro => "src/pages/index.astro",
         }
     }
 }
@@ -233,6 +339,19 @@ mod tests {
             FrameworkTarget::parse("next"),
             Some(FrameworkTarget::NextJs)
         );
+        assert_eq!(
+            FrameworkTarget::parse("vue"),
+            Some(FrameworkTarget::ViteVue)
+        );
+        assert_eq!(FrameworkTarget::parse("nuxt"), Some(FrameworkTarget::Nuxt));
+        assert_eq!(
+            FrameworkTarget::parse("remix"),
+            Some(FrameworkTarget::ReactRouter)
+        );
+        assert_eq!(
+            FrameworkTarget::parse("astro"),
+            Some(FrameworkTarget::Astro)
+        );
         assert_eq!(FrameworkTarget::parse("invalid"), None);
     }
 
@@ -245,6 +364,37 @@ mod tests {
         assert!(!FrameworkTarget::Html.requires_build());
         assert!(FrameworkTarget::ViteReact.requires_build());
         assert!(FrameworkTarget::NextJs.requires_build());
+
+        assert!(FrameworkTarget::Nuxt.is_vue());
+        assert!(!FrameworkTarget::NextJs.is_vue());
+        assert_eq!(FrameworkTarget::Nuxt.main_source_path(), "pages/index.vue");
+        assert_eq!(FrameworkTarget::Astro.default_port(), 4321);
+    }
+
+    #[test]
+    fn detect_from_dependencies() {
+        let path = Utf8Path::new("/nonexistent/project");
+        let detect = |json| FrameworkTarget::detect(path, &json);
+
+        let react = serde_json::json!({ "dependencies": { "react": "^19.0.0" } });
+        assert_eq!(detect(react), FrameworkTarget::Html);
+
+        let next = serde_json::json!({ "dependencies": { "next": "^15.1.0", "react": "^19.0.0" } });
+        assert_eq!(detect(next), FrameworkTarget::NextJs);
+
+        let vue = serde_json::json!({ "dependencies": { "vue": "^3.5.0" } });
+        assert_eq!(detect(vue), FrameworkTarget::ViteVue);
+
+        let nuxt = serde_json::json!({ "dependencies": { "nuxt": "^3.15.0", "vue": "^3.5.0" } });
+        assert_eq!(detect(nuxt), FrameworkTarget::Nuxt);
+
+        let astro =
+            serde_json::json!({ "dependencies": { "astro": "^5.1.0", "react": "^19.0.0" } });
+        assert_eq!(detect(astro), FrameworkTarget::Astro);
+
+        let react_router =
+            serde_json::json!({ "devDependencies": { "@react-router/dev": "^7.1.0" } });
+        assert_eq!(detect(react_router), FrameworkTarget::ReactRouter);
     }
 
     #[test]
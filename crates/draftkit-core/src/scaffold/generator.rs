@@ -18,18 +18,22 @@
 //! Write to file
 //! ```
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::io;
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use super::{FrameworkTarget, ProjectConfig};
-use crate::components::{Framework, Mode};
+use super::adopt::ImportAlias;
+use super::icons::{IconOptions, substitute_icons};
+use super::site::{SitePageRecipe, SitePlan};
+use super::{FrameworkTarget, ProjectConfig, VirtualFs};
+use crate::components::{ComponentReader, ComponentRecord, Framework, Mode, TailwindVersion};
 use crate::fetch::{ComponentFetcher, FetchError};
 use crate::intelligence::{ComponentMatcher, Recipe, RecipeSection};
+use crate::slug::slugify;
 
 /// Page generation error.
 #[derive(Debug, Error)]
@@ -51,6 +55,35 @@ pub enum GenerateError {
 
     #[error("Framework mismatch: expected {expected}, got {got}")]
     FrameworkMismatch { expected: String, got: String },
+
+    #[error("Invalid site plan: {0}")]
+    InvalidSitePlan(String),
+
+    #[error("Invalid lockfile: {0}")]
+    InvalidLock(String),
+
+    #[error("No package.json found in {0}")]
+    NotAProject(Utf8PathBuf),
+
+    #[error(
+        "Section '{}' uses Tailwind v4-only features ({}) in a v3 project",
+        .0.section_type,
+        .0.v4_only.join(", ")
+    )]
+    TailwindIncompatible(Box<TailwindConflict>),
+}
+
+/// A section whose matched component needs Tailwind v4 in a v3 project.
+#[derive(Debug, Clone)]
+pub struct TailwindConflict {
+    /// Section type
+    pub section_type: String,
+    /// Matched component ID
+    pub component_id: String,
+    /// v4-only features the component uses
+    pub v4_only: Vec<String>,
+    /// v3-compatible components from the same sub-subcategory
+    pub alternatives: Vec<String>,
 }
 
 /// Slot value for content injection.
@@ -103,6 +136,18 @@ pub struct GenerateOptions {
     pub slots: HashMap<String, SlotValue>,
     /// Output path override
     pub output_path: Option<Utf8PathBuf>,
+    /// Write each section to its own component file
+    pub split_sections: bool,
+    /// Catalog components pinned by the lockfile, keyed by section component name
+    pub locked_components: HashMap<String, String>,
+    /// Fetch components that use Tailwind v4-only features into v3 projects
+    pub allow_v4_only: bool,
+    /// Directory for split section files (overrides framework default)
+    pub sections_dir: Option<Utf8PathBuf>,
+    /// Path alias for section imports instead of relative paths
+    pub import_alias: Option<ImportAlias>,
+    /// Icon library to rewrite fetched Heroicons to
+    pub icons: IconOptions,
 }
 
 impl GenerateOptions {
@@ -135,6 +180,34 @@ impl GenerateOptions {
         self.slots.insert(key.into(), value.into());
         self
     }
+
+    /// Write each section to its own component file.
+    #[must_use]
+    pub const fn with_split_sections(mut self) -> Self {
+        self.split_sections = true;
+        self
+    }
+
+    /// Write split section files into this directory.
+    #[must_use]
+    pub fn with_sections_dir(mut self, dir: impl Into<Utf8PathBuf>) -> Self {
+        self.sections_dir = Some(dir.into());
+        self
+    }
+
+    /// Import section files through a path alias.
+    #[must_use]
+    pub fn with_import_alias(mut self, alias: ImportAlias) -> Self {
+        self.import_alias = Some(alias);
+        self
+    }
+
+    /// Rewrite fetched Heroicons to another icon library.
+    #[must_use]
+    pub fn with_icons(mut self, icons: IconOptions) -> Self {
+        self.icons = icons;
+        self
+    }
 }
 
 /// Generated page content ready for writing.
@@ -150,6 +223,90 @@ pub struct GeneratedPage {
     pub dependencies: Vec<String>,
     /// Dev dependencies required
     pub dev_dependencies: Vec<String>,
+    /// Heroicons left in place because the icon library has no mapping
+    pub unmapped_icons: Vec<String>,
+    /// Per-section component files (empty unless sections are split)
+    pub sections: Vec<GeneratedSection>,
+    /// Component chosen for each section, in page order
+    pub choices: Vec<SectionChoice>,
+}
+
+impl GeneratedPage {
+    /// Files this page would write that already exist on disk.
+    #[must_use]
+    pub fn existing_files(&self) -> Vec<Utf8PathBuf> {
+        self.sections
+            .iter()
+            .map(|section| &section.path)
+            .chain(std::iter::once(&self.path))
+            .filter(|path| path.exists())
+            .cloned()
+            .collect()
+    }
+}
+
+/// The component a section resolved to during generation.
+#[derive(Debug, Clone)]
+pub struct SectionChoice {
+    /// Component name, unique within the page
+    pub name: String,
+    /// Section type
+    pub section_type: String,
+    /// Variant the section resolved to
+    pub variant_id: String,
+    /// Catalog component ID (None for placeholders)
+    pub component_id: Option<String>,
+    /// Catalog component UUID (None for placeholders)
+    pub uuid: Option<String>,
+}
+
+/// A section component written to its own file.
+#[derive(Debug, Clone)]
+pub struct GeneratedSection {
+    /// Section type this component renders
+    pub section_type: String,
+    /// Component name (e.g., "Hero")
+    pub name: String,
+    /// Target file path
+    pub path: Utf8PathBuf,
+    /// Generated code content
+    pub content: String,
+}
+
+/// Generated multi-page site ready for writing.
+#[derive(Debug, Clone)]
+pub struct GeneratedSite {
+    /// One file per route
+    pub pages: Vec<GeneratedPage>,
+    /// Shared section components plus the router or layout that renders them
+    pub shared: Vec<GeneratedPage>,
+    /// NPM dependencies required
+    pub dependencies: Vec<String>,
+}
+
+impl GeneratedSite {
+    /// All files in the site, shared files first.
+    pub fn files(&self) -> impl Iterator<Item = &GeneratedPage> {
+        self.shared.iter().chain(&self.pages)
+    }
+}
+
+/// Convert a file-system style route to react-router syntax.
+///
+/// `[param]` segments become `:param` and a `[...rest]` catch-all becomes
+/// `*`, so `/blog/[slug]` routes as `/blog/:slug`.
+fn react_router_path(route: &str) -> String {
+    route
+        .split('/')
+        .map(
+            |segment| match segment.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+                Some(param) if param.starts_with("...") => "*".to_string(),
+                Some(param) => format!(":{param}"),
+                None => segment.to_string(),
+            },
+        )
+        .collect::<Vec<_>>()
+        .join("/")
 }
 
 /// Page generator for assembling components into pages.
@@ -206,18 +363,21 @@ impl PageGenerator {
         config: &ProjectConfig,
         options: &GenerateOptions,
     ) -> Result<GeneratedPage, GenerateError> {
-        let framework = self.framework_from_target(config.framework);
+        // Astro renders inline sections as plain markup in the page template
+        let framework = if config.framework == FrameworkTarget::Astro && !options.split_sections {
+            Framework::Html
+        } else {
+            self.framework_from_target(config.framework)
+        };
         let mut sections_code = Vec::new();
         let mut all_dependencies = Vec::new();
         let mut imports = Vec::new();
+        let component_names = section_component_names(&recipe.sections);
 
-        for section in &recipe.sections {
-            // Generate component name from section
-            let component_name = self.section_to_component_name(&section.section_type);
-
+        for (section, component_name) in recipe.sections.iter().zip(&component_names) {
             // Generate placeholder code for MVP (real fetch would use self.fetcher)
             let section_code =
-                self.generate_section_placeholder(section, &component_name, framework, options);
+                self.generate_section_placeholder(section, component_name, framework, options);
 
             // Collect imports
             imports.push(format!(
@@ -228,24 +388,75 @@ impl PageGenerator {
             sections_code.push(section_code);
         }
 
-        // Assemble final page
-        let content = self.assemble_react_page(&config.name, &imports, &sections_code);
-
         // Collect dependencies from recipe
         all_dependencies.extend(recipe.dependencies.clone());
 
+        let choices = recipe
+            .sections
+            .iter()
+            .zip(&component_names)
+            .map(|(section, name)| SectionChoice {
+                name: name.clone(),
+                section_type: section.section_type.clone(),
+                variant_id: section.variant_id.clone(),
+                component_id: None,
+                uuid: None,
+            })
+            .collect();
+
         // Determine output path
         let path = options
             .output_path
             .clone()
             .unwrap_or_else(|| config.path.join(config.framework.main_source_path()));
 
+        if options.split_sections {
+            let sections = recipe
+                .sections
+                .iter()
+                .zip(component_names)
+                .zip(&sections_code)
+                .map(|((section, name), code)| {
+                    let content = match config.framework {
+                        FrameworkTarget::Html => format!("{}\n", dedent(code)),
+                        FrameworkTarget::ViteVue | FrameworkTarget::Nuxt => {
+                            format!("<template>\n{}\n</template>\n", indent(&dedent(code), "  "))
+                        }
+                        FrameworkTarget::ViteReact
+                        | FrameworkTarget::NextJs
+                        | FrameworkTarget::ReactRouter
+                        | FrameworkTarget::Astro => {
+                            self.assemble_component(&name, std::slice::from_ref(code))
+                        }
+                    };
+                    self.section_file(config, options, section, name, content)
+                })
+                .collect();
+
+            return Ok(self.split_page(config, options, path, sections, all_dependencies, choices));
+        }
+
+        // Assemble final page
+        let content = match config.framework {
+            FrameworkTarget::Html => self.assemble_html_page(&config.name, &sections_code),
+            FrameworkTarget::ViteVue | FrameworkTarget::Nuxt => {
+                self.assemble_vue_page(&[], &sections_code)
+            }
+            FrameworkTarget::Astro => self.assemble_astro_page(config, &path, &[], &sections_code),
+            FrameworkTarget::ViteReact | FrameworkTarget::NextJs | FrameworkTarget::ReactRouter => {
+                self.assemble_react_page(&config.name, &imports, &sections_code)
+            }
+        };
+
         Ok(GeneratedPage {
             name: "index".to_string(),
             path,
             content,
             dependencies: all_dependencies,
             dev_dependencies: vec![],
+            unmapped_icons: vec![],
+            sections: vec![],
+            choices,
         })
     }
 
@@ -253,9 +464,13 @@ impl PageGenerator {
     #[must_use]
     pub fn generate_placeholder(&self, config: &ProjectConfig) -> GeneratedPage {
         let content = match config.framework {
-            FrameworkTarget::ViteReact => self.react_placeholder(&config.name),
+            FrameworkTarget::ViteReact | FrameworkTarget::ReactRouter => {
+                self.react_placeholder(&config.name)
+            }
+            FrameworkTarget::Astro => self.astro_placeholder(&config.name),
             FrameworkTarget::Html => self.html_placeholder(&config.name),
             FrameworkTarget::NextJs => self.nextjs_placeholder(&config.name),
+            FrameworkTarget::ViteVue | FrameworkTarget::Nuxt => self.vue_placeholder(&config.name),
         };
 
         let path = config.path.join(config.framework.main_source_path());
@@ -266,11 +481,17 @@ impl PageGenerator {
             content,
             dependencies: vec![],
             dev_dependencies: vec![],
+            unmapped_icons: vec![],
+            sections: vec![],
+            choices: vec![],
         }
     }
 
-    /// Write a generated page to disk.
+    /// Write a generated page to disk, along with any split section files.
     pub fn write_page(&self, page: &GeneratedPage) -> Result<(), GenerateError> {
+        for section in &page.sections {
+            self.write_section(section)?;
+        }
         if let Some(parent) = page.path.parent() {
             fs::create_dir_all(parent)?;
         }
@@ -278,6 +499,286 @@ impl PageGenerator {
         Ok(())
     }
 
+    /// Stage a generated page and its split section files without writing.
+    pub fn stage_page(&self, page: &GeneratedPage, vfs: &mut VirtualFs) {
+        for section in &page.sections {
+            vfs.write(&section.path, &section.content);
+        }
+        vfs.write(&page.path, &page.content);
+    }
+
+    /// Write a single section component to disk.
+    ///
+    /// Lets one section be regenerated without touching the page or its
+    /// other sections.
+    pub fn write_section(&self, section: &GeneratedSection) -> Result<(), GenerateError> {
+        if let Some(parent) = section.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&section.path, &section.content)?;
+        Ok(())
+    }
+
+    /// Generate every page of a site plan with routing wired up, using
+    /// placeholder sections.
+    ///
+    /// Shared sections (header and footer by default) are extracted into
+    /// their own components once, using the first page's variant, and
+    /// removed from the individual pages:
+    ///
+    /// - Vite React: pages in `src/pages/`, components in `src/components/`,
+    ///   and a `react-router` setup in `src/App.tsx`
+    /// - Next.js: one `app/<route>/page.tsx` per route, components in
+    ///   `components/`, and the shared sections in `app/layout.tsx`
+    ///
+    /// See [`generate_site_async`](Self::generate_site_async) for real
+    /// components.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FrameworkMismatch` for plain HTML projects, which have no
+    /// client-side routing.
+    pub fn generate_site(
+        &self,
+        plan: &SitePlan,
+        pages: &[SitePageRecipe],
+        config: &ProjectConfig,
+        options: &GenerateOptions,
+    ) -> Result<GeneratedSite, GenerateError> {
+        check_site_framework(config)?;
+        let framework = self.framework_from_target(config.framework);
+        let shared_sections = shared_site_sections(plan, pages);
+
+        let shared = shared_sections
+            .iter()
+            .map(|(section, _)| {
+                let name = self.section_to_component_name(&section.section_type);
+                let body = self.generate_section_placeholder(section, &name, framework, options);
+                GeneratedPage {
+                    name: name.clone(),
+                    path: site_components_dir(config).join(format!("{name}.tsx")),
+                    content: self.assemble_component(&name, &[body]),
+                    dependencies: vec![],
+                    dev_dependencies: vec![],
+                    unmapped_icons: vec![],
+                    sections: vec![],
+                    choices: vec![],
+                }
+            })
+            .collect();
+
+        let generated_pages = pages
+            .iter()
+            .map(|site_page| {
+                let name = site_page.page.component_name();
+                let sections: Vec<String> = site_page
+                    .recipe
+                    .sections
+                    .iter()
+                    .filter(|s| !plan.is_shared(&s.section_type))
+                    .map(|section| {
+                        let component_name = self.section_to_component_name(&section.section_type);
+                        self.generate_section_placeholder(
+                            section,
+                            &component_name,
+                            framework,
+                            options,
+                        )
+                    })
+                    .collect();
+
+                GeneratedPage {
+                    name: site_page.page.route.clone(),
+                    path: site_page_path(config, site_page),
+                    content: self.assemble_component(&format!("{name}Page"), &sections),
+                    dependencies: site_page.recipe.dependencies.clone(),
+                    dev_dependencies: vec![],
+                    unmapped_icons: vec![],
+                    sections: vec![],
+                    choices: vec![],
+                }
+            })
+            .collect();
+
+        Ok(self.assemble_site(
+            plan,
+            pages,
+            config,
+            &shared_sections,
+            shared,
+            generated_pages,
+        ))
+    }
+
+    /// Generate every page of a site plan from real TailwindPlus components.
+    ///
+    /// Laid out like [`generate_site`](Self::generate_site), except that
+    /// each page's own sections are written as component files under
+    /// `components/{Page}/`, so pages can use different components of the
+    /// same name.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FrameworkMismatch` for plain HTML projects, and the errors
+    /// of [`generate_from_recipe_async`](Self::generate_from_recipe_async)
+    /// for any page or shared section.
+    pub async fn generate_site_async(
+        &self,
+        plan: &SitePlan,
+        pages: &[SitePageRecipe],
+        config: &ProjectConfig,
+        options: &GenerateOptions,
+    ) -> Result<GeneratedSite, GenerateError> {
+        check_site_framework(config)?;
+        let shared_sections = shared_site_sections(plan, pages);
+        let components_dir = site_components_dir(config);
+
+        let mut shared = Vec::new();
+        for (section, _) in &shared_sections {
+            let recipe = Recipe {
+                sections: vec![(*section).clone()],
+                ..pages[0].recipe.clone()
+            };
+            let options = GenerateOptions {
+                split_sections: true,
+                output_path: None,
+                sections_dir: Some(components_dir.clone()),
+                ..options.clone()
+            };
+            let page = self
+                .generate_from_recipe_async(&recipe, config, &options)
+                .await?;
+            let Some(component) = page.sections.into_iter().next() else {
+                continue;
+            };
+            shared.push(GeneratedPage {
+                name: component.name,
+                path: component.path,
+                content: component.content,
+                dependencies: page.dependencies,
+                dev_dependencies: vec![],
+                unmapped_icons: page.unmapped_icons,
+                sections: vec![],
+                choices: page.choices,
+            });
+        }
+
+        let mut generated_pages = Vec::new();
+        for site_page in pages {
+            let name = site_page.page.component_name();
+            let recipe = Recipe {
+                sections: site_page
+                    .recipe
+                    .sections
+                    .iter()
+                    .filter(|s| !plan.is_shared(&s.section_type))
+                    .cloned()
+                    .collect(),
+                ..site_page.recipe.clone()
+            };
+            let options = GenerateOptions {
+                split_sections: true,
+                output_path: Some(site_page_path(config, site_page)),
+                sections_dir: Some(components_dir.join(&name)),
+                ..options.clone()
+            };
+            let mut page = self
+                .generate_from_recipe_async(&recipe, config, &options)
+                .await?;
+            page.name = site_page.page.route.clone();
+            page.content = rename_default_export(&page.content, &format!("{name}Page"));
+            generated_pages.push(page);
+        }
+
+        Ok(self.assemble_site(
+            plan,
+            pages,
+            config,
+            &shared_sections,
+            shared,
+            generated_pages,
+        ))
+    }
+
+    /// Add the Next.js layout or the react-router setup that renders the
+    /// shared sections around the pages.
+    fn assemble_site(
+        &self,
+        plan: &SitePlan,
+        pages: &[SitePageRecipe],
+        config: &ProjectConfig,
+        shared_sections: &[(&RecipeSection, bool)],
+        mut shared: Vec<GeneratedPage>,
+        generated_pages: Vec<GeneratedPage>,
+    ) -> GeneratedSite {
+        let site_name = if plan.name.is_empty() {
+            &config.name
+        } else {
+            &plan.name
+        };
+
+        let component_names = |above: bool| -> Vec<String> {
+            shared_sections
+                .iter()
+                .filter(|(_, a)| *a == above)
+                .map(|(s, _)| self.section_to_component_name(&s.section_type))
+                .collect()
+        };
+        let (above, below) = (component_names(true), component_names(false));
+
+        let mut dependencies: Vec<String> = shared
+            .iter()
+            .chain(&generated_pages)
+            .flat_map(|p| p.dependencies.iter().cloned())
+            .collect();
+
+        match config.framework {
+            FrameworkTarget::NextJs => {
+                shared.push(GeneratedPage {
+                    name: "layout".to_string(),
+                    path: config.path.join("app/layout.tsx"),
+                    content: self.assemble_nextjs_layout(site_name, &above, &below),
+                    dependencies: vec![],
+                    dev_dependencies: vec![],
+                    unmapped_icons: vec![],
+                    sections: vec![],
+                    choices: vec![],
+                });
+            }
+            _ => {
+                let routes: Vec<(String, String)> = pages
+                    .iter()
+                    .map(|p| {
+                        (
+                            p.page.route.clone(),
+                            format!("{}Page", p.page.component_name()),
+                        )
+                    })
+                    .collect();
+                shared.push(GeneratedPage {
+                    name: "router".to_string(),
+                    path: config.path.join(config.framework.main_source_path()),
+                    content: self.assemble_react_router(&routes, &above, &below),
+                    dependencies: vec!["react-router".to_string()],
+                    dev_dependencies: vec![],
+                    unmapped_icons: vec![],
+                    sections: vec![],
+                    choices: vec![],
+                });
+                dependencies.push("react-router".to_string());
+            }
+        }
+
+        dependencies.sort();
+        dependencies.dedup();
+
+        GeneratedSite {
+            pages: generated_pages,
+            shared,
+            dependencies,
+        }
+    }
+
     /// Generate a page from a recipe with real component fetching.
     ///
     /// This async method:
@@ -308,26 +809,36 @@ impl PageGenerator {
         let framework = self.framework_from_target(config.framework);
         let mut sections_code = Vec::new();
         let mut all_dependencies = HashSet::new();
-        let mut all_imports = HashSet::new();
-
-        for section in &recipe.sections {
-            // Match section to real components
-            let recommendations =
-                self.matcher
-                    .match_section(&section.section_type, &section.variant_id, 1);
+        let mut unmapped_icons = BTreeSet::new();
+        let mut split_sections = Vec::new();
+        let mut choices = Vec::new();
+        // Fetched components are whole modules whose imports and top-level
+        // declarations clash when pasted into one file, so React and Vue
+        // sections always get their own files; only HTML is inlined
+        let split = options.split_sections || framework != Framework::Html;
+        let component_names = section_component_names(&recipe.sections);
+
+        if config.tailwind_version == TailwindVersion::V3
+            && !options.allow_v4_only
+            && let Some(conflict) = self
+                .tailwind_conflicts(recipe, config, options)
+                .into_iter()
+                .next()
+        {
+            return Err(GenerateError::TailwindIncompatible(Box::new(conflict)));
+        }
 
-            let recommendation = recommendations.first().ok_or_else(|| {
-                GenerateError::NoMatchingComponent(format!(
-                    "{} (variant: {})",
-                    section.section_type, section.variant_id
-                ))
-            })?;
+        for (section, component_name) in recipe.sections.iter().zip(component_names) {
+            let component = self.resolve_component(section, &component_name, framework, options)?;
+            let component_id = component.id.clone();
 
-            // Find the full component record to get UUID and category path
-            let reader = crate::components::ComponentReader::new();
-            let component = reader
-                .find_by_id(framework, &recommendation.id)
-                .ok_or_else(|| GenerateError::ComponentNotFound(recommendation.id.clone()))?;
+            choices.push(SectionChoice {
+                name: component_name.clone(),
+                section_type: section.section_type.clone(),
+                variant_id: section.variant_id.clone(),
+                component_id: Some(component_id),
+                uuid: Some(component.uuid.clone()),
+            });
 
             // Fetch the real component code
             let code = fetcher
@@ -341,30 +852,49 @@ impl PageGenerator {
                 )
                 .await?;
 
-            // Parse and transform the fetched code
-            let (section_imports, section_body) = self.parse_component_code(&code, framework);
-
-            // Collect imports (deduplicated)
-            all_imports.extend(section_imports);
-
-            // Add section comment and body
-            let section_code = format!(
-                "      {{/* {} - {} */}}\n{}",
-                recommendation.name, section.section_type, section_body
-            );
-            sections_code.push(section_code);
+            // Swap Heroicons for the project's icon library
+            let icons = substitute_icons(&code, framework, &options.icons);
+            let drop_heroicons = icons.package.is_some() && !icons.keeps_heroicons();
+            let code = icons.code;
+            unmapped_icons.extend(icons.unmapped);
+            if let Some(package) = icons.package {
+                all_dependencies.insert(package.to_string());
+            }
 
             // Extract dependencies from component metadata
             if let Some(meta) = &component.meta {
                 for dep in &meta.dependencies.packages {
-                    all_dependencies.insert(dep.clone());
+                    if !(drop_heroicons && dep.starts_with("@heroicons/")) {
+                        all_dependencies.insert(dep.clone());
+                    }
                 }
             }
-        }
 
-        // Assemble final page
-        let imports_vec: Vec<String> = all_imports.into_iter().collect();
-        let content = self.assemble_react_page_with_imports(&imports_vec, &sections_code);
+            // Split mode keeps the fetched component intact, imports and all
+            if split {
+                // SFCs are named by their file, so only React needs renaming
+                let content = if framework == Framework::React {
+                    rename_default_export(&code, &component_name)
+                } else {
+                    code
+                };
+                split_sections.push(self.section_file(
+                    config,
+                    options,
+                    section,
+                    component_name,
+                    content,
+                ));
+                continue;
+            }
+
+            sections_code.push(format!(
+                "  <!-- {} - {} -->\n{}",
+                component.name,
+                section.section_type,
+                indent(&dedent(&code), "  ")
+            ));
+        }
 
         // Collect dependencies
         let dependencies: Vec<String> = all_dependencies.into_iter().collect();
@@ -375,15 +905,87 @@ impl PageGenerator {
             .clone()
             .unwrap_or_else(|| config.path.join(config.framework.main_source_path()));
 
+        let unmapped_icons: Vec<String> = unmapped_icons.into_iter().collect();
+
+        if split {
+            let mut page =
+                self.split_page(config, options, path, split_sections, dependencies, choices);
+            page.unmapped_icons = unmapped_icons;
+            return Ok(page);
+        }
+
+        let content = self.assemble_html_page(&config.name, &sections_code);
+
         Ok(GeneratedPage {
             name: "index".to_string(),
             path,
             content,
             dependencies,
             dev_dependencies: vec![],
+            unmapped_icons,
+            sections: vec![],
+            choices,
         })
     }
 
+    /// Find sections whose components need Tailwind v4 in a v3 project.
+    ///
+    /// Uses catalog metadata only, so it works without a fetcher. Each
+    /// conflict lists v3-compatible components from the same
+    /// sub-subcategory to switch to. Sections without a catalog match are
+    /// skipped. Always empty for v4 projects.
+    #[must_use]
+    pub fn tailwind_conflicts(
+        &self,
+        recipe: &Recipe,
+        config: &ProjectConfig,
+        options: &GenerateOptions,
+    ) -> Vec<TailwindConflict> {
+        if config.tailwind_version != TailwindVersion::V3 {
+            return vec![];
+        }
+
+        let framework = self.framework_from_target(config.framework);
+        let reader = ComponentReader::new();
+        let mut conflicts = Vec::new();
+
+        let names = section_component_names(&recipe.sections);
+        for (section, name) in recipe.sections.iter().zip(&names) {
+            let Ok(component) = self.resolve_component(section, name, framework, options) else {
+                continue;
+            };
+            if is_v3_compatible(component) {
+                continue;
+            }
+
+            let alternatives = reader
+                .all(framework)
+                .iter()
+                .filter(|other| {
+                    other.id != component.id
+                        && other.category == component.category
+                        && other.subcategory == component.subcategory
+                        && other.sub_subcategory == component.sub_subcategory
+                        && is_v3_compatible(other)
+                })
+                .map(|other| other.id.clone())
+                .collect();
+
+            conflicts.push(TailwindConflict {
+                section_type: section.section_type.clone(),
+                component_id: component.id.clone(),
+                v4_only: component
+                    .meta
+                    .as_ref()
+                    .map(|meta| meta.tailwind.v4_only.clone())
+                    .unwrap_or_default(),
+                alternatives,
+            });
+        }
+
+        conflicts
+    }
+
     /// Get component recommendations for a recipe without fetching code.
     ///
     /// Useful for previewing what components would be used before generation.
@@ -408,112 +1010,57 @@ impl PageGenerator {
     // Internal helpers
     // -------------------------------------------------------------------------
 
-    /// Parse component code to extract imports and body.
-    ///
-    /// Returns (imports, body) where imports are React import statements
-    /// and body is the JSX content to embed in the page.
-    fn parse_component_code(&self, code: &str, _framework: Framework) -> (Vec<String>, String) {
-        let mut imports = Vec::new();
-        let mut body_lines = Vec::new();
-        let mut in_component = false;
-        let mut brace_depth: u32 = 0;
-
-        for line in code.lines() {
-            let trimmed = line.trim();
-
-            // Collect import statements
-            if trimmed.starts_with("import ") {
-                // Skip React import (will be at page level)
-                if !trimmed.contains("from 'react'") && !trimmed.contains("from \"react\"") {
-                    imports.push(line.to_string());
-                }
-                continue;
-            }
-
-            // Track when we enter the component function body
-            if trimmed.starts_with("export default function")
-                || trimmed.starts_with("function ")
-                || trimmed.starts_with("export function")
-            {
-                in_component = true;
-                continue;
-            }
-
-            // Track braces to know when we're in the return statement
-            if in_component {
-                for ch in line.chars() {
-                    match ch {
-                        '{' => brace_depth += 1,
-                        '}' => brace_depth = brace_depth.saturating_sub(1),
-                        _ => {}
-                    }
-                }
-
-                // Capture lines that are part of the JSX return
-                // Skip the return statement itself
-                if !trimmed.starts_with("return") && brace_depth > 0 {
-                    body_lines.push(format!("      {}", line.trim_start()));
-                }
+    /// Resolve a section to its catalog component: the locked one if the
+    /// lockfile pins it, otherwise the best match for its variant.
+    fn resolve_component(
+        &self,
+        section: &RecipeSection,
+        component_name: &str,
+        framework: Framework,
+        options: &GenerateOptions,
+    ) -> Result<&'static ComponentRecord, GenerateError> {
+        let component_id = match options.locked_components.get(component_name) {
+            Some(id) => id.clone(),
+            None => {
+                self.matcher
+                    .match_section(&section.section_type, &section.variant_id, 1)
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| {
+                        GenerateError::NoMatchingComponent(format!(
+                            "{} (variant: {})",
+                            section.section_type, section.variant_id
+                        ))
+                    })?
+                    .id
             }
-        }
-
-        // If we didn't find a proper component structure, just use the whole code
-        let body = if body_lines.is_empty() {
-            format!("      {}", code.replace('\n', "\n      "))
-        } else {
-            body_lines.join("\n")
         };
 
-        (imports, body)
-    }
-
-    /// Assemble a React page with proper imports.
-    fn assemble_react_page_with_imports(&self, imports: &[String], sections: &[String]) -> String {
-        let imports_str = if imports.is_empty() {
-            String::new()
-        } else {
-            format!("{}\n\n", imports.join("\n"))
-        };
-
-        let sections_jsx = sections.join("\n\n");
-
-        format!(
-            r#"{imports_str}export default function App() {{
-  return (
-    <div className="min-h-screen bg-white">
-{sections_jsx}
-    </div>
-  )
-}}
-"#
-        )
+        ComponentReader::new()
+            .find_by_id(framework, &component_id)
+            .ok_or(GenerateError::ComponentNotFound(component_id))
     }
 
     const fn framework_from_target(&self, target: FrameworkTarget) -> Framework {
         match target {
             FrameworkTarget::Html => Framework::Html,
-            FrameworkTarget::ViteReact | FrameworkTarget::NextJs => Framework::React,
+            FrameworkTarget::ViteReact
+            | FrameworkTarget::NextJs
+            | FrameworkTarget::ReactRouter
+            | FrameworkTarget::Astro => Framework::React,
+            FrameworkTarget::ViteVue | FrameworkTarget::Nuxt => Framework::Vue,
         }
     }
 
     fn section_to_component_name(&self, section_type: &str) -> String {
-        // Convert section type to PascalCase component name
-        section_type
-            .split('-')
-            .map(|word| {
-                let mut chars = word.chars();
-                chars.next().map_or_else(String::new, |c| {
-                    c.to_uppercase().collect::<String>() + chars.as_str()
-                })
-            })
-            .collect()
+        component_name(section_type)
     }
 
     fn generate_section_placeholder(
         &self,
         section: &RecipeSection,
         component_name: &str,
-        _framework: Framework,
+        framework: Framework,
         options: &GenerateOptions,
     ) -> String {
         // Fill slots from options or section defaults
@@ -531,6 +1078,20 @@ impl PageGenerator {
             .or_else(|| section.slots.get("subheadline").map(String::as_str))
             .unwrap_or("Build something amazing.");
 
+        // HTML and Vue templates share the same markup
+        if matches!(framework, Framework::Html | Framework::Vue) {
+            return format!(
+                r#"  <!-- {component_name} Section - {variant_id} -->
+  <section class="py-16 px-4">
+    <div class="max-w-7xl mx-auto">
+      <h2 class="text-3xl font-bold text-gray-900">{headline}</h2>
+      <p class="mt-4 text-lg text-gray-600">{subheadline}</p>
+    </div>
+  </section>"#,
+                variant_id = section.variant_id
+            );
+        }
+
         // Generate placeholder JSX for this section
         format!(
             r#"      {{/* {component_name} Section - {variant_id} */}}
@@ -570,33 +1131,418 @@ impl PageGenerator {
         )
     }
 
-    fn react_placeholder(&self, project_name: &str) -> String {
+    /// Assemble a Vue single-file component page.
+    ///
+    /// Section templates are rendered in order inside one root element;
+    /// `imports` pull in the section components they use.
+    fn assemble_vue_page(&self, imports: &[String], sections: &[String]) -> String {
+        let script_block = if imports.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "<script setup lang=\"ts\">\n{}\n</script>\n\n",
+                imports.join("\n")
+            )
+        };
+
+        let template: Vec<String> = sections
+            .iter()
+            .map(|section| indent(&dedent(section), "    "))
+            .collect();
+
         format!(
-            r#"export default function App() {{
-  return (
-    <div className="min-h-screen bg-white">
-      <div className="mx-auto max-w-7xl px-4 py-24 sm:px-6 lg:px-8">
-        <div className="text-center">
-          <h1 className="text-4xl font-bold tracking-tight text-gray-900 sm:text-5xl">
-            Welcome to {project_name}
-          </h1>
-          <p className="mt-6 text-lg text-gray-600">
-            Your site is ready. Run <code className="rounded bg-gray-100 px-2 py-1 font-mono text-sm">draftkit generate</code> to add components.
-          </p>
-        </div>
-      </div>
-    </div>
-  )
-}}
-"#
+            r#"{script_block}<template>
+  <div class="min-h-screen bg-white">
+{}
+  </div>
+</template>
+"#,
+            template.join("\n\n")
         )
     }
 
-    fn html_placeholder(&self, project_name: &str) -> String {
-        format!(
-            r#"<!DOCTYPE html>
-<html lang="en">
-<head>
+    /// Assemble an Astro page wrapped in the project's base layout.
+    fn assemble_astro_page(
+        &self,
+        config: &ProjectConfig,
+        path: &Utf8Path,
+        imports: &[String],
+        sections: &[String],
+    ) -> String {
+        let layout = relative_import(path, &config.path.join("src/layouts/Layout.astro"));
+        let mut frontmatter = vec![format!("import Layout from '{layout}.astro'")];
+        frontmatter.extend(imports.iter().cloned());
+
+        let body: Vec<String> = sections
+            .iter()
+            .map(|section| indent(&dedent(section), "  "))
+            .collect();
+
+        format!(
+            r#"---
+{}
+---
+
+<Layout title="{}">
+{}
+</Layout>
+"#,
+            frontmatter.join("\n"),
+            config.name,
+            body.join("\n\n")
+        )
+    }
+
+    /// Build the file for one split section.
+    fn section_file(
+        &self,
+        config: &ProjectConfig,
+        options: &GenerateOptions,
+        section: &RecipeSection,
+        name: String,
+        content: String,
+    ) -> GeneratedSection {
+        let file_name = match config.framework {
+            FrameworkTarget::Html => format!("{}.html", slugify(&name)),
+            FrameworkTarget::ViteReact
+            | FrameworkTarget::NextJs
+            | FrameworkTarget::ReactRouter
+            | FrameworkTarget::Astro => format!("{name}.tsx"),
+            FrameworkTarget::ViteVue | FrameworkTarget::Nuxt => format!("{name}.vue"),
+        };
+
+        GeneratedSection {
+            section_type: section.section_type.clone(),
+            path: options
+                .sections_dir
+                .clone()
+                .unwrap_or_else(|| config.path.join(config.framework.sections_dir()))
+                .join(file_name),
+            name,
+            content,
+        }
+    }
+
+    /// Build a page that composes split section files.
+    fn split_page(
+        &self,
+        config: &ProjectConfig,
+        options: &GenerateOptions,
+        path: Utf8PathBuf,
+        sections: Vec<GeneratedSection>,
+        dependencies: Vec<String>,
+        choices: Vec<SectionChoice>,
+    ) -> GeneratedPage {
+        let content = if config.framework.is_vue() {
+            let imports: Vec<String> = sections
+                .iter()
+                .map(|section| {
+                    format!(
+                        "import {} from '{}.vue'",
+                        section.name,
+                        section_import(&path, &section.path, options)
+                    )
+                })
+                .collect();
+            let body: Vec<String> = sections
+                .iter()
+                .map(|section| format!("<{} />", section.name))
+                .collect();
+            self.assemble_vue_page(&imports, &body)
+        } else if config.framework == FrameworkTarget::Astro {
+            let imports: Vec<String> = sections
+                .iter()
+                .map(|section| {
+                    format!(
+                        "import {} from '{}'",
+                        section.name,
+                        section_import(&path, &section.path, options)
+                    )
+                })
+                .collect();
+            // Interactive sections hydrate as islands; the rest are static
+            // markup and render without shipping JavaScript.
+            let body: Vec<String> = sections
+                .iter()
+                .map(|section| {
+                    if is_interactive(&section.content) {
+                        format!("<{} client:load />", section.name)
+                    } else {
+                        format!("<{} />", section.name)
+                    }
+                })
+                .collect();
+            self.assemble_astro_page(config, &path, &imports, &body)
+        } else if config.framework == FrameworkTarget::Html {
+            // Static HTML has no imports, so the page embeds each partial
+            // under a marker naming the file it came from.
+            let bodies: Vec<String> = sections
+                .iter()
+                .map(|section| {
+                    let source = section
+                        .path
+                        .strip_prefix(&config.path)
+                        .unwrap_or(&section.path);
+                    format!(
+                        "  <!-- {}: {source} -->\n{}",
+                        section.name,
+                        indent(section.content.trim_end(), "  ")
+                    )
+                })
+                .collect();
+            self.assemble_html_page(&config.name, &bodies)
+        } else {
+            let imports: Vec<String> = sections
+                .iter()
+                .map(|section| {
+                    format!(
+                        "import {} from '{}'",
+                        section.name,
+                        section_import(&path, &section.path, options)
+                    )
+                })
+                .collect();
+            let body: Vec<String> = sections
+                .iter()
+                .map(|section| format!("      <{} />", section.name))
+                .collect();
+            // A PascalCase file (e.g. PricingPage.tsx) names its component
+            let component = path
+                .file_stem()
+                .filter(|stem| stem.starts_with(|c: char| c.is_ascii_uppercase()))
+                .unwrap_or("App");
+            format!(
+                r#"{}
+
+export default function {component}() {{
+  return (
+    <div className="min-h-screen bg-white">
+{}
+    </div>
+  )
+}}
+"#,
+                imports.join("\n"),
+                body.join("\n")
+            )
+        };
+
+        GeneratedPage {
+            name: "index".to_string(),
+            path,
+            content,
+            dependencies,
+            dev_dependencies: vec![],
+            unmapped_icons: vec![],
+            sections,
+            choices,
+        }
+    }
+
+    /// Assemble a static HTML page around section markup.
+    fn assemble_html_page(&self, project_name: &str, sections: &[String]) -> String {
+        let sections_html = sections.join("\n\n");
+
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="UTF-8">
+  <meta name="viewport" content="width=device-width, initial-scale=1.0">
+  <title>{project_name}</title>
+  <link href="./output.css" rel="stylesheet">
+</head>
+<body class="min-h-screen bg-white">
+{sections_html}
+</body>
+</html>
+"#
+        )
+    }
+
+    /// Wrap section JSX in a default-exported component.
+    fn assemble_component(&self, name: &str, sections: &[String]) -> String {
+        let sections_jsx = sections.join("\n\n");
+
+        format!(
+            r#"export default function {name}() {{
+  return (
+    <>
+{sections_jsx}
+    </>
+  )
+}}
+"#
+        )
+    }
+
+    /// Build `App.tsx` with a react-router route per page.
+    fn assemble_react_router(
+        &self,
+        routes: &[(String, String)],
+        above: &[String],
+        below: &[String],
+    ) -> String {
+        let mut imports =
+            vec!["import { BrowserRouter, Route, Routes } from 'react-router'".to_string()];
+        for name in above.iter().chain(below) {
+            imports.push(format!("import {name} from './components/{name}'"));
+        }
+        for (_, page) in routes {
+            imports.push(format!("import {page} from './pages/{page}'"));
+        }
+        let imports = imports.join("\n");
+
+        let shared_jsx = |names: &[String]| -> String {
+            names
+                .iter()
+                .map(|name| format!("        <{name} />\n"))
+                .collect()
+        };
+        let (above, below) = (shared_jsx(above), shared_jsx(below));
+
+        let routes_jsx: String = routes
+            .iter()
+            .map(|(route, page)| {
+                let path = react_router_path(route);
+                format!("          <Route path=\"{path}\" element={{<{page} />}} />\n")
+            })
+            .collect();
+
+        format!(
+            r#"{imports}
+
+export default function App() {{
+  return (
+    <BrowserRouter>
+      <div className="min-h-screen bg-white">
+{above}        <Routes>
+{routes_jsx}        </Routes>
+{below}      </div>
+    </BrowserRouter>
+  )
+}}
+"#
+        )
+    }
+
+    /// Build a Next.js root layout that renders the shared sections.
+    fn assemble_nextjs_layout(
+        &self,
+        site_name: &str,
+        above: &[String],
+        below: &[String],
+    ) -> String {
+        let mut imports = vec![
+            "import type { Metadata } from 'next'".to_string(),
+            "import './globals.css'".to_string(),
+        ];
+        for name in above.iter().chain(below) {
+            imports.push(format!("import {name} from '../components/{name}'"));
+        }
+        let imports = imports.join("\n");
+
+        let shared_jsx = |names: &[String]| -> String {
+            names
+                .iter()
+                .map(|name| format!("        <{name} />\n"))
+                .collect()
+        };
+        let (above, below) = (shared_jsx(above), shared_jsx(below));
+
+        format!(
+            r#"{imports}
+
+export const metadata: Metadata = {{
+  title: '{site_name}',
+}}
+
+export default function RootLayout({{
+  children,
+}}: {{
+  children: React.ReactNode
+}}) {{
+  return (
+    <html lang="en">
+      <body className="min-h-screen bg-white">
+{above}        {{children}}
+{below}      </body>
+    </html>
+  )
+}}
+"#
+        )
+    }
+
+    fn react_placeholder(&self, project_name: &str) -> String {
+        format!(
+            r#"export default function App() {{
+  return (
+    <div className="min-h-screen bg-white">
+      <div className="mx-auto max-w-7xl px-4 py-24 sm:px-6 lg:px-8">
+        <div className="text-center">
+          <h1 className="text-4xl font-bold tracking-tight text-gray-900 sm:text-5xl">
+            Welcome to {project_name}
+          </h1>
+          <p className="mt-6 text-lg text-gray-600">
+            Your site is ready. Run <code className="rounded bg-gray-100 px-2 py-1 font-mono text-sm">draftkit generate</code> to add components.
+          </p>
+        </div>
+      </div>
+    </div>
+  )
+}}
+"#
+        )
+    }
+
+    fn astro_placeholder(&self, project_name: &str) -> String {
+        format!(
+            r#"---
+import Layout from '../layouts/Layout.astro'
+---
+
+<Layout title="{project_name}">
+  <div class="mx-auto max-w-7xl px-4 py-24 sm:px-6 lg:px-8">
+    <div class="text-center">
+      <h1 class="text-4xl font-bold tracking-tight text-gray-900 sm:text-5xl">
+        Welcome to {project_name}
+      </h1>
+      <p class="mt-6 text-lg text-gray-600">
+        Your site is ready. Run <code class="rounded bg-gray-100 px-2 py-1 font-mono text-sm">draftkit generate</code> to add components.
+      </p>
+    </div>
+  </div>
+</Layout>
+"#
+        )
+    }
+
+    fn vue_placeholder(&self, project_name: &str) -> String {
+        format!(
+            r#"<template>
+  <div class="min-h-screen bg-white">
+    <div class="mx-auto max-w-7xl px-4 py-24 sm:px-6 lg:px-8">
+      <div class="text-center">
+        <h1 class="text-4xl font-bold tracking-tight text-gray-900 sm:text-5xl">
+          Welcome to {project_name}
+        </h1>
+        <p class="mt-6 text-lg text-gray-600">
+          Your site is ready. Run <code class="rounded bg-gray-100 px-2 py-1 font-mono text-sm">draftkit generate</code> to add components.
+        </p>
+      </div>
+    </div>
+  </div>
+</template>
+"#
+        )
+    }
+
+    fn html_placeholder(&self, project_name: &str) -> String {
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
   <meta charset="UTF-8">
   <meta name="viewport" content="width=device-width, initial-scale=1.0">
   <title>{project_name}</title>
@@ -642,6 +1588,221 @@ impl PageGenerator {
     }
 }
 
+/// Whether a component works with Tailwind v3 (assumed when unanalyzed).
+fn is_v3_compatible(component: &ComponentRecord) -> bool {
+    component
+        .meta
+        .as_ref()
+        .is_none_or(|meta| meta.tailwind.v3_compatible && meta.tailwind.v4_only.is_empty())
+}
+
+/// Convert a section type to a PascalCase component name.
+fn component_name(section_type: &str) -> String {
+    section_type
+        .split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |c| {
+                c.to_uppercase().collect::<String>() + chars.as_str()
+            })
+        })
+        .collect()
+}
+
+/// Unique component names for each section ("Content", "Content2", ...).
+pub(super) fn section_component_names(sections: &[RecipeSection]) -> Vec<String> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    sections
+        .iter()
+        .map(|section| {
+            let name = component_name(&section.section_type);
+            let count = seen.entry(name.clone()).or_insert(0);
+            *count += 1;
+            if *count == 1 {
+                name
+            } else {
+                format!("{name}{count}")
+            }
+        })
+        .collect()
+}
+
+/// Sites need client-side routing, which plain HTML projects lack.
+fn check_site_framework(config: &ProjectConfig) -> Result<(), GenerateError> {
+    if matches!(
+        config.framework,
+        FrameworkTarget::ViteReact | FrameworkTarget::NextJs
+    ) {
+        Ok(())
+    } else {
+        Err(GenerateError::FrameworkMismatch {
+            expected: "vite-react or nextjs".to_string(),
+            got: config.framework.as_str().to_string(),
+        })
+    }
+}
+
+/// Pick each shared section once, from the first page that has it, and
+/// note whether it sits above or below the page content.
+fn shared_site_sections<'a>(
+    plan: &SitePlan,
+    pages: &'a [SitePageRecipe],
+) -> Vec<(&'a RecipeSection, bool)> {
+    let mut shared_sections: Vec<(&RecipeSection, bool)> = Vec::new();
+    for page in pages {
+        let first_own = page
+            .recipe
+            .sections
+            .iter()
+            .position(|s| !plan.is_shared(&s.section_type));
+
+        for (index, section) in page.recipe.sections.iter().enumerate() {
+            if plan.is_shared(&section.section_type)
+                && !shared_sections
+                    .iter()
+                    .any(|(s, _)| s.section_type == section.section_type)
+            {
+                let above = first_own.is_none_or(|first| index < first);
+                shared_sections.push((section, above));
+            }
+        }
+    }
+    shared_sections
+}
+
+/// Directory holding a site's components.
+fn site_components_dir(config: &ProjectConfig) -> Utf8PathBuf {
+    match config.framework {
+        FrameworkTarget::NextJs => config.path.join("components"),
+        _ => config.path.join("src/components"),
+    }
+}
+
+/// File of one site page.
+fn site_page_path(config: &ProjectConfig, site_page: &SitePageRecipe) -> Utf8PathBuf {
+    match config.framework {
+        FrameworkTarget::NextJs => {
+            let route = site_page.page.route.trim_matches('/');
+            if route.is_empty() {
+                config.path.join("app/page.tsx")
+            } else {
+                config.path.join(format!("app/{route}/page.tsx"))
+            }
+        }
+        _ => config.path.join(format!(
+            "src/pages/{}Page.tsx",
+            site_page.page.component_name()
+        )),
+    }
+}
+
+/// Whether a React component needs JavaScript in the browser: it uses
+/// Headless UI, calls a hook or sets an event handler. Icon imports and
+/// plain markup render fine as static HTML.
+fn is_interactive(code: &str) -> bool {
+    if ["'@headlessui/react'", "\"@headlessui/react\""]
+        .iter()
+        .any(|source| code.contains(source))
+    {
+        return true;
+    }
+    let starts_upper = |word: &str, prefix: &str| {
+        word.strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()))
+    };
+
+    let mut start = None;
+    for (i, c) in code.char_indices().chain([(code.len(), ' ')]) {
+        let in_word = c.is_ascii_alphanumeric() || c == '_' || c == '$';
+        match (start, in_word) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                let (word, rest) = (&code[s..i], &code[i..]);
+                let hook = starts_upper(word, "use") && rest.starts_with('(');
+                let handler = starts_upper(word, "on") && rest.starts_with("={");
+                if hook || handler {
+                    return true;
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Module specifier for importing `to_file` from `from_file`, without extension.
+fn relative_import(from_file: &Utf8Path, to_file: &Utf8Path) -> String {
+    let from_dir: Vec<_> = from_file
+        .parent()
+        .map(|dir| dir.components().collect())
+        .unwrap_or_default();
+    let target = to_file.with_extension("");
+    let to: Vec<_> = target.components().collect();
+
+    let common = from_dir.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let rest: Vec<&str> = to[common..].iter().map(|c| c.as_str()).collect();
+
+    let prefix = match from_dir.len() - common {
+        0 => "./".to_string(),
+        ups => "../".repeat(ups),
+    };
+    format!("{prefix}{}", rest.join("/"))
+}
+
+/// Module specifier for a split section, through the import alias when it
+/// covers the section file.
+fn section_import(page: &Utf8Path, section: &Utf8Path, options: &GenerateOptions) -> String {
+    options
+        .import_alias
+        .as_ref()
+        .and_then(|alias| alias.specifier(section))
+        .unwrap_or_else(|| relative_import(page, section))
+}
+
+/// Rename the default-exported component in fetched code.
+fn rename_default_export(code: &str, name: &str) -> String {
+    const MARKER: &str = "export default function ";
+
+    let Some(start) = code.find(MARKER).map(|i| i + MARKER.len()) else {
+        return code.to_string();
+    };
+    let end = code[start..]
+        .find(|c: char| !c.is_alphanumeric() && c != '_')
+        .map_or(code.len(), |i| start + i);
+
+    format!("{}{name}{}", &code[..start], &code[end..])
+}
+
+/// Strip the common leading indentation from a block of markup.
+fn dedent(code: &str) -> String {
+    let margin = code
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    code.lines()
+        .map(|line| line.get(margin..).unwrap_or(""))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Indent every non-empty line of a block.
+fn indent(code: &str, prefix: &str) -> String {
+    code.lines()
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("{prefix}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 impl Default for PageGenerator {
     fn default() -> Self {
         Self::new()
@@ -725,6 +1886,375 @@ mod tests {
         assert!(page.content.contains("<!DOCTYPE html>"));
     }
 
+    fn site_recipes() -> (SitePlan, Vec<SitePageRecipe>) {
+        let plan: SitePlan = toml::from_str::<crate::scaffold::SitePlanFile>(
+            r#"
+[site]
+name = "Acme"
+[[site.pages]]
+route = "/"
+pattern = "saas-landing"
+[[site.pages]]
+route = "/pricing"
+page_type = "pricing"
+"#,
+        )
+        .unwrap()
+        .site;
+        let recipes = plan
+            .build_recipes(
+                &crate::patterns::PatternLoader::builtin_only(),
+                &HashMap::new(),
+            )
+            .unwrap();
+        (plan, recipes)
+    }
+
+    #[test]
+    fn generate_site_vite_react() {
+        let dir = TempDir::new().unwrap();
+        let base_path = Utf8Path::from_path(dir.path()).unwrap();
+        let config =
+            ProjectConfig::new("site", base_path).with_framework(FrameworkTarget::ViteReact);
+
+        let (plan, recipes) = site_recipes();
+        let site = PageGenerator::new()
+            .generate_site(&plan, &recipes, &config, &GenerateOptions::default())
+            .unwrap();
+
+        assert_eq!(site.pages.len(), 2);
+        assert!(site.pages[0].path.ends_with("src/pages/HomePage.tsx"));
+        assert!(site.pages[1].path.ends_with("src/pages/PricingPage.tsx"));
+        assert!(
+            site.pages
+                .iter()
+                .all(|p| !p.content.contains("Header Section"))
+        );
+
+        let names: Vec<&str> = site.shared.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Header", "Footer", "router"]);
+
+        let app = &site.shared[2];
+        assert!(app.path.ends_with("src/App.tsx"));
+        assert!(
+            app.content
+                .contains(r#"<Route path="/pricing" element={<PricingPage />} />"#)
+        );
+        let header = app.content.find("<Header />").unwrap();
+        let routes = app.content.find("<Routes>").unwrap();
+        let footer = app.content.find("<Footer />").unwrap();
+        assert!(header < routes && routes < footer);
+        assert!(site.dependencies.contains(&"react-router".to_string()));
+    }
+
+    #[test]
+    fn react_router_path_converts_dynamic_segments() {
+        assert_eq!(react_router_path("/"), "/");
+        assert_eq!(react_router_path("/pricing"), "/pricing");
+        assert_eq!(react_router_path("/blog/[slug]"), "/blog/:slug");
+        assert_eq!(
+            react_router_path("/shop/[category]/[id]"),
+            "/shop/:category/:id"
+        );
+        assert_eq!(react_router_path("/docs/[...rest]"), "/docs/*");
+    }
+
+    #[test]
+    fn generate_site_nextjs() {
+        let dir = TempDir::new().unwrap();
+        let base_path = Utf8Path::from_path(dir.path()).unwrap();
+        let config = ProjectConfig::new("site", base_path).with_framework(FrameworkTarget::NextJs);
+
+        let (plan, recipes) = site_recipes();
+        let site = PageGenerator::new()
+            .generate_site(&plan, &recipes, &config, &GenerateOptions::default())
+            .unwrap();
+
+        assert!(site.pages[0].path.ends_with("app/page.tsx"));
+        assert!(site.pages[1].path.ends_with("app/pricing/page.tsx"));
+
+        let layout = site.shared.iter().find(|p| p.name == "layout").unwrap();
+        assert!(layout.path.ends_with("app/layout.tsx"));
+        assert!(
+            layout
+                .content
+                .contains("import Header from '../components/Header'")
+        );
+        assert!(layout.content.contains("title: 'Acme'"));
+        assert!(site.dependencies.is_empty());
+    }
+
+    #[test]
+    fn generate_site_rejects_html() {
+        let dir = TempDir::new().unwrap();
+        let base_path = Utf8Path::from_path(dir.path()).unwrap();
+        let config = ProjectConfig::new("site", base_path).with_framework(FrameworkTarget::Html);
+
+        let (plan, recipes) = site_recipes();
+        let result = PageGenerator::new().generate_site(
+            &plan,
+            &recipes,
+            &config,
+            &GenerateOptions::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(GenerateError::FrameworkMismatch { .. })
+        ));
+    }
+
+    fn split_recipe() -> Recipe {
+        let sections = ["hero", "content", "content"]
+            .into_iter()
+            .zip(0u8..)
+            .map(|(section_type, position)| RecipeSection {
+                section_type: section_type.to_string(),
+                variant_id: format!("{section_type}-simple"),
+                position,
+                slots: HashMap::new(),
+            })
+            .collect();
+
+        Recipe {
+            pattern_id: "test".to_string(),
+            sections,
+            coherence: crate::intelligence::PageCoherence {
+                score: 1.0,
+                issues: vec![],
+                valid: true,
+                pairwise_scores: vec![],
+            },
+            dependencies: vec![],
+        }
+    }
+
+    #[test]
+    fn generate_split_sections_react() {
+        let dir = TempDir::new().unwrap();
+        let base_path = Utf8Path::from_path(dir.path()).unwrap();
+        let config =
+            ProjectConfig::new("split", base_path).with_framework(FrameworkTarget::ViteReact);
+        let options = GenerateOptions::default().with_split_sections();
+
+        let generator = PageGenerator::new();
+        let page = generator
+            .generate_from_recipe(&split_recipe(), &config, &options)
+            .unwrap();
+
+        let names: Vec<&str> = page.sections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["Hero", "Content", "Content2"]);
+        assert!(
+            page.sections[2]
+                .path
+                .ends_with("src/components/sections/Content2.tsx")
+        );
+        assert!(
+            page.sections[0]
+                .content
+                .contains("export default function Hero()")
+        );
+        assert!(
+            page.content
+                .contains("import Content2 from './components/sections/Content2'")
+        );
+        assert!(page.content.contains("<Hero />"));
+        assert!(!page.content.contains("Hero Section"));
+
+        generator.write_page(&page).unwrap();
+        assert!(
+            config
+                .path
+                .join("src/components/sections/Hero.tsx")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn generate_split_sections_html() {
+        let dir = TempDir::new().unwrap();
+        let base_path = Utf8Path::from_path(dir.path()).unwrap();
+        let config = ProjectConfig::new("split", base_path).with_framework(FrameworkTarget::Html);
+        let options = GenerateOptions::default().with_split_sections();
+
+        let page = PageGenerator::new()
+            .generate_from_recipe(&split_recipe(), &config, &options)
+            .unwrap();
+
+        assert!(page.sections[0].path.ends_with("sections/hero.html"));
+        assert!(page.sections[0].content.starts_with("<!-- Hero Section"));
+        assert!(!page.sections[0].content.contains("className"));
+        assert!(page.content.starts_with("<!DOCTYPE html>"));
+        assert!(page.content.contains("<!-- Hero: sections/hero.html -->"));
+    }
+
+    #[test]
+    fn generate_vue_page() {
+        let dir = TempDir::new().unwrap();
+        let base_path = Utf8Path::from_path(dir.path()).unwrap();
+        let config = ProjectConfig::new("vue", base_path).with_framework(FrameworkTarget::Nuxt);
+
+        let generator = PageGenerator::new();
+        let page = generator
+            .generate_from_recipe(&split_recipe(), &config, &GenerateOptions::default())
+            .unwrap();
+        assert!(page.path.ends_with("pages/index.vue"));
+        assert!(page.content.starts_with("<template>"));
+        assert!(page.content.contains("<!-- Hero Section - hero-simple -->"));
+        assert!(!page.content.contains("className"));
+
+        let options = GenerateOptions::default().with_split_sections();
+        let page = generator
+            .generate_from_recipe(&split_recipe(), &config, &options)
+            .unwrap();
+        assert!(
+            page.sections[0]
+                .path
+                .ends_with("components/sections/Hero.vue")
+        );
+        assert!(
+            page.sections[0]
+                .content
+                .starts_with("<template>\n  <!-- Hero")
+        );
+        assert!(page.content.starts_with("<script setup lang=\"ts\">"));
+        assert!(
+            page.content
+                .contains("import Hero from '../components/sections/Hero.vue'")
+        );
+        assert!(page.content.contains("    <Content2 />"));
+    }
+
+    #[test]
+    fn generate_astro_page() {
+        let dir = TempDir::new().unwrap();
+        let base_path = Utf8Path::from_path(dir.path()).unwrap();
+        let config = ProjectConfig::new("astro", base_path).with_framework(FrameworkTarget::Astro);
+
+        let generator = PageGenerator::new();
+        let page = generator
+            .generate_from_recipe(&split_recipe(), &config, &GenerateOptions::default())
+            .unwrap();
+        assert!(page.path.ends_with("src/pages/index.astro"));
+        assert!(
+            page.content
+                .starts_with("---\nimport Layout from '../layouts/Layout.astro'\n---")
+        );
+        assert!(page.content.contains("<Layout title=\"astro\">"));
+        assert!(page.content.contains("  <section class=\"py-16 px-4\">"));
+
+        let options = GenerateOptions::default().with_split_sections();
+        let page = generator
+            .generate_from_recipe(&split_recipe(), &config, &options)
+            .unwrap();
+        assert!(
+            page.sections[0]
+                .path
+                .ends_with("src/components/sections/Hero.tsx")
+        );
+        assert!(
+            page.content
+                .contains("import Hero from '../components/sections/Hero'")
+        );
+        assert!(page.content.contains("  <Hero />"));
+    }
+
+    #[test]
+    fn interactive_sections_need_hydration() {
+        let static_hero = "import { ArrowRightIcon } from '@heroicons/react/20/solid'\n\n\
+            export default function Hero() {\n  return <p>Use our import tools</p>\n}\n";
+        assert!(!is_interactive(static_hero));
+        assert!(!is_interactive("<button className=\"useful\">Go</button>"));
+
+        assert!(is_interactive(
+            "const [open, setOpen] = useState(false)\nreturn <div />"
+        ));
+        assert!(is_interactive(
+            "<button onClick={() => alert('hi')}>Go</button>"
+        ));
+        assert!(is_interactive(
+            "import { Dialog } from '@headlessui/react'\nreturn <Dialog />"
+        ));
+    }
+
+    #[test]
+    fn generate_react_router_page() {
+        let dir = TempDir::new().unwrap();
+        let base_path = Utf8Path::from_path(dir.path()).unwrap();
+        let config =
+            ProjectConfig::new("rr", base_path).with_framework(FrameworkTarget::ReactRouter);
+        let options = GenerateOptions::default().with_split_sections();
+
+        let page = PageGenerator::new()
+            .generate_from_recipe(&split_recipe(), &config, &options)
+            .unwrap();
+        assert!(page.path.ends_with("app/routes/home.tsx"));
+        assert!(
+            page.sections[0]
+                .path
+                .ends_with("app/components/sections/Hero.tsx")
+        );
+        assert!(
+            page.content
+                .contains("import Hero from '../components/sections/Hero'")
+        );
+    }
+
+    #[test]
+    fn v3_compatibility_from_metadata() {
+        let record = |meta: &str| -> ComponentRecord {
+            serde_json::from_str(&format!(
+                r#"{{"id": "a", "uuid": "u", "name": "A", "category": "c", "subcategory": "s", "sub_subcategory": "ss"{meta}}}"#
+            ))
+            .unwrap()
+        };
+
+        assert!(is_v3_compatible(&record("")));
+        assert!(is_v3_compatible(&record(r#", "meta": {}"#)));
+        assert!(!is_v3_compatible(&record(
+            r#", "meta": {"tailwind": {"v4_only": ["size-"], "v3_compatible": false}}"#
+        )));
+    }
+
+    #[test]
+    fn tailwind_conflicts_empty_for_v4() {
+        let dir = TempDir::new().unwrap();
+        let base_path = Utf8Path::from_path(dir.path()).unwrap();
+        let config = ProjectConfig::new("v4", base_path);
+
+        let conflicts = PageGenerator::new().tailwind_conflicts(
+            &split_recipe(),
+            &config,
+            &GenerateOptions::default(),
+        );
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn relative_import_paths() {
+        assert_eq!(
+            relative_import(
+                Utf8Path::new("/p/src/App.tsx"),
+                Utf8Path::new("/p/src/components/sections/Hero.tsx")
+            ),
+            "./components/sections/Hero"
+        );
+        assert_eq!(
+            relative_import(
+                Utf8Path::new("/p/app/page.tsx"),
+                Utf8Path::new("/p/components/sections/Hero.tsx")
+            ),
+            "../components/sections/Hero"
+        );
+    }
+
+    #[test]
+    fn rename_default_export_keeps_imports() {
+        let code = "import { Dialog } from '@headlessui/react'\n\nexport default function Example() {\n  return <Dialog />\n}\n";
+        let renamed = rename_default_export(code, "Hero");
+        assert!(renamed.starts_with("import { Dialog }"));
+        assert!(renamed.contains("export default function Hero() {"));
+    }
+
     #[test]
     fn write_page_creates_file() {
         let dir = TempDir::new().unwrap();
@@ -737,6 +2267,9 @@ mod tests {
             content: "// test content".to_string(),
             dependencies: vec![],
             dev_dependencies: vec![],
+            unmapped_icons: vec![],
+            sections: vec![],
+            choices: vec![],
         };
 
         let generator = PageGenerator::new();
@@ -0,0 +1,615 @@
+//! Icon library substitution for fetched component code.
+//!
+//! Catalog components import Heroicons. Projects standardized on another
+//! icon set can have those imports rewritten during generation: each
+//! `@heroicons/*` import is replaced by an import from the chosen library,
+//! and every usage of the icon (JSX tags, Vue templates, `icon: Bars3Icon`
+//! references) is renamed to match.
+//!
+//! Names come from a bundled Heroicons → Lucide/Phosphor/Tabler table.
+//! Project config and presets can add or override entries with
+//! [`IconOptions::mappings`]; icons with no mapping keep their Heroicons
+//! import and are reported so they can be mapped by hand.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use regex_lite::{Captures, Regex};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::components::Framework;
+
+/// Icon library used by generated code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum IconLibrary {
+    /// Heroicons, as shipped by the catalog (no substitution)
+    #[default]
+    Heroicons,
+    /// Lucide (`lucide-react`, `lucide-vue-next`)
+    Lucide,
+    /// Phosphor (`@phosphor-icons/react`, `@phosphor-icons/vue`)
+    Phosphor,
+    /// Tabler (`@tabler/icons-react`, `@tabler/icons-vue`)
+    Tabler,
+}
+
+impl IconLibrary {
+    /// All supported libraries.
+    pub const ALL: [Self; 4] = [Self::Heroicons, Self::Lucide, Self::Phosphor, Self::Tabler];
+
+    /// Get the library identifier.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Heroicons => "heroicons",
+            Self::Lucide => "lucide",
+            Self::Phosphor => "phosphor",
+            Self::Tabler => "tabler",
+        }
+    }
+
+    /// npm package providing the library's components for `framework`.
+    ///
+    /// `None` for plain HTML, which has no component imports.
+    #[must_use]
+    pub const fn package(&self, framework: Framework) -> Option<&'static str> {
+        let vue = match framework {
+            Framework::Html => return None,
+            Framework::React => false,
+            Framework::Vue => true,
+        };
+        Some(match (self, vue) {
+            (Self::Heroicons, false) => "@heroicons/react",
+            (Self::Heroicons, true) => "@heroicons/vue",
+            (Self::Lucide, false) => "lucide-react",
+            (Self::Lucide, true) => "lucide-vue-next",
+            (Self::Phosphor, false) => "@phosphor-icons/react",
+            (Self::Phosphor, true) => "@phosphor-icons/vue",
+            (Self::Tabler, false) => "@tabler/icons-react",
+            (Self::Tabler, true) => "@tabler/icons-vue",
+        })
+    }
+
+    /// Export name for a base icon name from the bundled table.
+    ///
+    /// The `*Icon` forms are used where the library offers them so names
+    /// don't collide with components like Headless UI's `Menu`.
+    fn export_name(self, base: &str, framework: Framework) -> String {
+        match (self, framework) {
+            (Self::Phosphor, Framework::Vue) => format!("Ph{base}"),
+            (Self::Tabler, _) => format!("Icon{base}"),
+            _ => format!("{base}Icon"),
+        }
+    }
+
+    /// Column of the bundled table holding this library's names.
+    const fn column(self) -> Option<usize> {
+        match self {
+            Self::Heroicons => None,
+            Self::Lucide => Some(0),
+            Self::Phosphor => Some(1),
+            Self::Tabler => Some(2),
+        }
+    }
+}
+
+impl fmt::Display for IconLibrary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IconLibrary {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|library| library.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!(
+                    "Unknown icon library '{s}'. Valid options: heroicons, lucide, phosphor, tabler"
+                )
+            })
+    }
+}
+
+/// Icon settings from project config, presets and the command line.
+///
+/// ```toml
+/// [icons]
+/// library = "lucide"
+///
+/// [icons.mappings]
+/// Bars3Icon = "AlignJustifyIcon"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(default)]
+pub struct IconOptions {
+    /// Library to rewrite Heroicons to (`None` keeps Heroicons)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub library: Option<IconLibrary>,
+    /// Heroicons export name → export name in the target library,
+    /// overriding the bundled table
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub mappings: BTreeMap<String, String>,
+}
+
+impl IconOptions {
+    /// Options rewriting to `library` with the bundled names.
+    #[must_use]
+    pub const fn new(library: IconLibrary) -> Self {
+        Self {
+            library: Some(library),
+            mappings: BTreeMap::new(),
+        }
+    }
+
+    /// Layer `other` on top: its library wins when set, and its mappings
+    /// replace ours by name.
+    pub fn merge(&mut self, other: &Self) {
+        if other.library.is_some() {
+            self.library = other.library;
+        }
+        self.mappings.extend(
+            other
+                .mappings
+                .iter()
+                .map(|(from, to)| (from.clone(), to.clone())),
+        );
+    }
+
+    /// Whether generated code gets rewritten at all.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.library
+            .is_some_and(|library| library != IconLibrary::Heroicons)
+    }
+
+    /// Target export name for a Heroicons export like `Bars3Icon`.
+    #[must_use]
+    pub fn map(&self, heroicon: &str, framework: Framework) -> Option<String> {
+        if let Some(mapped) = self.mappings.get(heroicon) {
+            return Some(mapped.clone());
+        }
+        let library = self.library?;
+        let column = library.column()?;
+        let base = heroicon.strip_suffix("Icon").unwrap_or(heroicon);
+        ICONS
+            .iter()
+            .find(|(hero, _)| *hero == base)
+            .map(|(_, names)| library.export_name(names[column], framework))
+    }
+}
+
+/// Component code after icon substitution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IconSubstitution {
+    /// Rewritten code
+    pub code: String,
+    /// Package the rewritten imports come from, when any icon was mapped
+    pub package: Option<&'static str>,
+    /// Heroicons left in place because nothing maps them, sorted
+    pub unmapped: Vec<String>,
+}
+
+impl IconSubstitution {
+    /// Whether the code still imports from Heroicons.
+    #[must_use]
+    pub const fn keeps_heroicons(&self) -> bool {
+        !self.unmapped.is_empty()
+    }
+}
+
+/// Rewrite the Heroicons imports and usages in `code` for `options`.
+///
+/// Aliased imports (`CheckIcon as CheckSolid`) keep their local name.
+/// Returns the code unchanged when `options` is inactive or the code has
+/// no Heroicons imports.
+#[must_use]
+pub fn substitute_icons(
+    code: &str,
+    framework: Framework,
+    options: &IconOptions,
+) -> IconSubstitution {
+    let unchanged = || IconSubstitution {
+        code: code.to_string(),
+        package: None,
+        unmapped: vec![],
+    };
+    let Some(package) = options
+        .library
+        .filter(|_| options.is_active())
+        .and_then(|library| library.package(framework))
+    else {
+        return unchanged();
+    };
+
+    let mut before = None;
+    let mut rest = String::new();
+    let mut last = 0;
+    let mut quote = '\'';
+    let mut semicolon = false;
+    let mut mapped = BTreeSet::new();
+    let mut kept: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    let mut renames = HashMap::new();
+    let mut unmapped = BTreeSet::new();
+
+    for caps in import_re().captures_iter(code) {
+        let whole = caps.get(0).expect("match");
+        rest.push_str(&code[last..whole.start()]);
+        last = whole.end();
+        if code[last..].starts_with('\n') {
+            last += 1;
+        }
+        if before.is_none() {
+            before = Some(std::mem::take(&mut rest));
+            quote = caps[2].chars().next().unwrap_or('\'');
+            semicolon = caps.get(4).is_some();
+        }
+
+        let source = caps.get(3).expect("source").as_str();
+        for specifier in caps.get(1).expect("specifiers").as_str().split(',') {
+            let mut parts = specifier.split_whitespace();
+            let (Some(name), local) = (parts.next(), parts.nth(1)) else {
+                continue;
+            };
+            match options.map(name, framework) {
+                Some(target) => match local {
+                    Some(local) => {
+                        mapped.insert(format!("{target} as {local}"));
+                    }
+                    None => {
+                        renames.insert(name.to_string(), target.clone());
+                        mapped.insert(target);
+                    }
+                },
+                None => {
+                    unmapped.insert(name.to_string());
+                    kept.entry(source).or_default().push(specifier.trim());
+                }
+            }
+        }
+    }
+    let Some(before) = before else {
+        return unchanged();
+    };
+    rest.push_str(&code[last..]);
+
+    let indent = &before[before.rfind('\n').map_or(0, |i| i + 1)..];
+    let end = if semicolon { ";" } else { "" };
+    let any_mapped = !mapped.is_empty();
+    let mut imports = Vec::new();
+    if any_mapped {
+        let names: Vec<_> = mapped.into_iter().collect();
+        imports.push(format!(
+            "import {{ {} }} from {quote}{package}{quote}{end}",
+            names.join(", ")
+        ));
+    }
+    for (source, names) in kept {
+        imports.push(format!(
+            "import {{ {} }} from {quote}{source}{quote}{end}",
+            names.join(", ")
+        ));
+    }
+    let imports = imports.join(&format!("\n{indent}"));
+
+    let rename = |text: &str| {
+        identifier_re()
+            .replace_all(text, |caps: &Captures<'_>| {
+                renames
+                    .get(&caps[0])
+                    .cloned()
+                    .unwrap_or_else(|| caps[0].to_string())
+            })
+            .into_owned()
+    };
+    let code = if imports.is_empty() {
+        format!(
+            "{}{}",
+            rename(before.trim_end_matches([' ', '\t'])),
+            rename(&rest)
+        )
+    } else {
+        format!("{}{imports}\n{}", rename(&before), rename(&rest))
+    };
+
+    IconSubstitution {
+        code,
+        package: any_mapped.then_some(package),
+        unmapped: unmapped.into_iter().collect(),
+    }
+}
+
+/// `import { ... } from '@heroicons/<framework>/<variant>'`
+fn import_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"import\s*\{([^}]*)\}\s*from\s*(['"])(@heroicons/[^'"]+)['"](;)?"#)
+            .expect("valid regex")
+    })
+}
+
+/// Identifiers that may name an icon.
+fn identifier_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\b[A-Z][A-Za-z0-9]*\b").expect("valid regex"))
+}
+
+/// Heroicons base names (without `Icon`) and their Lucide, Phosphor and
+/// Tabler base names.
+const ICONS: &[(&str, [&str; 3])] = &[
+    ("AcademicCap", ["GraduationCap", "GraduationCap", "School"]),
+    (
+        "AdjustmentsHorizontal",
+        [
+            "SlidersHorizontal",
+            "SlidersHorizontal",
+            "AdjustmentsHorizontal",
+        ],
+    ),
+    ("ArrowDown", ["ArrowDown", "ArrowDown", "ArrowDown"]),
+    ("ArrowDownTray", ["Download", "DownloadSimple", "Download"]),
+    ("ArrowLeft", ["ArrowLeft", "ArrowLeft", "ArrowLeft"]),
+    ("ArrowPath", ["RefreshCw", "ArrowsClockwise", "Refresh"]),
+    ("ArrowRight", ["ArrowRight", "ArrowRight", "ArrowRight"]),
+    ("ArrowRightOnRectangle", ["LogOut", "SignOut", "Logout"]),
+    (
+        "ArrowRightStartOnRectangle",
+        ["LogOut", "SignOut", "Logout"],
+    ),
+    (
+        "ArrowTopRightOnSquare",
+        ["ExternalLink", "ArrowSquareOut", "ExternalLink"],
+    ),
+    ("ArrowUp", ["ArrowUp", "ArrowUp", "ArrowUp"]),
+    ("ArrowUpTray", ["Upload", "UploadSimple", "Upload"]),
+    ("Bars3", ["Menu", "List", "Menu2"]),
+    (
+        "Bars3BottomLeft",
+        ["AlignLeft", "TextAlignLeft", "AlignLeft"],
+    ),
+    ("Bell", ["Bell", "Bell", "Bell"]),
+    ("Bolt", ["Zap", "Lightning", "Bolt"]),
+    ("BookOpen", ["BookOpen", "BookOpen", "Book"]),
+    ("Bookmark", ["Bookmark", "BookmarkSimple", "Bookmark"]),
+    ("Briefcase", ["Briefcase", "Briefcase", "Briefcase"]),
+    ("BuildingOffice", ["Building", "Building", "Building"]),
+    ("Calendar", ["Calendar", "Calendar", "Calendar"]),
+    (
+        "CalendarDays",
+        ["CalendarDays", "CalendarDots", "CalendarEvent"],
+    ),
+    ("Camera", ["Camera", "Camera", "Camera"]),
+    ("ChartBar", ["ChartColumn", "ChartBar", "ChartBar"]),
+    ("ChartPie", ["ChartPie", "ChartPie", "ChartPie"]),
+    ("ChatBubbleLeft", ["MessageSquare", "Chat", "Message"]),
+    (
+        "ChatBubbleLeftRight",
+        ["MessagesSquare", "Chats", "Messages"],
+    ),
+    ("Check", ["Check", "Check", "Check"]),
+    ("CheckCircle", ["CircleCheck", "CheckCircle", "CircleCheck"]),
+    ("ChevronDown", ["ChevronDown", "CaretDown", "ChevronDown"]),
+    ("ChevronLeft", ["ChevronLeft", "CaretLeft", "ChevronLeft"]),
+    (
+        "ChevronRight",
+        ["ChevronRight", "CaretRight", "ChevronRight"],
+    ),
+    ("ChevronUp", ["ChevronUp", "CaretUp", "ChevronUp"]),
+    (
+        "ChevronUpDown",
+        ["ChevronsUpDown", "CaretUpDown", "Selector"],
+    ),
+    ("Clock", ["Clock", "Clock", "Clock"]),
+    ("Cloud", ["Cloud", "Cloud", "Cloud"]),
+    (
+        "CloudArrowUp",
+        ["CloudUpload", "CloudArrowUp", "CloudUpload"],
+    ),
+    ("CodeBracket", ["Code", "Code", "Code"]),
+    ("Cog6Tooth", ["Settings", "Gear", "Settings"]),
+    ("CommandLine", ["Terminal", "Terminal", "Terminal"]),
+    ("ComputerDesktop", ["Monitor", "Desktop", "DeviceDesktop"]),
+    ("CpuChip", ["Cpu", "Cpu", "Cpu"]),
+    ("CreditCard", ["CreditCard", "CreditCard", "CreditCard"]),
+    (
+        "CurrencyDollar",
+        ["DollarSign", "CurrencyDollar", "CurrencyDollar"],
+    ),
+    (
+        "DevicePhoneMobile",
+        ["Smartphone", "DeviceMobile", "DeviceMobile"],
+    ),
+    ("Document", ["File", "File", "File"]),
+    ("DocumentDuplicate", ["Copy", "Copy", "Copy"]),
+    ("DocumentText", ["FileText", "FileText", "FileText"]),
+    ("EllipsisHorizontal", ["Ellipsis", "DotsThree", "Dots"]),
+    (
+        "EllipsisVertical",
+        ["EllipsisVertical", "DotsThreeVertical", "DotsVertical"],
+    ),
+    ("Envelope", ["Mail", "Envelope", "Mail"]),
+    (
+        "ExclamationCircle",
+        ["CircleAlert", "WarningCircle", "AlertCircle"],
+    ),
+    (
+        "ExclamationTriangle",
+        ["TriangleAlert", "Warning", "AlertTriangle"],
+    ),
+    ("Eye", ["Eye", "Eye", "Eye"]),
+    ("EyeSlash", ["EyeOff", "EyeSlash", "EyeOff"]),
+    ("FaceSmile", ["Smile", "Smiley", "MoodSmile"]),
+    ("FingerPrint", ["Fingerprint", "Fingerprint", "Fingerprint"]),
+    ("Fire", ["Flame", "Fire", "Flame"]),
+    ("Folder", ["Folder", "Folder", "Folder"]),
+    ("Funnel", ["Funnel", "Funnel", "Filter"]),
+    ("Gift", ["Gift", "Gift", "Gift"]),
+    ("GlobeAlt", ["Globe", "Globe", "World"]),
+    ("HandThumbUp", ["ThumbsUp", "ThumbsUp", "ThumbUp"]),
+    ("Heart", ["Heart", "Heart", "Heart"]),
+    ("Home", ["House", "House", "Home"]),
+    ("Inbox", ["Inbox", "Tray", "Inbox"]),
+    ("InformationCircle", ["Info", "Info", "InfoCircle"]),
+    ("Key", ["Key", "Key", "Key"]),
+    ("Lifebuoy", ["LifeBuoy", "Lifebuoy", "Lifebuoy"]),
+    ("Link", ["Link", "Link", "Link"]),
+    ("ListBullet", ["List", "ListBullets", "List"]),
+    ("LockClosed", ["Lock", "Lock", "Lock"]),
+    ("LockOpen", ["LockOpen", "LockOpen", "LockOpen"]),
+    ("MagnifyingGlass", ["Search", "MagnifyingGlass", "Search"]),
+    ("MapPin", ["MapPin", "MapPin", "MapPin"]),
+    ("Minus", ["Minus", "Minus", "Minus"]),
+    ("Moon", ["Moon", "Moon", "Moon"]),
+    ("Newspaper", ["Newspaper", "Newspaper", "News"]),
+    ("PaperAirplane", ["Send", "PaperPlaneRight", "Send"]),
+    ("PaperClip", ["Paperclip", "Paperclip", "Paperclip"]),
+    ("Pause", ["Pause", "Pause", "PlayerPause"]),
+    ("Pencil", ["Pencil", "Pencil", "Pencil"]),
+    ("PencilSquare", ["SquarePen", "NotePencil", "Edit"]),
+    ("Phone", ["Phone", "Phone", "Phone"]),
+    ("Photo", ["Image", "Image", "Photo"]),
+    ("Play", ["Play", "Play", "PlayerPlay"]),
+    ("Plus", ["Plus", "Plus", "Plus"]),
+    ("PlusCircle", ["CirclePlus", "PlusCircle", "CirclePlus"]),
+    (
+        "QuestionMarkCircle",
+        ["CircleHelp", "Question", "HelpCircle"],
+    ),
+    ("RocketLaunch", ["Rocket", "RocketLaunch", "Rocket"]),
+    ("Scale", ["Scale", "Scales", "Scale"]),
+    ("ServerStack", ["Server", "HardDrives", "Server"]),
+    ("Share", ["Share2", "ShareNetwork", "Share"]),
+    ("ShieldCheck", ["ShieldCheck", "ShieldCheck", "ShieldCheck"]),
+    ("ShoppingBag", ["ShoppingBag", "ShoppingBag", "ShoppingBag"]),
+    (
+        "ShoppingCart",
+        ["ShoppingCart", "ShoppingCart", "ShoppingCart"],
+    ),
+    ("Sparkles", ["Sparkles", "Sparkle", "Sparkles"]),
+    ("Squares2X2", ["LayoutGrid", "SquaresFour", "LayoutGrid"]),
+    ("Star", ["Star", "Star", "Star"]),
+    ("Sun", ["Sun", "Sun", "Sun"]),
+    ("Tag", ["Tag", "Tag", "Tag"]),
+    ("Trash", ["Trash2", "Trash", "Trash"]),
+    ("Truck", ["Truck", "Truck", "Truck"]),
+    ("User", ["User", "User", "User"]),
+    ("UserCircle", ["CircleUser", "UserCircle", "UserCircle"]),
+    ("UserGroup", ["Users", "UsersThree", "Users"]),
+    ("Users", ["Users", "Users", "Users"]),
+    ("VideoCamera", ["Video", "VideoCamera", "Video"]),
+    ("Wifi", ["Wifi", "WifiHigh", "Wifi"]),
+    ("XCircle", ["CircleX", "XCircle", "CircleX"]),
+    ("XMark", ["X", "X", "X"]),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAVBAR: &str = r#"'use client'
+
+import { useState } from 'react'
+import { Dialog } from '@headlessui/react'
+import { Bars3Icon, XMarkIcon } from '@heroicons/react/24/outline'
+import { CheckIcon as CheckSolid, CubeTransparentIcon } from '@heroicons/react/20/solid'
+
+export default function Example() {
+  return (
+    <nav>
+      <Bars3Icon aria-hidden="true" className="size-6" />
+      <XMarkIcon className="size-6" />
+      <CheckSolid className="size-5" />
+      <CubeTransparentIcon className="size-5" />
+    </nav>
+  )
+}
+"#;
+
+    #[test]
+    fn inactive_options_leave_code_alone() {
+        let result = substitute_icons(NAVBAR, Framework::React, &IconOptions::default());
+        assert_eq!(result.code, NAVBAR);
+        assert_eq!(result.package, None);
+
+        let heroicons = IconOptions::new(IconLibrary::Heroicons);
+        assert_eq!(
+            substitute_icons(NAVBAR, Framework::React, &heroicons).code,
+            NAVBAR
+        );
+    }
+
+    #[test]
+    fn rewrites_imports_and_usages() {
+        let result = substitute_icons(
+            NAVBAR,
+            Framework::React,
+            &IconOptions::new(IconLibrary::Lucide),
+        );
+        assert_eq!(result.package, Some("lucide-react"));
+        assert_eq!(result.unmapped, ["CubeTransparentIcon"]);
+        assert!(result.keeps_heroicons());
+        assert!(result.code.contains(
+            "import { Dialog } from '@headlessui/react'\n\
+             import { CheckIcon as CheckSolid, MenuIcon, XIcon } from 'lucide-react'\n\
+             import { CubeTransparentIcon } from '@heroicons/react/20/solid'\n\n"
+        ));
+        assert!(result.code.contains("<MenuIcon aria-hidden=\"true\""));
+        assert!(result.code.contains("<XIcon className"));
+        assert!(result.code.contains("<CheckSolid className"));
+        assert!(!result.code.contains("Bars3Icon"));
+    }
+
+    #[test]
+    fn library_naming_per_framework() {
+        let code = "<script setup>\nimport { Bars3Icon } from \"@heroicons/vue/24/outline\";\n</script>\n\n<template>\n  <Bars3Icon class=\"size-6\" />\n</template>\n";
+        let phosphor = substitute_icons(
+            code,
+            Framework::Vue,
+            &IconOptions::new(IconLibrary::Phosphor),
+        );
+        assert_eq!(
+            phosphor.code,
+            "<script setup>\nimport { PhList } from \"@phosphor-icons/vue\";\n</script>\n\n<template>\n  <PhList class=\"size-6\" />\n</template>\n"
+        );
+        assert!(!phosphor.keeps_heroicons());
+
+        let tabler = substitute_icons(code, Framework::Vue, &IconOptions::new(IconLibrary::Tabler));
+        assert_eq!(tabler.package, Some("@tabler/icons-vue"));
+        assert!(tabler.code.contains("<IconMenu2 class"));
+    }
+
+    #[test]
+    fn mappings_override_the_table() {
+        let mut options = IconOptions::new(IconLibrary::Lucide);
+        options.merge(&IconOptions {
+            library: None,
+            mappings: BTreeMap::from([
+                ("Bars3Icon".to_string(), "AlignJustifyIcon".to_string()),
+                ("CubeTransparentIcon".to_string(), "BoxIcon".to_string()),
+            ]),
+        });
+        assert_eq!(options.library, Some(IconLibrary::Lucide));
+
+        let result = substitute_icons(NAVBAR, Framework::React, &options);
+        assert!(result.unmapped.is_empty());
+        assert!(!result.code.contains("@heroicons"));
+        assert!(result.code.contains("<AlignJustifyIcon aria-hidden"));
+        assert!(result.code.contains("<BoxIcon className"));
+    }
+
+    #[test]
+    fn parse_library() {
+        assert_eq!("Lucide".parse(), Ok(IconLibrary::Lucide));
+        assert!("feather".parse::<IconLibrary>().is_err());
+    }
+}
@@ -0,0 +1,533 @@
+//! Lockfile of generated component choices.
+//!
+//! `.draftkit/lock.toml` records, for every generated page, which variant
+//! and component each section resolved to and a hash of every file
+//! written. Regeneration reuses the locked choices so output stays stable,
+//! and the hashes reveal files that were edited by hand since:
+//!
+//! ```toml
+//! version = 1
+//!
+//! [pages.index]
+//! pattern = "saas-landing"
+//! path = "src/App.tsx"
+//! hash = "fnv1a64:3c6e0b8a9c15224a"
+//!
+//! [[pages.index.sections]]
+//! name = "Hero"
+//! type = "hero"
+//! variant = "hero-split-screenshot"
+//! mode = "light"
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::{Deserialize, Serialize};
+
+use super::{GenerateError, GeneratedPage, VirtualFs};
+use crate::components::Mode;
+use crate::intelligence::Recipe;
+
+/// Lockfile location, relative to the project root.
+pub const LOCK_FILE: &str = ".draftkit/lock.toml";
+
+const LOCK_VERSION: u32 = 1;
+
+/// Recorded component choices for every generated page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerateLock {
+    /// Lockfile format version
+    pub version: u32,
+    /// Locked pages keyed by page name
+    #[serde(default)]
+    pub pages: BTreeMap<String, LockedPage>,
+    /// Hashes of shared site files (layouts, routers), keyed by path
+    /// relative to the project root
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<Utf8PathBuf, String>,
+}
+
+impl Default for GenerateLock {
+    fn default() -> Self {
+        Self {
+            version: LOCK_VERSION,
+            pages: BTreeMap::new(),
+            files: BTreeMap::new(),
+        }
+    }
+}
+
+/// A generated page and the sections it was built from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockedPage {
+    /// Pattern the page was generated from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Preset applied when generating
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    /// Page file, relative to the project root
+    pub path: Utf8PathBuf,
+    /// Hash of the page file as written
+    pub hash: String,
+    /// Sections in page order
+    #[serde(default)]
+    pub sections: Vec<LockedSection>,
+}
+
+/// The component chosen for one section.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockedSection {
+    /// Component name, unique within the page
+    pub name: String,
+    /// Section type
+    #[serde(rename = "type")]
+    pub section_type: String,
+    /// Variant the section resolved to
+    pub variant: String,
+    /// Catalog component ID, when real code was fetched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+    /// Catalog component UUID, when real code was fetched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+    /// Theme mode the section was generated in
+    pub mode: String,
+    /// Section file, relative to the project root, when split out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<Utf8PathBuf>,
+    /// Hash of the section file as written
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+}
+
+impl GenerateLock {
+    /// Load the lockfile from a project, or an empty lock if there is none.
+    pub fn load(project: &Utf8Path) -> Result<Self, GenerateError> {
+        let path = project.join(LOCK_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let lock: Self = toml::from_str(&content)
+            .map_err(|e| GenerateError::InvalidLock(format!("{path}: {e}")))?;
+        if lock.version > LOCK_VERSION {
+            return Err(GenerateError::InvalidLock(format!(
+                "{path}: unsupported version {}",
+                lock.version
+            )));
+        }
+        Ok(lock)
+    }
+
+    /// Write the lockfile into a project.
+    pub fn save(&self, project: &Utf8Path) -> Result<(), GenerateError> {
+        let path = project.join(LOCK_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_toml()?)?;
+        Ok(())
+    }
+
+    /// Stage the lockfile for a project without writing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidLock` if the lockfile cannot be serialized.
+    pub fn stage(&self, project: &Utf8Path, vfs: &mut VirtualFs) -> Result<(), GenerateError> {
+        vfs.write(project.join(LOCK_FILE), self.to_toml()?);
+        Ok(())
+    }
+
+    fn to_toml(&self) -> Result<String, GenerateError> {
+        toml::to_string_pretty(self).map_err(|e| GenerateError::InvalidLock(e.to_string()))
+    }
+
+    /// Pin a recipe to the choices locked for a page.
+    ///
+    /// Sections whose type is listed in `update` keep the freshly matched
+    /// variant. Returns the locked catalog component for each pinned
+    /// section, keyed by component name.
+    pub fn apply(
+        &self,
+        page: &str,
+        recipe: &mut Recipe,
+        update: &[String],
+    ) -> HashMap<String, String> {
+        let mut components = HashMap::new();
+        let Some(locked) = self.pages.get(page) else {
+            return components;
+        };
+
+        let names = super::generator::section_component_names(&recipe.sections);
+        for (section, name) in recipe.sections.iter_mut().zip(names) {
+            if update.contains(&section.section_type) {
+                continue;
+            }
+            let Some(entry) = locked
+                .sections
+                .iter()
+                .find(|s| s.name == name && s.section_type == section.section_type)
+            else {
+                continue;
+            };
+
+            section.variant_id.clone_from(&entry.variant);
+            if let Some(ref component) = entry.component {
+                components.insert(name, component.clone());
+            }
+        }
+        components
+    }
+
+    /// Theme mode a page was last generated in.
+    ///
+    /// Regeneration reuses it unless another mode is asked for, so a dark
+    /// page does not silently turn light.
+    #[must_use]
+    pub fn mode(&self, page: &str) -> Option<Mode> {
+        self.pages
+            .get(page)?
+            .sections
+            .first()
+            .and_then(|section| Mode::parse(&section.mode))
+    }
+
+    /// Files of a generated page that were edited since they were locked.
+    ///
+    /// Only files that would be overwritten and still exist on disk are
+    /// checked; files the lock knows nothing about are never reported.
+    #[must_use]
+    pub fn modified_files(
+        &self,
+        page: &str,
+        project: &Utf8Path,
+        generated: &GeneratedPage,
+    ) -> Vec<Utf8PathBuf> {
+        let Some(locked) = self.pages.get(page) else {
+            return vec![];
+        };
+
+        let mut hashes: HashMap<&Utf8Path, &str> = HashMap::new();
+        hashes.insert(&locked.path, &locked.hash);
+        for section in &locked.sections {
+            if let (Some(path), Some(hash)) = (&section.path, &section.hash) {
+                hashes.insert(path, hash);
+            }
+        }
+
+        std::iter::once(&generated.path)
+            .chain(generated.sections.iter().map(|s| &s.path))
+            .filter(|path| {
+                let relative = path.strip_prefix(project).unwrap_or(path);
+                hashes.get(relative).is_some_and(|hash| {
+                    fs::read_to_string(path).is_ok_and(|disk| content_hash(&disk) != *hash)
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Existing files among `paths` that the lock does not vouch for.
+    ///
+    /// A file is safe to overwrite only if it was recorded with
+    /// [`record_file`](Self::record_file) and is unchanged since; anything
+    /// else on disk, such as a hand-written `App.tsx`, is reported.
+    #[must_use]
+    pub fn unlocked_files<'a>(
+        &self,
+        project: &Utf8Path,
+        paths: impl IntoIterator<Item = &'a Utf8PathBuf>,
+    ) -> Vec<Utf8PathBuf> {
+        paths
+            .into_iter()
+            .filter(|path| {
+                let Ok(disk) = fs::read_to_string(path) else {
+                    return false;
+                };
+                let relative = path.strip_prefix(project).unwrap_or(path);
+                self.files
+                    .get(relative)
+                    .is_none_or(|hash| content_hash(&disk) != *hash)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Record a freshly written shared site file.
+    pub fn record_file(&mut self, project: &Utf8Path, path: &Utf8Path, content: &str) {
+        let relative = path.strip_prefix(project).unwrap_or(path).to_owned();
+        self.files.insert(relative, content_hash(content));
+    }
+
+    /// Record a freshly written page.
+    ///
+    /// With `only` set, just the sections of that type are replaced and
+    /// the rest of the locked entry, page hash included, is kept. Pages
+    /// without an entry yet are left unlocked in that case.
+    pub fn record(&mut self, page: &str, entry: LockedPage, only: Option<&str>) {
+        let Some(section_type) = only else {
+            self.pages.insert(page.to_string(), entry);
+            return;
+        };
+
+        // A lone section is only recorded onto a page that is already locked
+        let Some(existing) = self.pages.get_mut(page) else {
+            return;
+        };
+
+        for fresh in entry
+            .sections
+            .into_iter()
+            .filter(|s| s.section_type == section_type)
+        {
+            match existing.sections.iter_mut().find(|s| s.name == fresh.name) {
+                Some(slot) => *slot = fresh,
+                None => existing.sections.push(fresh),
+            }
+        }
+    }
+}
+
+impl LockedPage {
+    /// Build a lock entry from a generated page.
+    #[must_use]
+    pub fn from_generated(
+        project: &Utf8Path,
+        page: &GeneratedPage,
+        pattern: Option<String>,
+        preset: Option<String>,
+        mode: Mode,
+    ) -> Self {
+        let relative = |path: &Utf8Path| path.strip_prefix(project).unwrap_or(path).to_owned();
+
+        let sections = page
+            .choices
+            .iter()
+            .map(|choice| {
+                let file = page.sections.iter().find(|s| s.name == choice.name);
+                LockedSection {
+                    name: choice.name.clone(),
+                    section_type: choice.section_type.clone(),
+                    variant: choice.variant_id.clone(),
+                    component: choice.component_id.clone(),
+                    uuid: choice.uuid.clone(),
+                    mode: mode.as_str().to_string(),
+                    path: file.map(|f| relative(&f.path)),
+                    hash: file.map(|f| content_hash(&f.content)),
+                }
+            })
+            .collect();
+
+        Self {
+            pattern,
+            preset,
+            path: relative(&page.path),
+            hash: content_hash(&page.content),
+            sections,
+        }
+    }
+}
+
+/// Stable content hash for detecting local edits (64-bit FNV-1a).
+#[must_use]
+pub fn content_hash(content: &str) -> String {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let hash = content.bytes().fold(OFFSET, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    });
+    format!("fnv1a64:{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intelligence::{PageCoherence, RecipeSection};
+    use crate::scaffold::{FrameworkTarget, GenerateOptions, PageGenerator, ProjectConfig};
+    use tempfile::TempDir;
+
+    fn recipe(variants: &[(&str, &str)]) -> Recipe {
+        Recipe {
+            pattern_id: "test".to_string(),
+            sections: variants
+                .iter()
+                .zip(0u8..)
+                .map(|((section_type, variant), position)| RecipeSection {
+                    section_type: (*section_type).to_string(),
+                    variant_id: (*variant).to_string(),
+                    position,
+                    slots: HashMap::new(),
+                })
+                .collect(),
+            coherence: PageCoherence {
+                score: 1.0,
+                issues: vec![],
+                valid: true,
+                pairwise_scores: vec![],
+            },
+            dependencies: vec![],
+        }
+    }
+
+    fn generate(dir: &TempDir, recipe: &Recipe) -> (ProjectConfig, GeneratedPage) {
+        let base_path = Utf8Path::from_path(dir.path()).unwrap();
+        let config =
+            ProjectConfig::new("site", base_path).with_framework(FrameworkTarget::ViteReact);
+        let options = GenerateOptions::default().with_split_sections();
+        let page = PageGenerator::new()
+            .generate_from_recipe(recipe, &config, &options)
+            .unwrap();
+        (config, page)
+    }
+
+    #[test]
+    fn content_hash_is_stable() {
+        assert_eq!(content_hash(""), "fnv1a64:cbf29ce484222325");
+        assert_ne!(content_hash("a"), content_hash("b"));
+    }
+
+    #[test]
+    fn lock_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let (config, page) = generate(&dir, &recipe(&[("hero", "hero-simple")]));
+
+        let mut lock = GenerateLock::default();
+        let entry = LockedPage::from_generated(
+            &config.path,
+            &page,
+            Some("saas-landing".to_string()),
+            None,
+            Mode::Dark,
+        );
+        lock.record("index", entry, None);
+        lock.save(&config.path).unwrap();
+
+        let loaded = GenerateLock::load(&config.path).unwrap();
+        let locked = &loaded.pages["index"];
+        assert_eq!(locked.path, "src/App.tsx");
+        assert_eq!(locked.sections[0].variant, "hero-simple");
+        assert_eq!(locked.sections[0].mode, "dark");
+        assert_eq!(
+            locked.sections[0].path.as_deref(),
+            Some(Utf8Path::new("src/components/sections/Hero.tsx"))
+        );
+    }
+
+    #[test]
+    fn apply_reuses_locked_variants() {
+        let dir = TempDir::new().unwrap();
+        let (config, page) = generate(
+            &dir,
+            &recipe(&[("hero", "hero-simple"), ("cta", "cta-simple")]),
+        );
+        let mut lock = GenerateLock::default();
+        lock.record(
+            "index",
+            LockedPage::from_generated(&config.path, &page, None, None, Mode::Light),
+            None,
+        );
+
+        let mut fresh = recipe(&[("hero", "hero-split"), ("cta", "cta-banner")]);
+        lock.apply("index", &mut fresh, &["cta".to_string()]);
+
+        assert_eq!(fresh.sections[0].variant_id, "hero-simple");
+        assert_eq!(fresh.sections[1].variant_id, "cta-banner");
+    }
+
+    #[test]
+    fn mode_reads_the_locked_mode() {
+        let dir = TempDir::new().unwrap();
+        let (config, page) = generate(&dir, &recipe(&[("hero", "hero-simple")]));
+        let mut lock = GenerateLock::default();
+        lock.record(
+            "index",
+            LockedPage::from_generated(&config.path, &page, None, None, Mode::Dark),
+            None,
+        );
+
+        assert_eq!(lock.mode("index"), Some(Mode::Dark));
+        assert_eq!(lock.mode("about"), None);
+    }
+
+    #[test]
+    fn modified_files_detects_local_edits() {
+        let dir = TempDir::new().unwrap();
+        let (config, page) = generate(&dir, &recipe(&[("hero", "hero-simple")]));
+        PageGenerator::new().write_page(&page).unwrap();
+
+        let mut lock = GenerateLock::default();
+        lock.record(
+            "index",
+            LockedPage::from_generated(&config.path, &page, None, None, Mode::Light),
+            None,
+        );
+        assert!(lock.modified_files("index", &config.path, &page).is_empty());
+
+        let hero = &page.sections[0].path;
+        fs::write(hero, "// edited by hand").unwrap();
+        assert_eq!(
+            lock.modified_files("index", &config.path, &page),
+            vec![hero.clone()]
+        );
+    }
+
+    #[test]
+    fn unlocked_files_only_trusts_recorded_content() {
+        let dir = TempDir::new().unwrap();
+        let project = Utf8Path::from_path(dir.path()).unwrap();
+        let app = project.join("src/App.tsx");
+        let missing = project.join("src/Missing.tsx");
+        fs::create_dir_all(app.parent().unwrap()).unwrap();
+        fs::write(&app, "// hand written").unwrap();
+
+        let mut lock = GenerateLock::default();
+        assert_eq!(
+            lock.unlocked_files(project, [&app, &missing]),
+            vec![app.clone()]
+        );
+
+        fs::write(&app, "// generated").unwrap();
+        lock.record_file(project, &app, "// generated");
+        assert!(lock.unlocked_files(project, [&app]).is_empty());
+        assert!(lock.files.contains_key(Utf8Path::new("src/App.tsx")));
+
+        fs::write(&app, "// edited").unwrap();
+        assert_eq!(lock.unlocked_files(project, [&app]), vec![app.clone()]);
+    }
+
+    #[test]
+    fn record_single_section_keeps_the_rest() {
+        let dir = TempDir::new().unwrap();
+        let (config, page) = generate(
+            &dir,
+            &recipe(&[("hero", "hero-simple"), ("cta", "cta-simple")]),
+        );
+        let mut lock = GenerateLock::default();
+        lock.record(
+            "index",
+            LockedPage::from_generated(&config.path, &page, None, None, Mode::Light),
+            None,
+        );
+
+        let (_, updated) = generate(
+            &dir,
+            &recipe(&[("hero", "hero-split"), ("cta", "cta-banner")]),
+        );
+        lock.record(
+            "index",
+            LockedPage::from_generated(&config.path, &updated, None, None, Mode::Light),
+            Some("cta"),
+        );
+
+        let locked = &lock.pages["index"];
+        assert_eq!(locked.sections[0].variant, "hero-simple");
+        assert_eq!(locked.sections[1].variant, "cta-banner");
+        assert_eq!(locked.hash, content_hash(&page.content));
+    }
+}
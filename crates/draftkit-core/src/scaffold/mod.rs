@@ -2,16 +2,51 @@
 //!
 //! This module provides:
 //! - Package manager detection and command generation
-//! - Framework templates (Vite+React, HTML, Next.js)
+//! - Framework templates (Vite+React, Vite+Vue, HTML, Next.js, Nuxt,
+//!   React Router, Astro)
 //! - Project initialization
 //! - Page generation from patterns
+//! - Multi-page sites from site plans
+//! - A lockfile pinning generated component choices
+//! - Adoption of existing projects
+//! - Dry runs staged in a virtual filesystem
+//! - User template packs layered on the built-in scaffolds
+//! - Monorepo workspace detection and registration
+//! - Version-pinned dependencies for generated pages
+//! - Icon library substitution in fetched component code
 
+mod adopt;
+mod dependencies;
 mod framework;
 mod generator;
+mod icons;
+mod lock;
+mod package_json;
 mod package_manager;
+mod packs;
+mod site;
 mod templates;
+mod vfs;
+mod workspace;
 
+pub use adopt::{
+    ImportAlias, MissingPackage, ProjectInspection, route_for_page, tailwind_dependency_version,
+};
+pub use dependencies::{
+    ProjectVersions, UNPINNED_VERSION, compatible_version, package_name, stage_dependencies,
+};
 pub use framework::{FrameworkTarget, ProjectConfig};
-pub use generator::{GenerateError, GenerateOptions, GeneratedPage, PageGenerator, SlotValue};
+pub use generator::{
+    GenerateError, GenerateOptions, GeneratedPage, GeneratedSection, GeneratedSite, PageGenerator,
+    SectionChoice, SlotValue, TailwindConflict,
+};
+pub use icons::{IconLibrary, IconOptions, IconSubstitution, substitute_icons};
+pub use lock::{GenerateLock, LOCK_FILE, LockedPage, LockedSection, content_hash};
 pub use package_manager::PackageManager;
-pub use templates::{TemplateEngine, TemplateError};
+pub use packs::{
+    PACK_MANIFEST, PackFile, PackSource, PackVariable, TemplatePack, list_template_packs, pack_dirs,
+};
+pub use site::{SitePage, SitePageRecipe, SitePlan, SitePlanFile};
+pub use templates::{TemplateEngine, TemplateError, TemplateValue};
+pub use vfs::{ChangeKind, FileChange, VirtualFs, WritePlan, unified_diff};
+pub use workspace::{PNPM_WORKSPACE_FILE, Workspace, WorkspaceConfig};
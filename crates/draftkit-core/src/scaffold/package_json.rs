@@ -0,0 +1,66 @@
+//! Order-preserving edits to `package.json`.
+//!
+//! `serde_json::Value` sorts object keys, so a round trip through it would
+//! reorder every field of a user's `package.json`. Edits go through
+//! [`PackageJson`] instead, which keeps keys where the file had them and
+//! appends new ones at the end.
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+/// A JSON value whose objects keep their key order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PackageJson {
+    Object(IndexMap<String, Self>),
+    Array(Vec<Self>),
+    Scalar(serde_json::Value),
+}
+
+impl PackageJson {
+    /// Parse `content`, failing with `InvalidData` on malformed JSON.
+    pub fn parse(content: &str) -> std::io::Result<Self> {
+        serde_json::from_str(content).map_err(invalid_data)
+    }
+
+    /// Pretty-printed JSON with a trailing newline.
+    pub fn to_pretty_string(&self) -> std::io::Result<String> {
+        let mut content = serde_json::to_string_pretty(self).map_err(invalid_data)?;
+        content.push('\n');
+        Ok(content)
+    }
+
+    pub const fn as_object_mut(&mut self) -> Option<&mut IndexMap<String, Self>> {
+        match self {
+            Self::Object(object) => Some(object),
+            _ => None,
+        }
+    }
+
+    pub const fn as_array_mut(&mut self) -> Option<&mut Vec<Self>> {
+        match self {
+            Self::Array(array) => Some(array),
+            _ => None,
+        }
+    }
+
+    pub fn string(value: impl Into<String>) -> Self {
+        Self::Scalar(serde_json::Value::String(value.into()))
+    }
+}
+
+fn invalid_data(e: serde_json::Error) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_keeps_key_order() {
+        let content = "{\n  \"name\": \"site\",\n  \"version\": \"1.0.0\",\n  \"private\": true,\n  \"scripts\": {\n    \"dev\": \"vite\",\n    \"build\": \"vite build\"\n  },\n  \"files\": [\n    \"dist\"\n  ],\n  \"license\": null,\n  \"port\": 5173\n}\n";
+        let json = PackageJson::parse(content).unwrap();
+        assert_eq!(json.to_pretty_string().unwrap(), content);
+    }
+}
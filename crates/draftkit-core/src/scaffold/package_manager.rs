@@ -1,16 +1,20 @@
 //! Package manager detection and command generation.
 //!
 //! Detection priority:
-//! 1. Existing lockfile in directory
-//! 2. User config preference
-//! 3. Installed tooling (prefer bun > pnpm > yarn > npm by speed)
-//! 4. Fallback: npm
+//! 1. `packageManager` pin (corepack) in the directory's package.json
+//! 2. Existing lockfile in directory
+//! 3. The enclosing monorepo workspace's manager
+//! 4. User config preference
+//! 5. Installed tooling (prefer bun > pnpm > yarn > npm by speed)
+//! 6. Fallback: npm
 
 use std::process::Command;
 
 use camino::Utf8Path;
 use serde::{Deserialize, Serialize};
 
+use super::Workspace;
+
 /// JavaScript package manager.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -26,29 +30,66 @@ impl PackageManager {
     /// Detect the appropriate package manager for a directory.
     ///
     /// Priority order:
-    /// 1. Existing lockfile in directory (bun.lockb → pnpm-lock.yaml → yarn.lock → package-lock.json)
-    /// 2. User config preference (if provided)
-    /// 3. Installed tooling (prefer bun > pnpm > yarn > npm by speed)
-    /// 4. Fallback: npm
+    /// 1. `packageManager` pin in the directory's package.json
+    /// 2. Existing lockfile in directory (bun.lockb → pnpm-lock.yaml → yarn.lock → package-lock.json)
+    /// 3. The enclosing workspace's pin or lockfile
+    /// 4. User config preference (if provided)
+    /// 5. Installed tooling (prefer bun > pnpm > yarn > npm by speed)
+    /// 6. Fallback: npm
     #[must_use]
     pub fn detect(dir: &Utf8Path, config_preference: Option<Self>) -> Self {
-        // 1. Check for existing lockfiles (highest priority to preserve consistency)
-        if let Some(pm) = Self::detect_from_lockfile(dir) {
+        // 1-2. The project's own pin or lockfile (highest priority to preserve consistency)
+        let package_json = std::fs::read_to_string(dir.join("package.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok());
+        if let Some(pm) = package_json
+            .as_ref()
+            .and_then(Self::from_package_json)
+            .or_else(|| Self::detect_from_lockfile(dir))
+        {
+            return pm;
+        }
+
+        // 3. Monorepo packages follow the workspace root
+        if let Some(pm) = Workspace::find(dir).and_then(|workspace| workspace.package_manager) {
             return pm;
         }
 
-        // 2. User config preference
+        // 4. User config preference
         if let Some(preferred) = config_preference {
             return preferred;
         }
 
-        // 3. Installed tooling (prefer faster options)
+        // 5. Installed tooling (prefer faster options)
         Self::detect_installed()
     }
 
+    /// Package manager pinned in a package.json, via corepack's
+    /// `packageManager` field or `devEngines.packageManager`.
+    #[must_use]
+    pub fn from_package_json(package_json: &serde_json::Value) -> Option<Self> {
+        package_json
+            .get("packageManager")
+            .and_then(|pin| pin.as_str())
+            .and_then(Self::parse_pin)
+            .or_else(|| {
+                let name = package_json
+                    .get("devEngines")?
+                    .get("packageManager")?
+                    .get("name")?;
+                Self::parse(name.as_str()?)
+            })
+    }
+
+    /// Parse a corepack pin such as `pnpm@9.15.0+sha512.abc`.
+    #[must_use]
+    pub fn parse_pin(pin: &str) -> Option<Self> {
+        Self::parse(pin.split('@').next()?.trim())
+    }
+
     /// Detect package manager from lockfile presence.
     #[must_use]
-    fn detect_from_lockfile(dir: &Utf8Path) -> Option<Self> {
+    pub(crate) fn detect_from_lockfile(dir: &Utf8Path) -> Option<Self> {
         let dir = dir.as_std_path();
 
         // Order by preference (bun is fastest, npm is most compatible)
@@ -163,6 +204,31 @@ impl PackageManager {
         cmd
     }
 
+    /// Get the add dependency command for one workspace package.
+    ///
+    /// Runs from the workspace root and targets the package by name, or by
+    /// its path for bun, which has no workspace filter for `add`.
+    #[must_use]
+    pub fn workspace_add_cmd(
+        &self,
+        packages: &[&str],
+        dev: bool,
+        member_name: &str,
+        member_path: &str,
+    ) -> Vec<String> {
+        let mut cmd = self.add_cmd(packages, dev);
+        let target = match self {
+            Self::Npm => vec!["--workspace", member_name],
+            Self::Pnpm => vec!["--filter", member_name],
+            Self::Yarn => vec!["workspace", member_name],
+            Self::Bun => vec!["--cwd", member_path],
+        };
+        // Yarn takes `workspace <name>` before the subcommand
+        let at = if *self == Self::Yarn { 1 } else { 2 };
+        cmd.splice(at..at, target.into_iter().map(ToString::to_string));
+        cmd
+    }
+
     /// Get the lockfile name for this package manager.
     #[must_use]
     pub const fn lockfile(&self) -> &'static str {
@@ -322,6 +388,77 @@ mod tests {
         assert_eq!(pnpm_add_dev, vec!["pnpm", "add", "-D", "typescript"]);
     }
 
+    #[test]
+    fn workspace_add_commands() {
+        let add = |pm: PackageManager| pm.workspace_add_cmd(&["react"], true, "site", "apps/site");
+        assert_eq!(
+            add(PackageManager::Npm),
+            [
+                "npm",
+                "install",
+                "--workspace",
+                "site",
+                "--save-dev",
+                "react"
+            ]
+        );
+        assert_eq!(
+            add(PackageManager::Pnpm),
+            ["pnpm", "add", "--filter", "site", "-D", "react"]
+        );
+        assert_eq!(
+            add(PackageManager::Yarn),
+            ["yarn", "workspace", "site", "add", "-D", "react"]
+        );
+        assert_eq!(
+            add(PackageManager::Bun),
+            ["bun", "add", "--cwd", "apps/site", "-d", "react"]
+        );
+    }
+
+    #[test]
+    fn pins_take_precedence_over_lockfiles() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("package-lock.json"), "{}").unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"packageManager": "pnpm@9.15.0+sha512.abc"}"#,
+        )
+        .unwrap();
+
+        let path = Utf8Path::from_path(dir.path()).unwrap();
+        assert_eq!(PackageManager::detect(path, None), PackageManager::Pnpm);
+
+        let dev_engines = serde_json::json!({"devEngines": {"packageManager": {"name": "bun"}}});
+        assert_eq!(
+            PackageManager::from_package_json(&dev_engines),
+            Some(PackageManager::Bun)
+        );
+        assert_eq!(
+            PackageManager::parse_pin("yarn@4.5.0"),
+            Some(PackageManager::Yarn)
+        );
+        assert_eq!(PackageManager::parse_pin("deno@2"), None);
+    }
+
+    #[test]
+    fn workspace_members_use_the_root_lockfile() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"workspaces": ["apps/*"]}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("bun.lock"), "").unwrap();
+        fs::create_dir_all(dir.path().join("apps/web")).unwrap();
+
+        let path = Utf8Path::from_path(dir.path()).unwrap();
+        assert_eq!(
+            PackageManager::detect(&path.join("apps/web"), Some(PackageManager::Npm)),
+            PackageManager::Bun
+        );
+    }
+
     #[test]
     fn serde_roundtrip() {
         let json = serde_json::to_string(&PackageManager::Pnpm).unwrap();
@@ -0,0 +1,470 @@
+//! User-defined template packs layered on top of the built-in scaffolds.
+//!
+//! A pack is a directory with a `template.toml` manifest, found in (highest
+//! precedence first):
+//!
+//! 1. Project packs (`./.draftkit/templates/<name>/`)
+//! 2. User packs (`~/.config/draftkit/templates/<name>/`)
+//!
+//! ```text
+//! acme/
+//! ├── template.toml
+//! ├── files/                  # copied into the project
+//! │   ├── eslint.config.js
+//! │   ├── src/tokens.css.tmpl  # rendered, written as src/tokens.css
+//! │   └── .github/workflows/ci.yml
+//! └── partials/
+//!     └── tailwind-css.tmpl   # overrides the built-in partial
+//! ```
+//!
+//! Files ending in `.tmpl` are rendered with the template engine and lose the
+//! extension; everything else is copied byte for byte, so CI files using
+//! `${{ }}` need no escaping and images or fonts survive intact. Pack files
+//! replace built-in files at the same path.
+//!
+//! ```toml
+//! [template]
+//! description = "Acme house style"
+//! framework = "vite-react"
+//!
+//! [[variables]]
+//! name = "brand_color"
+//! prompt = "Brand color"
+//! default = "#4f46e5"
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::Deserialize;
+
+use super::{FrameworkTarget, TemplateError};
+
+/// Manifest file name inside a template pack.
+pub const PACK_MANIFEST: &str = "template.toml";
+
+/// Where a template pack was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackSource {
+    /// User's config directory
+    User,
+    /// Project-local .draftkit directory
+    Project,
+}
+
+/// A variable a pack declares, filled in on `init`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PackVariable {
+    /// Name used in templates
+    pub name: String,
+    /// Question asked when prompting (defaults to the name)
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// Value used when not prompting
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+impl PackVariable {
+    /// Text to show when asking for the value.
+    #[must_use]
+    pub fn prompt(&self) -> &str {
+        self.prompt.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// `template.toml` contents.
+#[derive(Debug, Deserialize)]
+struct PackManifest {
+    #[serde(default)]
+    template: PackInfo,
+    #[serde(default)]
+    variables: Vec<PackVariable>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PackInfo {
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    framework: Option<String>,
+}
+
+/// A file a pack adds to the project.
+#[derive(Debug, Clone)]
+pub struct PackFile {
+    /// Relative path within the project
+    pub path: Utf8PathBuf,
+    /// File content; UTF-8 text when `render` is set
+    pub content: Vec<u8>,
+    /// Whether the content is a template to render
+    pub render: bool,
+}
+
+/// A template pack loaded from disk.
+#[derive(Debug, Clone)]
+pub struct TemplatePack {
+    /// Pack name (its directory name)
+    pub name: String,
+    /// Human-readable description
+    pub description: String,
+    /// Built-in target the pack layers on, if it requires one
+    pub framework: Option<FrameworkTarget>,
+    /// Variables the pack's templates use
+    pub variables: Vec<PackVariable>,
+    /// Files added to the project
+    pub files: Vec<PackFile>,
+    /// Partials available to every template
+    pub partials: HashMap<String, String>,
+    /// Where the pack was found
+    pub source: PackSource,
+    /// Pack directory
+    pub path: Utf8PathBuf,
+}
+
+impl TemplatePack {
+    /// Load a pack from its directory.
+    pub fn load(dir: &Utf8Path, source: PackSource) -> Result<Self, TemplateError> {
+        let manifest_path = dir.join(PACK_MANIFEST);
+        let manifest: PackManifest =
+            toml::from_str(&fs::read_to_string(&manifest_path)?).map_err(|e| {
+                TemplateError::InvalidManifest {
+                    path: manifest_path.clone(),
+                    message: e.message().to_string(),
+                }
+            })?;
+
+        let framework = manifest
+            .template
+            .framework
+            .map(|name| FrameworkTarget::parse(&name).ok_or(TemplateError::UnknownFramework(name)))
+            .transpose()?;
+
+        let mut files = Vec::new();
+        let files_dir = dir.join("files");
+        if files_dir.is_dir() {
+            for path in files_under(&files_dir)? {
+                let relative = path.strip_prefix(&files_dir).unwrap_or(&path);
+                let (target, render) = relative.as_str().strip_suffix(".tmpl").map_or_else(
+                    || (relative.to_path_buf(), false),
+                    |stripped| (Utf8PathBuf::from(stripped), true),
+                );
+                // Only templates are read as text
+                let content = if render {
+                    fs::read_to_string(&path)?.into_bytes()
+                } else {
+                    fs::read(&path)?
+                };
+                let path = target;
+                files.push(PackFile {
+                    path,
+                    content,
+                    render,
+                });
+            }
+        }
+
+        let mut partials = HashMap::new();
+        let partials_dir = dir.join("partials");
+        if partials_dir.is_dir() {
+            for path in files_under(&partials_dir)? {
+                if path.extension() == Some("tmpl") {
+                    let relative = path.strip_prefix(&partials_dir).unwrap_or(&path);
+                    let name = relative.with_extension("");
+                    partials.insert(name.to_string(), fs::read_to_string(&path)?);
+                }
+            }
+        }
+
+        Ok(Self {
+            name: dir.file_name().unwrap_or_default().to_string(),
+            description: manifest.template.description,
+            framework,
+            variables: manifest.variables,
+            files,
+            partials,
+            source,
+            path: dir.to_path_buf(),
+        })
+    }
+
+    /// Find a pack by name, preferring project packs over user packs.
+    pub fn find(name: &str, project_root: &Utf8Path) -> Result<Self, TemplateError> {
+        // Pack names are directory names, never paths
+        if !name.is_empty() && !name.contains(['/', '\\']) && name != ".." {
+            for (dir, source) in pack_dirs(project_root) {
+                let pack_dir = dir.join(name);
+                if pack_dir.join(PACK_MANIFEST).is_file() {
+                    return Self::load(&pack_dir, source);
+                }
+            }
+        }
+
+        let available = list_template_packs(project_root);
+        Err(TemplateError::NotFound(if available.is_empty() {
+            format!("template pack '{name}' (no packs installed)")
+        } else {
+            format!(
+                "template pack '{name}' (available: {})",
+                available.into_keys().collect::<Vec<_>>().join(", ")
+            )
+        }))
+    }
+
+    /// Values for the pack's variables.
+    ///
+    /// Explicit values win; otherwise `ask` is consulted, and the declared
+    /// default is used when it returns `None`. An error from `ask`, such as
+    /// an interrupted prompt, aborts resolution.
+    pub fn resolve_variables(
+        &self,
+        provided: &HashMap<String, String>,
+        mut ask: impl FnMut(&PackVariable) -> std::io::Result<Option<String>>,
+    ) -> Result<BTreeMap<String, String>, TemplateError> {
+        if let Some(unknown) = provided
+            .keys()
+            .find(|key| !self.variables.iter().any(|v| &v.name == *key))
+        {
+            return Err(TemplateError::InvalidVariable(format!(
+                "{unknown} is not declared by template pack '{}'",
+                self.name
+            )));
+        }
+
+        self.variables
+            .iter()
+            .map(|variable| {
+                let asked = match provided.get(&variable.name) {
+                    Some(value) => Some(value.clone()),
+                    None => ask(variable)?,
+                };
+                let value = asked
+                    .or_else(|| variable.default.clone())
+                    .ok_or_else(|| TemplateError::UndefinedVariable(variable.name.clone()))?;
+                Ok((variable.name.clone(), value))
+            })
+            .collect()
+    }
+}
+
+/// Directories searched for packs, highest precedence first.
+#[must_use]
+pub fn pack_dirs(project_root: &Utf8Path) -> Vec<(Utf8PathBuf, PackSource)> {
+    let mut dirs = vec![(
+        project_root.join(".draftkit").join("templates"),
+        PackSource::Project,
+    )];
+    if let Some(config_dir) = dirs::config_dir().and_then(|dir| Utf8PathBuf::try_from(dir).ok()) {
+        dirs.push((
+            config_dir.join("draftkit").join("templates"),
+            PackSource::User,
+        ));
+    }
+    dirs
+}
+
+/// Names of all installed packs and where each would be loaded from.
+#[must_use]
+pub fn list_template_packs(project_root: &Utf8Path) -> BTreeMap<String, PackSource> {
+    let mut packs = BTreeMap::new();
+    for (dir, source) in pack_dirs(project_root) {
+        let Ok(entries) = dir.read_dir_utf8() else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if entry.path().join(PACK_MANIFEST).is_file() {
+                packs.entry(entry.file_name().to_string()).or_insert(source);
+            }
+        }
+    }
+    packs
+}
+
+/// All files below `dir`, in a stable order.
+fn files_under(dir: &Utf8Path) -> Result<Vec<Utf8PathBuf>, TemplateError> {
+    let mut files = Vec::new();
+    for entry in dir.read_dir_utf8()? {
+        let path = entry?.into_path();
+        if path.is_dir() {
+            files.extend(files_under(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scaffold::{ProjectConfig, TemplateEngine, VirtualFs};
+    use tempfile::TempDir;
+
+    fn write(path: &Utf8Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn pack_fixture(root: &Utf8Path) -> Utf8PathBuf {
+        let dir = root.join(".draftkit/templates/acme");
+        write(
+            &dir.join(PACK_MANIFEST),
+            r##"
+[template]
+description = "Acme house style"
+framework = "vite-react"
+
+[[variables]]
+name = "brand_color"
+prompt = "Brand color"
+default = "#4f46e5"
+
+[[variables]]
+name = "team"
+"##,
+        );
+        write(
+            &dir.join("files/src/tokens.css.tmpl"),
+            "--brand: {{{brand_color}}};\n",
+        );
+        write(
+            &dir.join("files/.github/workflows/ci.yml"),
+            "run: ${{ matrix.os }}\n",
+        );
+        write(
+            &dir.join("partials/tailwind-css.tmpl"),
+            "@import \"tailwindcss\";\n",
+        );
+        dir
+    }
+
+    #[test]
+    fn loads_manifest_files_and_partials() {
+        let temp = TempDir::new().unwrap();
+        let root = Utf8Path::from_path(temp.path()).unwrap();
+        pack_fixture(root);
+
+        let pack = TemplatePack::find("acme", root).unwrap();
+        assert_eq!(pack.name, "acme");
+        assert_eq!(pack.source, PackSource::Project);
+        assert_eq!(pack.framework, Some(FrameworkTarget::ViteReact));
+        assert_eq!(pack.variables[0].prompt(), "Brand color");
+        assert_eq!(pack.variables[1].prompt(), "team");
+        assert!(pack.partials.contains_key("tailwind-css"));
+
+        let files: Vec<(&str, bool)> = pack
+            .files
+            .iter()
+            .map(|file| (file.path.as_str(), file.render))
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                (".github/workflows/ci.yml", false),
+                ("src/tokens.css", true)
+            ]
+        );
+        assert!(list_template_packs(root).contains_key("acme"));
+    }
+
+    #[test]
+    fn binary_files_are_copied_byte_for_byte() {
+        let temp = TempDir::new().unwrap();
+        let root = Utf8Path::from_path(temp.path()).unwrap();
+        let dir = pack_fixture(root);
+        let logo = [0x89, b'P', b'N', b'G', 0x00, 0xff, 0xfe];
+        fs::create_dir_all(dir.join("files/public")).unwrap();
+        fs::write(dir.join("files/public/logo.png"), logo).unwrap();
+
+        let pack = TemplatePack::find("acme", root).unwrap();
+        let file = pack
+            .files
+            .iter()
+            .find(|file| file.path == "public/logo.png")
+            .unwrap();
+        assert!(!file.render);
+        assert_eq!(file.content, logo);
+
+        let config = ProjectConfig::new("site", root);
+        let mut engine = TemplateEngine::from_config(&config);
+        engine.add_pack(
+            &pack,
+            pack.resolve_variables(
+                &HashMap::from([("team".to_string(), "web".to_string())]),
+                |_| Ok(None),
+            )
+            .unwrap(),
+        );
+        let mut vfs = VirtualFs::new(&config.path);
+        engine.stage(&config, &mut vfs).unwrap();
+        vfs.apply().unwrap();
+        assert_eq!(fs::read(config.path.join("public/logo.png")).unwrap(), logo);
+    }
+
+    #[test]
+    fn missing_pack_lists_available() {
+        let temp = TempDir::new().unwrap();
+        let root = Utf8Path::from_path(temp.path()).unwrap();
+        pack_fixture(root);
+
+        let err = TemplatePack::find("nope", root).unwrap_err();
+        assert!(err.to_string().contains("acme"));
+    }
+
+    #[test]
+    fn invalid_manifest_is_reported() {
+        let temp = TempDir::new().unwrap();
+        let root = Utf8Path::from_path(temp.path()).unwrap();
+        let dir = root.join(".draftkit/templates/broken");
+        write(
+            &dir.join(PACK_MANIFEST),
+            "[template]\nframework = \"cobol\"\n",
+        );
+        assert!(matches!(
+            TemplatePack::find("broken", root),
+            Err(TemplateError::UnknownFramework(_))
+        ));
+
+        write(&dir.join(PACK_MANIFEST), "[[variables]]\nprompt = 1\n");
+        assert!(matches!(
+            TemplatePack::find("broken", root),
+            Err(TemplateError::InvalidManifest { .. })
+        ));
+    }
+
+    #[test]
+    fn resolves_variables_in_order_of_precedence() {
+        let temp = TempDir::new().unwrap();
+        let root = Utf8Path::from_path(temp.path()).unwrap();
+        pack_fixture(root);
+        let pack = TemplatePack::find("acme", root).unwrap();
+
+        let provided = HashMap::from([("team".to_string(), "web".to_string())]);
+        let values = pack.resolve_variables(&provided, |_| Ok(None)).unwrap();
+        assert_eq!(values["brand_color"], "#4f46e5");
+        assert_eq!(values["team"], "web");
+
+        let values = pack
+            .resolve_variables(&provided, |v| Ok(Some(format!("asked {}", v.name))))
+            .unwrap();
+        assert_eq!(values["brand_color"], "asked brand_color");
+        assert_eq!(values["team"], "web");
+
+        assert!(matches!(
+            pack.resolve_variables(&HashMap::new(), |_| Ok(None)),
+            Err(TemplateError::UndefinedVariable(name)) if name == "team"
+        ));
+
+        let interrupted = pack.resolve_variables(&HashMap::new(), |_| {
+            Err(std::io::Error::from(std::io::ErrorKind::Interrupted))
+        });
+        assert!(matches!(interrupted, Err(TemplateError::Io(_))));
+        let unknown = HashMap::from([("colour".to_string(), "red".to_string())]);
+        assert!(matches!(
+            pack.resolve_variables(&unknown, |_| Ok(None)),
+            Err(TemplateError::InvalidVariable(_))
+        ));
+    }
+}
@@ -0,0 +1,350 @@
+//! Multi-page site plans.
+//!
+//! A site plan lists the routes of a site and what each page is built
+//! from, either a pattern or a page type:
+//!
+//! ```toml
+//! [site]
+//! name = "Acme"
+//! preset = "Minimalist"
+//!
+//! [[site.pages]]
+//! route = "/"
+//! pattern = "saas-landing"
+//!
+//! [[site.pages]]
+//! route = "/pricing"
+//! page_type = "pricing"
+//! ```
+//!
+//! Sections listed in `shared` (header and footer by default) are pulled
+//! out of every page and rendered once around the router outlet.
+
+use std::collections::{HashMap, HashSet};
+
+use camino::Utf8Path;
+use serde::{Deserialize, Serialize};
+
+use super::GenerateError;
+use crate::intelligence::{
+    PageCoherence, PageType, PatternMatcher, Recipe, RecipeOptions, RecipeSection,
+};
+use crate::patterns::PatternLoader;
+
+/// Root structure of a site plan TOML file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SitePlanFile {
+    /// The site definition
+    pub site: SitePlan,
+}
+
+/// A multi-page site: its routes and the settings shared across them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SitePlan {
+    /// Site name (defaults to the project name)
+    #[serde(default)]
+    pub name: String,
+
+    /// Preset applied to every page
+    #[serde(default)]
+    pub preset: Option<String>,
+
+    /// Section types rendered once for the whole site
+    #[serde(default = "default_shared")]
+    pub shared: Vec<String>,
+
+    /// Pages in navigation order
+    #[serde(default)]
+    pub pages: Vec<SitePage>,
+}
+
+fn default_shared() -> Vec<String> {
+    vec!["header".to_string(), "footer".to_string()]
+}
+
+/// A single route in a site plan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SitePage {
+    /// Route path (e.g., "/", "/pricing", "/blog/[slug]")
+    pub route: String,
+
+    /// Component name override (defaults to one derived from the route)
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Pattern to build the page from
+    #[serde(default)]
+    pub pattern: Option<String>,
+
+    /// Page type to build the page from when no pattern is given
+    /// (defaults to one inferred from the route)
+    #[serde(default)]
+    pub page_type: Option<String>,
+}
+
+impl SitePage {
+    /// Resolved page type, from `page_type` or the route.
+    #[must_use]
+    pub fn resolved_page_type(&self) -> PageType {
+        self.page_type.as_deref().map_or_else(
+            || match PageType::from_route(&self.route) {
+                PageType::Unknown => PageType::Content,
+                page_type => page_type,
+            },
+            PageType::parse,
+        )
+    }
+
+    /// PascalCase name for the page component (e.g., "/about/team" → "AboutTeam").
+    #[must_use]
+    pub fn component_name(&self) -> String {
+        if let Some(ref name) = self.name {
+            return pascal_case(name);
+        }
+
+        let name = pascal_case(&self.route);
+        if name.is_empty() {
+            "Home".to_string()
+        } else {
+            name
+        }
+    }
+}
+
+/// A site page paired with its generated recipe.
+#[derive(Debug, Clone)]
+pub struct SitePageRecipe {
+    /// The planned page
+    pub page: SitePage,
+    /// Sections selected for the page
+    pub recipe: Recipe,
+}
+
+impl SitePlan {
+    /// Load a site plan from a TOML file.
+    pub fn from_file(path: &Utf8Path) -> Result<Self, GenerateError> {
+        let content = std::fs::read_to_string(path)?;
+        let file: SitePlanFile = toml::from_str(&content)
+            .map_err(|e| GenerateError::InvalidSitePlan(format!("{path}: {e}")))?;
+        file.site.validate()?;
+        Ok(file.site)
+    }
+
+    /// Check that the plan has pages and that routes are well-formed and unique.
+    pub fn validate(&self) -> Result<(), GenerateError> {
+        if self.pages.is_empty() {
+            return Err(GenerateError::InvalidSitePlan(
+                "site plan has no pages".to_string(),
+            ));
+        }
+
+        let mut routes = HashSet::new();
+        let mut names = HashSet::new();
+        for page in &self.pages {
+            if !page.route.starts_with('/') {
+                return Err(GenerateError::InvalidSitePlan(format!(
+                    "route '{}' must start with '/'",
+                    page.route
+                )));
+            }
+            if !routes.insert(page.route.trim_end_matches('/')) {
+                return Err(GenerateError::InvalidSitePlan(format!(
+                    "duplicate route '{}'",
+                    page.route
+                )));
+            }
+            if !names.insert(page.component_name()) {
+                return Err(GenerateError::InvalidSitePlan(format!(
+                    "duplicate page name '{}' (set `name` on route '{}')",
+                    page.component_name(),
+                    page.route
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Build a recipe for every page.
+    ///
+    /// Pattern pages go through the pattern matcher; page-type pages use
+    /// the typical sections for that page type. The same variant
+    /// preferences (from the site's preset) are applied to every page so
+    /// sections stay consistent across routes.
+    pub fn build_recipes(
+        &self,
+        patterns: &PatternLoader,
+        variant_preferences: &HashMap<String, String>,
+    ) -> Result<Vec<SitePageRecipe>, GenerateError> {
+        let matcher = PatternMatcher::new();
+
+        self.pages
+            .iter()
+            .map(|page| {
+                let mut recipe = if let Some(ref pattern_id) = page.pattern {
+                    let loaded = patterns
+                        .get(pattern_id)
+                        .ok_or_else(|| GenerateError::PatternNotFound(pattern_id.clone()))?;
+                    matcher.generate_recipe(&loaded.pattern, &RecipeOptions::default())
+                } else {
+                    page_type_recipe(page.resolved_page_type())
+                };
+
+                for section in &mut recipe.sections {
+                    if let Some(variant) = variant_preferences.get(&section.section_type) {
+                        section.variant_id.clone_from(variant);
+                    }
+                }
+
+                Ok(SitePageRecipe {
+                    page: page.clone(),
+                    recipe,
+                })
+            })
+            .collect()
+    }
+
+    /// Whether a section type is rendered once for the whole site.
+    #[must_use]
+    pub fn is_shared(&self, section_type: &str) -> bool {
+        self.shared.iter().any(|s| s == section_type)
+    }
+}
+
+/// Build a recipe from the typical sections of a page type.
+fn page_type_recipe(page_type: PageType) -> Recipe {
+    let sections = page_type
+        .typical_sections()
+        .iter()
+        .zip(0u8..)
+        .map(|(section_type, position)| RecipeSection {
+            section_type: (*section_type).to_string(),
+            variant_id: (*section_type).to_string(),
+            position,
+            slots: HashMap::new(),
+        })
+        .collect();
+
+    Recipe {
+        pattern_id: format!("page-type:{}", page_type.as_str()),
+        sections,
+        coherence: PageCoherence {
+            score: 1.0,
+            issues: vec![],
+            valid: true,
+            pairwise_scores: vec![],
+        },
+        dependencies: vec![],
+    }
+}
+
+/// Convert a route or name to PascalCase ("/case-studies/[slug]" → "CaseStudiesSlug").
+fn pascal_case(s: &str) -> String {
+    s.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |c| {
+                c.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan(toml: &str) -> SitePlan {
+        toml::from_str::<SitePlanFile>(toml).unwrap().site
+    }
+
+    #[test]
+    fn parse_site_plan() {
+        let site = plan(
+            r#"
+[site]
+name = "Acme"
+preset = "Minimalist"
+
+[[site.pages]]
+route = "/"
+pattern = "saas-landing"
+
+[[site.pages]]
+route = "/pricing"
+page_type = "plans"
+
+[[site.pages]]
+route = "/about/team"
+"#,
+        );
+
+        assert_eq!(site.shared, ["header", "footer"]);
+        assert_eq!(site.pages.len(), 3);
+        assert_eq!(site.pages[0].component_name(), "Home");
+        assert_eq!(site.pages[1].resolved_page_type(), PageType::Pricing);
+        assert_eq!(site.pages[2].component_name(), "AboutTeam");
+        assert_eq!(site.pages[2].resolved_page_type(), PageType::About);
+        assert!(site.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_bad_routes() {
+        let missing_slash = plan("[site]\n[[site.pages]]\nroute = \"pricing\"\n");
+        assert!(missing_slash.validate().is_err());
+
+        let duplicate =
+            plan("[site]\n[[site.pages]]\nroute = \"/a\"\n[[site.pages]]\nroute = \"/a/\"\n");
+        assert!(duplicate.validate().is_err());
+
+        let empty = plan("[site]\n");
+        assert!(empty.validate().is_err());
+    }
+
+    #[test]
+    fn build_recipes_applies_preferences_to_every_page() {
+        let site = plan(
+            r#"
+[site]
+[[site.pages]]
+route = "/"
+pattern = "saas-landing"
+[[site.pages]]
+route = "/contact"
+"#,
+        );
+
+        let prefs = HashMap::from([("header".to_string(), "header-minimal".to_string())]);
+        let recipes = site
+            .build_recipes(&PatternLoader::builtin_only(), &prefs)
+            .unwrap();
+
+        assert_eq!(recipes.len(), 2);
+        for page in &recipes {
+            let header = page
+                .recipe
+                .sections
+                .iter()
+                .find(|s| s.section_type == "header")
+                .unwrap();
+            assert_eq!(header.variant_id, "header-minimal");
+        }
+        assert!(
+            recipes[1]
+                .recipe
+                .sections
+                .iter()
+                .any(|s| s.section_type == "contact")
+        );
+    }
+
+    #[test]
+    fn build_recipes_unknown_pattern() {
+        let site = plan("[site]\n[[site.pages]]\nroute = \"/\"\npattern = \"nope\"\n");
+        assert!(matches!(
+            site.build_recipes(&PatternLoader::builtin_only(), &HashMap::new()),
+            Err(GenerateError::PatternNotFound(_))
+        ));
+    }
+}
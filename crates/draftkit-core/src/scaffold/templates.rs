@@ -1,16 +1,31 @@
 //! Template loading and rendering for project scaffolding.
 //!
 //! Templates are embedded at compile time using `include_str!` and rendered
-//! with simple mustache-style substitution.
+//! with a small Handlebars-style language:
+//!
+//! - `{{var}}` inserts an HTML-escaped value and `{{{var}}}` a raw one
+//! - `{{#if var}}`, `{{#unless var}}` and `{{#each var}}` blocks nest and take
+//!   an optional `{{else}}` branch
+//! - `{{> name}}` includes a partial and `{{! comment}}` renders nothing
+//!
+//! Inside `{{#each}}`, `this`, `@index`, `@first`, `@last` and `@key` refer to
+//! the current item, and the fields of map items can be named directly. A
+//! block, comment or partial tag that sits alone on a line takes the whole
+//! line with it, so templates can put control flow on its own lines.
 
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::io;
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use thiserror::Error;
 
-use super::{FrameworkTarget, ProjectConfig};
+use super::{FrameworkTarget, PackFile, ProjectConfig, TemplatePack, VirtualFs};
+use crate::components::TailwindVersion;
+
+/// How deeply partials may include other partials.
+const MAX_PARTIAL_DEPTH: usize = 16;
 
 /// Template rendering error.
 #[derive(Debug, Error)]
@@ -26,6 +41,77 @@ pub enum TemplateError {
 
     #[error("Invalid template variable: {0}")]
     InvalidVariable(String),
+
+    #[error("Undefined template variable: {0}")]
+    UndefinedVariable(String),
+
+    #[error("Template syntax error on line {line}: {message}")]
+    Syntax { line: usize, message: String },
+
+    #[error("Invalid template manifest {path}: {message}")]
+    InvalidManifest { path: Utf8PathBuf, message: String },
+
+    #[error("Failed to render {path}: {source}")]
+    Render {
+        path: Utf8PathBuf,
+        source: Box<Self>,
+    },
+}
+
+/// A value that templates can reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateValue {
+    /// Text; `""`, `"false"` and `"0"` count as false
+    Str(String),
+    /// A flag
+    Bool(bool),
+    /// Items for `{{#each}}`; false when empty
+    List(Vec<Self>),
+    /// Named fields, iterated in key order by `{{#each}}`; false when empty
+    Map(BTreeMap<String, Self>),
+}
+
+impl TemplateValue {
+    /// Whether the value selects the `{{#if}}` branch.
+    #[must_use]
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Self::Str(s) => !s.is_empty() && s != "false" && s != "0",
+            Self::Bool(b) => *b,
+            Self::List(items) => !items.is_empty(),
+            Self::Map(fields) => !fields.is_empty(),
+        }
+    }
+}
+
+impl From<&str> for TemplateValue {
+    fn from(value: &str) -> Self {
+        Self::Str(value.to_string())
+    }
+}
+
+impl From<String> for TemplateValue {
+    fn from(value: String) -> Self {
+        Self::Str(value)
+    }
+}
+
+impl From<bool> for TemplateValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl<T: Into<Self>> From<Vec<T>> for TemplateValue {
+    fn from(items: Vec<T>) -> Self {
+        Self::List(items.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<BTreeMap<String, Self>> for TemplateValue {
+    fn from(fields: BTreeMap<String, Self>) -> Self {
+        Self::Map(fields)
+    }
 }
 
 /// A template file with its target path and content.
@@ -40,35 +126,78 @@ pub struct TemplateFile {
 /// Template engine for rendering project scaffolds.
 #[derive(Debug, Default)]
 pub struct TemplateEngine {
-    variables: HashMap<String, String>,
+    variables: HashMap<String, TemplateValue>,
+    partials: HashMap<String, String>,
+    pack_files: Vec<PackFile>,
+    strict: bool,
 }
 
 impl TemplateEngine {
     /// Create a new template engine from project config.
+    ///
+    /// Scaffolding renders in strict mode, so a template that references an
+    /// unknown variable fails instead of producing a broken file.
     #[must_use]
     pub fn from_config(config: &ProjectConfig) -> Self {
-        let mut variables = HashMap::new();
-        variables.insert("project_name".to_string(), config.name.clone());
-        variables.insert(
-            "tailwind_version".to_string(),
-            config.tailwind_version.as_str().to_string(),
+        let mut engine = Self::default().with_strict(true);
+        engine.set("project_name", config.name.as_str());
+        engine.set("tailwind_version", config.tailwind_version.as_str());
+        engine.set(
+            "tailwind_v3",
+            config.tailwind_version == TailwindVersion::V3,
         );
-        variables.insert("has_content".to_string(), "false".to_string());
-
-        Self { variables }
+        engine.set("tailwind_content", tailwind_content_globs(config.framework));
+        engine.set(
+            "tailwind_dependencies",
+            tailwind_dependencies(config.tailwind_version),
+        );
+        engine.set("has_content", false);
+        engine
     }
 
     /// Create a new template engine with custom variables.
     #[must_use]
-    pub const fn with_variables(variables: HashMap<String, String>) -> Self {
-        Self { variables }
+    pub fn with_variables(variables: HashMap<String, String>) -> Self {
+        Self {
+            variables: variables
+                .into_iter()
+                .map(|(key, value)| (key, value.into()))
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Error on undefined variables instead of rendering them as empty.
+    #[must_use]
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
     }
 
     /// Set a template variable.
-    pub fn set(&mut self, key: impl Into<String>, value: impl Into<String>) {
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<TemplateValue>) {
         self.variables.insert(key.into(), value.into());
     }
 
+    /// Register a partial, overriding any built-in partial of the same name.
+    pub fn register_partial(&mut self, name: impl Into<String>, content: impl Into<String>) {
+        self.partials.insert(name.into(), content.into());
+    }
+
+    /// Layer a template pack on top of the built-in templates.
+    ///
+    /// The pack's partials take precedence over built-in ones, its variables
+    /// are set from `values`, and its files are staged after the framework's.
+    pub fn add_pack(&mut self, pack: &TemplatePack, values: BTreeMap<String, String>) {
+        for (name, content) in &pack.partials {
+            self.register_partial(name.clone(), content.clone());
+        }
+        for (key, value) in values {
+            self.set(key, value);
+        }
+        self.pack_files.extend(pack.files.iter().cloned());
+    }
+
     /// Get all template files for a framework.
     #[must_use]
     pub fn get_templates(framework: FrameworkTarget) -> Vec<TemplateFile> {
@@ -76,107 +205,224 @@ impl TemplateEngine {
             FrameworkTarget::ViteReact => Self::vite_react_templates(),
             FrameworkTarget::Html => Self::html_templates(),
             FrameworkTarget::NextJs => Self::nextjs_templates(),
+            FrameworkTarget::ViteVue => Self::vite_vue_templates(),
+            FrameworkTarget::Nuxt => Self::nuxt_templates(),
+            FrameworkTarget::ReactRouter => Self::react_router_templates(),
+            FrameworkTarget::Astro => Self::astro_templates(),
         }
     }
 
-    /// Render a template string with variable substitution.
-    #[must_use]
-    pub fn render(&self, template: &str) -> String {
-        let mut result = template.to_string();
-
-        // Handle simple conditionals: {{#if var}}...{{else}}...{{/if}} or {{#if var}}...{{/if}}
-        result = self.process_conditionals(&result);
+    /// Render a template string.
+    pub fn render(&self, template: &str) -> Result<String, TemplateError> {
+        let nodes = parse(template)?;
+        let mut out = String::with_capacity(template.len());
+        self.render_nodes(&nodes, &mut Vec::new(), 0, &mut out)?;
+        Ok(out)
+    }
 
-        // Simple mustache-style substitution: {{variable}}
-        for (key, value) in &self.variables {
-            let placeholder = format!("{{{{{key}}}}}");
-            result = result.replace(&placeholder, value);
+    fn render_nodes<'a>(
+        &'a self,
+        nodes: &[Node],
+        scopes: &mut Vec<Scope<'a>>,
+        depth: usize,
+        out: &mut String,
+    ) -> Result<(), TemplateError> {
+        for node in nodes {
+            match node {
+                Node::Text(text) => out.push_str(text),
+                Node::Var { path, escape } => {
+                    let Some(value) = self.resolve(path, scopes)? else {
+                        continue;
+                    };
+                    let text = match value.as_ref() {
+                        TemplateValue::Str(s) => Cow::Borrowed(s.as_str()),
+                        TemplateValue::Bool(b) => Cow::Owned(b.to_string()),
+                        TemplateValue::List(_) | TemplateValue::Map(_) => {
+                            return Err(TemplateError::InvalidVariable(format!(
+                                "{path} cannot be rendered as text"
+                            )));
+                        }
+                    };
+                    if *escape {
+                        escape_html(&text, out);
+                    } else {
+                        out.push_str(&text);
+                    }
+                }
+                Node::Block {
+                    kind: kind @ (BlockKind::If | BlockKind::Unless),
+                    path,
+                    body,
+                    otherwise,
+                } => {
+                    let truthy = self
+                        .resolve(path, scopes)?
+                        .is_some_and(|value| value.is_truthy());
+                    let branch = if truthy == (*kind == BlockKind::If) {
+                        body
+                    } else {
+                        otherwise
+                    };
+                    self.render_nodes(branch, scopes, depth, out)?;
+                }
+                Node::Block {
+                    kind: BlockKind::Each,
+                    path,
+                    body,
+                    otherwise,
+                } => {
+                    let items: Vec<(Option<&'a str>, &'a TemplateValue)> =
+                        match self.resolve(path, scopes)? {
+                            None => Vec::new(),
+                            Some(Cow::Borrowed(TemplateValue::List(items))) => {
+                                items.iter().map(|item| (None, item)).collect()
+                            }
+                            Some(Cow::Borrowed(TemplateValue::Map(fields))) => fields
+                                .iter()
+                                .map(|(key, item)| (Some(key.as_str()), item))
+                                .collect(),
+                            Some(_) => {
+                                return Err(TemplateError::InvalidVariable(format!(
+                                    "{path} is not a list or map"
+                                )));
+                            }
+                        };
+                    if items.is_empty() {
+                        self.render_nodes(otherwise, scopes, depth, out)?;
+                    }
+                    let len = items.len();
+                    for (index, (key, item)) in items.into_iter().enumerate() {
+                        scopes.push(Scope {
+                            item,
+                            index,
+                            len,
+                            key,
+                        });
+                        let result = self.render_nodes(body, scopes, depth, out);
+                        scopes.pop();
+                        result?;
+                    }
+                }
+                Node::Partial { name, indent, line } => {
+                    if depth >= MAX_PARTIAL_DEPTH {
+                        return Err(syntax(
+                            *line,
+                            format!(
+                                "partials nested more than {MAX_PARTIAL_DEPTH} deep at '{name}'"
+                            ),
+                        ));
+                    }
+                    let source = self
+                        .partial(name)
+                        .ok_or_else(|| TemplateError::NotFound(format!("partial '{name}'")))?;
+                    let mut rendered = String::new();
+                    self.render_nodes(&parse(source)?, scopes, depth + 1, &mut rendered)?;
+                    indent_lines(&rendered, indent, out);
+                }
+            }
         }
-
-        result
+        Ok(())
     }
 
-    /// Process conditional blocks in templates.
-    fn process_conditionals(&self, template: &str) -> String {
-        let mut result = template.to_string();
-
-        // Pattern: {{#if var}}true_content{{else}}false_content{{/if}}
-        // or: {{#if var}}true_content{{/if}}
-        loop {
-            let Some(if_start) = result.find("{{#if ") else {
-                break;
-            };
+    /// Look up a variable, failing in strict mode when it is undefined.
+    fn resolve<'a>(
+        &'a self,
+        path: &str,
+        scopes: &[Scope<'a>],
+    ) -> Result<Option<Cow<'a, TemplateValue>>, TemplateError> {
+        match self.lookup(path, scopes) {
+            None if self.strict => Err(TemplateError::UndefinedVariable(path.to_string())),
+            value => Ok(value),
+        }
+    }
 
-            let Some(if_var_end) = result[if_start..].find("}}") else {
-                break;
+    /// Find a variable in the innermost `{{#each}}` item that has it, falling
+    /// back to the top-level variables.
+    fn lookup<'a>(&'a self, path: &str, scopes: &[Scope<'a>]) -> Option<Cow<'a, TemplateValue>> {
+        if let Some(meta) = path.strip_prefix('@') {
+            let scope = scopes.last()?;
+            return match meta {
+                "index" => Some(Cow::Owned(scope.index.to_string().into())),
+                "first" => Some(Cow::Owned((scope.index == 0).into())),
+                "last" => Some(Cow::Owned((scope.index + 1 == scope.len).into())),
+                "key" => scope.key.map(|key| Cow::Owned(key.into())),
+                _ => None,
             };
-            let if_var_end = if_start + if_var_end;
-
-            let var_name = &result[if_start + 6..if_var_end];
+        }
+        if path == "this" || path == "." {
+            return scopes.last().map(|scope| Cow::Borrowed(scope.item));
+        }
+        if let Some(rest) = path.strip_prefix("this.") {
+            let scope = scopes.last()?;
+            return field(scope.item, rest).map(Cow::Borrowed);
+        }
 
-            let Some(endif_pos) = result[if_var_end..].find("{{/if}}") else {
-                break;
-            };
-            let endif_pos = if_var_end + endif_pos;
+        let (head, rest) = path
+            .split_once('.')
+            .map_or((path, None), |(head, rest)| (head, Some(rest)));
+        let value = scopes
+            .iter()
+            .rev()
+            .find_map(|scope| match scope.item {
+                TemplateValue::Map(fields) => fields.get(head),
+                _ => None,
+            })
+            .or_else(|| self.variables.get(head))?;
+        rest.map_or(Some(value), |rest| field(value, rest))
+            .map(Cow::Borrowed)
+    }
 
-            let block_content = &result[if_var_end + 2..endif_pos];
+    /// A registered partial, or a built-in one.
+    fn partial(&self, name: &str) -> Option<&str> {
+        self.partials
+            .get(name)
+            .map(String::as_str)
+            .or_else(|| builtin_partial(name))
+    }
 
-            // Check for {{else}}
-            let (true_content, false_content) = block_content
-                .find("{{else}}")
-                .map_or((block_content, ""), |else_pos| {
-                    (&block_content[..else_pos], &block_content[else_pos + 8..])
-                });
+    /// Render a project's files into a virtual filesystem without writing.
+    pub fn stage(&self, config: &ProjectConfig, vfs: &mut VirtualFs) -> Result<(), TemplateError> {
+        let mut templates = Self::get_templates(config.framework);
+        if config.tailwind_version == TailwindVersion::V3 {
+            templates.extend(Self::tailwind_v3_templates(config.framework));
+        }
 
-            // Evaluate the condition
-            let var_value = self
-                .variables
-                .get(var_name)
-                .map(String::as_str)
-                .unwrap_or("false");
-            let is_truthy = !var_value.is_empty() && var_value != "false" && var_value != "0";
+        for template in templates {
+            let content = self.render_file(&template.path, template.content)?;
+            vfs.write(config.path.join(&template.path), content);
+        }
 
-            let replacement = if is_truthy {
-                true_content
+        // Pack files replace built-in files at the same path
+        for file in &self.pack_files {
+            let path = config.path.join(&file.path);
+            if file.render {
+                let template = String::from_utf8_lossy(&file.content);
+                vfs.write(path, self.render_file(&file.path, &template)?);
             } else {
-                false_content
-            };
-
-            result = format!(
-                "{}{}{}",
-                &result[..if_start],
-                replacement,
-                &result[endif_pos + 7..]
-            );
+                vfs.write_bytes(path, file.content.clone());
+            }
         }
+        Ok(())
+    }
 
-        result
+    /// Render a template file, naming it in any error.
+    fn render_file(&self, path: &Utf8Path, content: &str) -> Result<String, TemplateError> {
+        self.render(content)
+            .map_err(|source| TemplateError::Render {
+                path: path.to_path_buf(),
+                source: Box::new(source),
+            })
     }
 
     /// Scaffold a project to disk.
     pub fn scaffold(&self, config: &ProjectConfig) -> Result<Vec<Utf8PathBuf>, TemplateError> {
-        let templates = Self::get_templates(config.framework);
-        let mut created_files = Vec::new();
+        let mut vfs = VirtualFs::new(&config.path);
+        self.stage(config, &mut vfs)?;
 
         // Create project directory
         fs::create_dir_all(&config.path)?;
 
-        for template in templates {
-            let target_path = config.path.join(&template.path);
-
-            // Ensure parent directory exists
-            if let Some(parent) = target_path.parent() {
-                fs::create_dir_all(parent)?;
-            }
-
-            // Render and write
-            let content = self.render(template.content);
-            fs::write(&target_path, content)?;
-
-            created_files.push(target_path);
-        }
-
-        Ok(created_files)
+        Ok(vfs.apply()?)
     }
 
     // -------------------------------------------------------------------------
@@ -236,6 +482,172 @@ impl TemplateEngine {
         ]
     }
 
+    fn vite_vue_templates() -> Vec<TemplateFile> {
+        vec![
+            TemplateFile {
+                path: "package.json".into(),
+                content: include_str!("templates/vite-vue/package.json.tmpl"),
+            },
+            TemplateFile {
+                path: "vite.config.ts".into(),
+                content: include_str!("templates/vite-vue/vite.config.ts.tmpl"),
+            },
+            TemplateFile {
+                path: "tsconfig.json".into(),
+                content: include_str!("templates/vite-vue/tsconfig.json.tmpl"),
+            },
+            TemplateFile {
+                path: "index.html".into(),
+                content: include_str!("templates/vite-vue/index.html.tmpl"),
+            },
+            TemplateFile {
+                path: ".gitignore".into(),
+                content: include_str!("templates/vite-vue/.gitignore.tmpl"),
+            },
+            TemplateFile {
+                path: "src/main.ts".into(),
+                content: include_str!("templates/vite-vue/src/main.ts.tmpl"),
+            },
+            TemplateFile {
+                path: "src/App.vue".into(),
+                content: include_str!("templates/vite-vue/src/App.vue.tmpl"),
+            },
+            TemplateFile {
+                path: "src/style.css".into(),
+                content: include_str!("templates/vite-vue/src/style.css.tmpl"),
+            },
+            TemplateFile {
+                path: "src/vite-env.d.ts".into(),
+                content: include_str!("templates/vite-vue/src/vite-env.d.ts.tmpl"),
+            },
+        ]
+    }
+
+    fn nuxt_templates() -> Vec<TemplateFile> {
+        vec![
+            TemplateFile {
+                path: "package.json".into(),
+                content: include_str!("templates/nuxt/package.json.tmpl"),
+            },
+            TemplateFile {
+                path: "nuxt.config.ts".into(),
+                content: include_str!("templates/nuxt/nuxt.config.ts.tmpl"),
+            },
+            TemplateFile {
+                path: "tsconfig.json".into(),
+                content: include_str!("templates/nuxt/tsconfig.json.tmpl"),
+            },
+            TemplateFile {
+                path: ".gitignore".into(),
+                content: include_str!("templates/nuxt/.gitignore.tmpl"),
+            },
+            TemplateFile {
+                path: "app.vue".into(),
+                content: include_str!("templates/nuxt/app.vue.tmpl"),
+            },
+            TemplateFile {
+                path: "pages/index.vue".into(),
+                content: include_str!("templates/nuxt/pages/index.vue.tmpl"),
+            },
+            TemplateFile {
+                path: "assets/css/main.css".into(),
+                content: include_str!("templates/nuxt/assets/css/main.css.tmpl"),
+            },
+        ]
+    }
+
+    fn react_router_templates() -> Vec<TemplateFile> {
+        vec![
+            TemplateFile {
+                path: "package.json".into(),
+                content: include_str!("templates/react-router/package.json.tmpl"),
+            },
+            TemplateFile {
+                path: "react-router.config.ts".into(),
+                content: include_str!("templates/react-router/react-router.config.ts.tmpl"),
+            },
+            TemplateFile {
+                path: "vite.config.ts".into(),
+                content: include_str!("templates/react-router/vite.config.ts.tmpl"),
+            },
+            TemplateFile {
+                path: "tsconfig.json".into(),
+                content: include_str!("templates/react-router/tsconfig.json.tmpl"),
+            },
+            TemplateFile {
+                path: ".gitignore".into(),
+                content: include_str!("templates/react-router/.gitignore.tmpl"),
+            },
+            TemplateFile {
+                path: "app/root.tsx".into(),
+                content: include_str!("templates/react-router/app/root.tsx.tmpl"),
+            },
+            TemplateFile {
+                path: "app/routes.ts".into(),
+                content: include_str!("templates/react-router/app/routes.ts.tmpl"),
+            },
+            TemplateFile {
+                path: "app/routes/home.tsx".into(),
+                content: include_str!("templates/react-router/app/routes/home.tsx.tmpl"),
+            },
+            TemplateFile {
+                path: "app/app.css".into(),
+                content: include_str!("templates/react-router/app/app.css.tmpl"),
+            },
+        ]
+    }
+
+    fn astro_templates() -> Vec<TemplateFile> {
+        vec![
+            TemplateFile {
+                path: "package.json".into(),
+                content: include_str!("templates/astro/package.json.tmpl"),
+            },
+            TemplateFile {
+                path: "astro.config.mjs".into(),
+                content: include_str!("templates/astro/astro.config.mjs.tmpl"),
+            },
+            TemplateFile {
+                path: "tsconfig.json".into(),
+                content: include_str!("templates/astro/tsconfig.json.tmpl"),
+            },
+            TemplateFile {
+                path: ".gitignore".into(),
+                content: include_str!("templates/astro/.gitignore.tmpl"),
+            },
+            TemplateFile {
+                path: "src/layouts/Layout.astro".into(),
+                content: include_str!("templates/astro/src/layouts/Layout.astro.tmpl"),
+            },
+            TemplateFile {
+                path: "src/pages/index.astro".into(),
+                content: include_str!("templates/astro/src/pages/index.astro.tmpl"),
+            },
+            TemplateFile {
+                path: "src/styles/global.css".into(),
+                content: include_str!("templates/astro/src/styles/global.css.tmpl"),
+            },
+        ]
+    }
+
+    /// Tailwind v3 config files added on top of a framework's templates.
+    ///
+    /// v4 is configured from CSS, while v3 needs `tailwind.config.js` and a
+    /// PostCSS setup. Nuxt takes its PostCSS plugins from `nuxt.config.ts`.
+    fn tailwind_v3_templates(framework: FrameworkTarget) -> Vec<TemplateFile> {
+        let mut templates = vec![TemplateFile {
+            path: "tailwind.config.js".into(),
+            content: include_str!("templates/tailwind-v3/tailwind.config.js.tmpl"),
+        }];
+        if framework != FrameworkTarget::Nuxt {
+            templates.push(TemplateFile {
+                path: "postcss.config.js".into(),
+                content: include_str!("templates/tailwind-v3/postcss.config.js.tmpl"),
+            });
+        }
+        templates
+    }
+
     const fn html_templates() -> Vec<TemplateFile> {
         // HTML templates will be added post-MVP
         vec![]
@@ -247,10 +659,420 @@ impl TemplateEngine {
     }
 }
 
+/// Source globs Tailwind v3 scans for class names.
+fn tailwind_content_globs(framework: FrameworkTarget) -> Vec<&'static str> {
+    let globs: &[&str] = match framework {
+        FrameworkTarget::Html => &["./**/*.html"],
+        FrameworkTarget::ViteReact => &["./index.html", "./src/**/*.{js,ts,jsx,tsx}"],
+        FrameworkTarget::ViteVue => &["./index.html", "./src/**/*.{vue,js,ts,jsx,tsx}"],
+        FrameworkTarget::NextJs => &[
+            "./app/**/*.{js,ts,jsx,tsx,mdx}",
+            "./components/**/*.{js,ts,jsx,tsx,mdx}",
+        ],
+        FrameworkTarget::Nuxt => &[
+            "./app.vue",
+            "./components/**/*.{vue,js,ts}",
+            "./layouts/**/*.vue",
+            "./pages/**/*.vue",
+        ],
+        FrameworkTarget::ReactRouter => &["./app/**/*.{js,ts,jsx,tsx}"],
+        FrameworkTarget::Astro => &["./src/**/*.{astro,html,js,jsx,md,mdx,ts,tsx}"],
+    };
+
+    globs.to_vec()
+}
+
+/// Tailwind packages a scaffolded project depends on, as `{name, version}` maps.
+fn tailwind_dependencies(version: TailwindVersion) -> Vec<TemplateValue> {
+    let packages: &[(&str, &str)] = match version {
+        TailwindVersion::V3 => &[
+            ("autoprefixer", "^10.4.20"),
+            ("postcss", "^8.4.49"),
+            ("tailwindcss", "^3.4.17"),
+        ],
+        TailwindVersion::V4 => &[("@tailwindcss/vite", "^4.0.0"), ("tailwindcss", "^4.0.0")],
+    };
+
+    packages
+        .iter()
+        .map(|(name, version)| {
+            TemplateValue::Map(BTreeMap::from([
+                ("name".to_string(), (*name).into()),
+                ("version".to_string(), (*version).into()),
+            ]))
+        })
+        .collect()
+}
+
+/// Partials shared by the framework templates.
+fn builtin_partial(name: &str) -> Option<&'static str> {
+    match name {
+        "tailwind-css" => Some(include_str!("templates/partials/tailwind-css.tmpl")),
+        "tailwind-dependencies" => Some(include_str!(
+            "templates/partials/tailwind-dependencies.tmpl"
+        )),
+        _ => None,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Parsing
+// -----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockKind {
+    If,
+    Unless,
+    Each,
+}
+
+impl BlockKind {
+    fn parse(keyword: &str) -> Option<Self> {
+        match keyword {
+            "if" => Some(Self::If),
+            "unless" => Some(Self::Unless),
+            "each" => Some(Self::Each),
+            _ => None,
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::If => "if",
+            Self::Unless => "unless",
+            Self::Each => "each",
+        }
+    }
+}
+
+/// A single `{{...}}` tag.
+#[derive(Debug)]
+enum Tag {
+    Var { path: String, escape: bool },
+    Open { kind: BlockKind, path: String },
+    Else,
+    Close(BlockKind),
+    Partial { name: String, indent: String },
+    Comment,
+}
+
+#[derive(Debug)]
+enum Token {
+    Text(String),
+    Tag { tag: Tag, line: usize },
+}
+
+/// A parsed template.
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Var {
+        path: String,
+        escape: bool,
+    },
+    Block {
+        kind: BlockKind,
+        path: String,
+        body: Vec<Self>,
+        otherwise: Vec<Self>,
+    },
+    Partial {
+        name: String,
+        indent: String,
+        line: usize,
+    },
+}
+
+/// Where a run of nodes stopped.
+enum End {
+    Eof,
+    Else(usize),
+    Close(BlockKind, usize),
+}
+
+/// The current item of an `{{#each}}` loop.
+struct Scope<'a> {
+    item: &'a TemplateValue,
+    index: usize,
+    len: usize,
+    key: Option<&'a str>,
+}
+
+fn syntax(line: usize, message: impl Into<String>) -> TemplateError {
+    TemplateError::Syntax {
+        line,
+        message: message.into(),
+    }
+}
+
+fn parse(source: &str) -> Result<Vec<Node>, TemplateError> {
+    let mut tokens = tokenize(source)?;
+    strip_standalone(&mut tokens);
+
+    let (nodes, end) = parse_nodes(&mut tokens.into_iter())?;
+    match end {
+        End::Eof => Ok(nodes),
+        End::Else(line) => Err(syntax(line, "{{else}} outside of a block")),
+        End::Close(kind, line) => Err(syntax(
+            line,
+            format!("{{{{/{}}}}} without a matching block", kind.name()),
+        )),
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, TemplateError> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    let mut line = 1;
+
+    while let Some(start) = rest.find("{{") {
+        if start > 0 {
+            tokens.push(Token::Text(rest[..start].to_string()));
+            line += rest[..start].matches('\n').count();
+        }
+        rest = &rest[start..];
+
+        let (open, close) = if rest.starts_with("{{{") {
+            (3, "}}}")
+        } else if rest.starts_with("{{!--") {
+            (5, "--}}")
+        } else {
+            (2, "}}")
+        };
+        let end = rest[open..]
+            .find(close)
+            .ok_or_else(|| syntax(line, "unclosed tag"))?;
+        let body = &rest[open..open + end];
+
+        let tag = match open {
+            3 => Tag::Var {
+                path: body.trim().to_string(),
+                escape: false,
+            },
+            5 => Tag::Comment,
+            _ => parse_tag(body.trim(), line)?,
+        };
+        tokens.push(Token::Tag { tag, line });
+
+        let len = open + end + close.len();
+        line += rest[..len].matches('\n').count();
+        rest = &rest[len..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest.to_string()));
+    }
+
+    Ok(tokens)
+}
+
+fn parse_tag(body: &str, line: usize) -> Result<Tag, TemplateError> {
+    if body.starts_with('!') {
+        return Ok(Tag::Comment);
+    }
+    if body == "else" {
+        return Ok(Tag::Else);
+    }
+    if let Some(name) = body.strip_prefix('>') {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(syntax(line, "partial tag without a name"));
+        }
+        return Ok(Tag::Partial {
+            name: name.to_string(),
+            indent: String::new(),
+        });
+    }
+    if let Some(open) = body.strip_prefix('#') {
+        let (keyword, path) = open.split_once(char::is_whitespace).unwrap_or((open, ""));
+        let kind = BlockKind::parse(keyword)
+            .ok_or_else(|| syntax(line, format!("unknown block '{keyword}'")))?;
+        let path = path.trim();
+        if path.is_empty() {
+            return Err(syntax(
+                line,
+                format!("{{{{#{keyword}}}}} without a variable"),
+            ));
+        }
+        return Ok(Tag::Open {
+            kind,
+            path: path.to_string(),
+        });
+    }
+    if let Some(close) = body.strip_prefix('/') {
+        let kind = BlockKind::parse(close.trim())
+            .ok_or_else(|| syntax(line, format!("unknown block '{}'", close.trim())))?;
+        return Ok(Tag::Close(kind));
+    }
+    if body.is_empty() {
+        return Err(syntax(line, "empty tag"));
+    }
+
+    Ok(Tag::Var {
+        path: body.to_string(),
+        escape: true,
+    })
+}
+
+/// Remove the lines of tags that stand alone on them.
+///
+/// A standalone partial keeps the line's indentation and applies it to every
+/// line it renders.
+fn strip_standalone(tokens: &mut [Token]) {
+    let standalone: Vec<usize> = (0..tokens.len())
+        .filter(|&i| is_standalone(tokens, i))
+        .collect();
+
+    for i in standalone {
+        let indent = match i.checked_sub(1).and_then(|prev| tokens.get_mut(prev)) {
+            Some(Token::Text(text)) => {
+                let start = text.rfind('\n').map_or(0, |pos| pos + 1);
+                text.split_off(start)
+            }
+            _ => String::new(),
+        };
+        if let Some(Token::Text(text)) = tokens.get_mut(i + 1) {
+            let end = text.find('\n').map_or(text.len(), |pos| pos + 1);
+            text.drain(..end);
+        }
+        if let Token::Tag {
+            tag: Tag::Partial { indent: slot, .. },
+            ..
+        } = &mut tokens[i]
+        {
+            *slot = indent;
+        }
+    }
+}
+
+fn is_standalone(tokens: &[Token], i: usize) -> bool {
+    let is_blank = |s: &str| s.chars().all(|c| matches!(c, ' ' | '\t' | '\r'));
+
+    match &tokens[i] {
+        Token::Tag {
+            tag: Tag::Var { .. },
+            ..
+        }
+        | Token::Text(_) => return false,
+        Token::Tag { .. } => {}
+    }
+
+    let line_start = match i.checked_sub(1).map(|prev| &tokens[prev]) {
+        None => true,
+        Some(Token::Text(text)) => text.rfind('\n').map_or_else(
+            || i == 1 && is_blank(text),
+            |pos| is_blank(&text[pos + 1..]),
+        ),
+        Some(Token::Tag { .. }) => false,
+    };
+    let line_end = match tokens.get(i + 1) {
+        None => true,
+        Some(Token::Text(text)) => text.find('\n').map_or_else(
+            || i + 2 == tokens.len() && is_blank(text),
+            |pos| is_blank(&text[..pos]),
+        ),
+        Some(Token::Tag { .. }) => false,
+    };
+
+    line_start && line_end
+}
+
+fn parse_nodes(
+    tokens: &mut impl Iterator<Item = Token>,
+) -> Result<(Vec<Node>, End), TemplateError> {
+    let mut nodes = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        let (tag, line) = match token {
+            Token::Text(text) => {
+                if !text.is_empty() {
+                    nodes.push(Node::Text(text));
+                }
+                continue;
+            }
+            Token::Tag { tag, line } => (tag, line),
+        };
+
+        match tag {
+            Tag::Var { path, escape } => nodes.push(Node::Var { path, escape }),
+            Tag::Partial { name, indent } => nodes.push(Node::Partial { name, indent, line }),
+            Tag::Comment => {}
+            Tag::Else => return Ok((nodes, End::Else(line))),
+            Tag::Close(kind) => return Ok((nodes, End::Close(kind, line))),
+            Tag::Open { kind, path } => {
+                let (body, mut end) = parse_nodes(tokens)?;
+                let mut otherwise = Vec::new();
+                if matches!(end, End::Else(_)) {
+                    (otherwise, end) = parse_nodes(tokens)?;
+                }
+                match end {
+                    End::Close(close, _) if close == kind => {}
+                    End::Close(close, close_line) => {
+                        return Err(syntax(
+                            close_line,
+                            format!(
+                                "expected {{{{/{}}}}} but found {{{{/{}}}}}",
+                                kind.name(),
+                                close.name()
+                            ),
+                        ));
+                    }
+                    End::Else(else_line) => {
+                        return Err(syntax(else_line, "more than one {{else}} in a block"));
+                    }
+                    End::Eof => {
+                        return Err(syntax(
+                            line,
+                            format!("{{{{#{} {path}}}}} is never closed", kind.name()),
+                        ));
+                    }
+                }
+                nodes.push(Node::Block {
+                    kind,
+                    path,
+                    body,
+                    otherwise,
+                });
+            }
+        }
+    }
+
+    Ok((nodes, End::Eof))
+}
+
+fn field<'a>(value: &'a TemplateValue, path: &str) -> Option<&'a TemplateValue> {
+    path.split('.').try_fold(value, |value, key| match value {
+        TemplateValue::Map(fields) => fields.get(key),
+        _ => None,
+    })
+}
+
+fn escape_html(text: &str, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#x27;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+/// Append `text`, prefixing each non-empty line with `indent`.
+fn indent_lines(text: &str, indent: &str, out: &mut String) {
+    for line in text.split_inclusive('\n') {
+        if line != "\n" {
+            out.push_str(indent);
+        }
+        out.push_str(line);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use camino::Utf8Path;
+    use crate::scaffold::PackSource;
     use tempfile::TempDir;
 
     #[test]
@@ -258,7 +1080,7 @@ mod tests {
         let mut engine = TemplateEngine::default();
         engine.set("name", "my-project");
 
-        let result = engine.render("Hello, {{name}}!");
+        let result = engine.render("Hello, {{name}}!").unwrap();
         assert_eq!(result, "Hello, my-project!");
     }
 
@@ -268,7 +1090,7 @@ mod tests {
         engine.set("name", "test");
         engine.set("version", "1.0.0");
 
-        let result = engine.render("{{name}} v{{version}} - {{name}}");
+        let result = engine.render("{{name}} v{{version}} - {{name}}").unwrap();
         assert_eq!(result, "test v1.0.0 - test");
     }
 
@@ -277,7 +1099,9 @@ mod tests {
         let mut engine = TemplateEngine::default();
         engine.set("has_content", "true");
 
-        let result = engine.render("{{#if has_content}}YES{{else}}NO{{/if}}");
+        let result = engine
+            .render("{{#if has_content}}YES{{else}}NO{{/if}}")
+            .unwrap();
         assert_eq!(result, "YES");
     }
 
@@ -286,7 +1110,9 @@ mod tests {
         let mut engine = TemplateEngine::default();
         engine.set("has_content", "false");
 
-        let result = engine.render("{{#if has_content}}YES{{else}}NO{{/if}}");
+        let result = engine
+            .render("{{#if has_content}}YES{{else}}NO{{/if}}")
+            .unwrap();
         assert_eq!(result, "NO");
     }
 
@@ -294,7 +1120,9 @@ mod tests {
     fn conditional_missing() {
         let engine = TemplateEngine::default();
 
-        let result = engine.render("{{#if missing}}YES{{else}}NO{{/if}}");
+        let result = engine
+            .render("{{#if missing}}YES{{else}}NO{{/if}}")
+            .unwrap();
         assert_eq!(result, "NO");
     }
 
@@ -303,10 +1131,240 @@ mod tests {
         let mut engine = TemplateEngine::default();
         engine.set("show", "true");
 
-        let result = engine.render("prefix{{#if show}}CONTENT{{/if}}suffix");
+        let result = engine
+            .render("prefix{{#if show}}CONTENT{{/if}}suffix")
+            .unwrap();
         assert_eq!(result, "prefixCONTENTsuffix");
     }
 
+    #[test]
+    fn nested_conditionals() {
+        let mut engine = TemplateEngine::default();
+        engine.set("outer", true);
+        engine.set("inner", false);
+
+        let template = "{{#if outer}}A{{#if inner}}B{{else}}C{{#unless inner}}D{{/unless}}{{/if}}{{else}}E{{/if}}";
+        assert_eq!(engine.render(template).unwrap(), "ACD");
+    }
+
+    #[test]
+    fn each_over_list_with_metadata() {
+        let mut engine = TemplateEngine::default();
+        engine.set("routes", vec!["/", "/about", "/pricing"]);
+
+        let result = engine
+            .render("{{#each routes}}{{@index}}={{this}}{{#unless @last}}, {{/unless}}{{/each}}")
+            .unwrap();
+        assert_eq!(result, "0=/, 1=/about, 2=/pricing");
+    }
+
+    #[test]
+    fn each_over_maps_and_else() {
+        let mut engine = TemplateEngine::default();
+        engine.set("project_name", "site");
+        engine.set("deps", tailwind_dependencies(TailwindVersion::V4));
+        engine.set("empty", Vec::<TemplateValue>::new());
+
+        let result = engine
+            .render("{{#each deps}}{{name}}@{{version}} in {{project_name}};{{/each}}")
+            .unwrap();
+        assert_eq!(
+            result,
+            "@tailwindcss/vite@^4.0.0 in site;tailwindcss@^4.0.0 in site;"
+        );
+
+        let result = engine
+            .render("{{#each empty}}item{{else}}none{{/each}}")
+            .unwrap();
+        assert_eq!(result, "none");
+    }
+
+    #[test]
+    fn standalone_tags_remove_their_lines() {
+        let mut engine = TemplateEngine::default();
+        engine.set("items", vec!["a", "b"]);
+        engine.set("show", false);
+
+        let template = "start\n{{#each items}}\n  - {{this}}\n{{/each}}\n{{#if show}}\nhidden\n{{/if}}\n{{! note }}\nend\n";
+        assert_eq!(
+            engine.render(template).unwrap(),
+            "start\n  - a\n  - b\nend\n"
+        );
+    }
+
+    #[test]
+    fn partials_are_indented() {
+        let mut engine = TemplateEngine::default();
+        engine.set("items", vec!["a", "b"]);
+        engine.register_partial("list", "{{#each items}}\n- {{this}}\n{{/each}}\n");
+
+        let result = engine.render("root:\n    {{> list}}\ndone").unwrap();
+        assert_eq!(result, "root:\n    - a\n    - b\ndone");
+    }
+
+    #[test]
+    fn builtin_partials_can_be_overridden() {
+        let mut engine = TemplateEngine::default();
+        engine.set("tailwind_v3", false);
+        assert_eq!(
+            engine.render("{{> tailwind-css}}").unwrap(),
+            "@import \"tailwindcss\";\n"
+        );
+
+        engine.register_partial("tailwind-css", "custom");
+        assert_eq!(engine.render("{{> tailwind-css}}").unwrap(), "custom");
+    }
+
+    #[test]
+    fn escapes_unless_triple_braced() {
+        let mut engine = TemplateEngine::default();
+        engine.set("html", "<b title=\"x\">Tom & Jerry's</b>");
+
+        assert_eq!(
+            engine.render("{{html}}").unwrap(),
+            "&lt;b title=&quot;x&quot;&gt;Tom &amp; Jerry&#x27;s&lt;/b&gt;"
+        );
+        assert_eq!(
+            engine.render("{{{html}}}").unwrap(),
+            "<b title=\"x\">Tom & Jerry's</b>"
+        );
+    }
+
+    #[test]
+    fn strict_mode_rejects_undefined_variables() {
+        let mut engine = TemplateEngine::default().with_strict(true);
+        engine.set("known", "yes");
+
+        assert!(matches!(
+            engine.render("{{unknown}}"),
+            Err(TemplateError::UndefinedVariable(name)) if name == "unknown"
+        ));
+        assert!(matches!(
+            engine.render("{{#if unknown}}x{{/if}}"),
+            Err(TemplateError::UndefinedVariable(_))
+        ));
+        assert_eq!(engine.render("{{known}}").unwrap(), "yes");
+
+        let lenient = TemplateEngine::default();
+        assert_eq!(lenient.render("[{{unknown}}]").unwrap(), "[]");
+    }
+
+    #[test]
+    fn syntax_errors_report_lines() {
+        let engine = TemplateEngine::default();
+
+        assert!(matches!(
+            engine.render("ok\n{{#if a}}\nnever closed"),
+            Err(TemplateError::Syntax { line: 2, .. })
+        ));
+        assert!(matches!(
+            engine.render("{{#if a}}{{/each}}"),
+            Err(TemplateError::Syntax { line: 1, .. })
+        ));
+        assert!(matches!(
+            engine.render("{{/if}}"),
+            Err(TemplateError::Syntax { .. })
+        ));
+        assert!(matches!(
+            engine.render("{{#loop a}}{{/loop}}"),
+            Err(TemplateError::Syntax { .. })
+        ));
+        assert!(matches!(
+            engine.render("{{name"),
+            Err(TemplateError::Syntax { .. })
+        ));
+        assert!(matches!(
+            engine.render("{{> missing}}"),
+            Err(TemplateError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn recursive_partials_are_rejected() {
+        let mut engine = TemplateEngine::default();
+        engine.register_partial("loop", "{{> loop}}");
+
+        assert!(matches!(
+            engine.render("{{> loop}}"),
+            Err(TemplateError::Syntax { .. })
+        ));
+    }
+
+    #[test]
+    fn all_frameworks_render_strictly() {
+        let frameworks = [
+            FrameworkTarget::ViteReact,
+            FrameworkTarget::ViteVue,
+            FrameworkTarget::Nuxt,
+            FrameworkTarget::ReactRouter,
+            FrameworkTarget::Astro,
+        ];
+        for framework in frameworks {
+            for version in [TailwindVersion::V3, TailwindVersion::V4] {
+                let config = ProjectConfig::new("my-site", Utf8Path::new("/tmp"))
+                    .with_framework(framework)
+                    .with_tailwind_version(version);
+                let mut vfs = VirtualFs::new(&config.path);
+                TemplateEngine::from_config(&config)
+                    .stage(&config, &mut vfs)
+                    .unwrap();
+
+                let package_json = vfs.read(&config.path.join("package.json")).unwrap();
+                let json: serde_json::Value = serde_json::from_str(package_json).unwrap();
+                assert_eq!(json["name"], "my-site");
+                let tailwind = ["dependencies", "devDependencies"]
+                    .iter()
+                    .find_map(|key| json[key]["tailwindcss"].as_str())
+                    .unwrap();
+                assert_eq!(tailwind.starts_with("^3"), version == TailwindVersion::V3);
+            }
+        }
+    }
+
+    #[test]
+    fn pack_files_and_partials_layer_over_builtins() {
+        let config = ProjectConfig::new("acme-site", Utf8Path::new("/tmp"))
+            .with_framework(FrameworkTarget::ViteReact);
+        let pack = TemplatePack {
+            name: "acme".to_string(),
+            description: String::new(),
+            framework: None,
+            variables: Vec::new(),
+            files: vec![
+                PackFile {
+                    path: "src/tokens.css".into(),
+                    content: b"--brand: {{{brand_color}}};\n".to_vec(),
+                    render: true,
+                },
+                PackFile {
+                    path: ".github/workflows/ci.yml".into(),
+                    content: b"os: ${{ matrix.os }}\n".to_vec(),
+                    render: false,
+                },
+            ],
+            partials: HashMap::from([(
+                "tailwind-css".to_string(),
+                "@import \"tailwindcss\";\n@import \"./tokens.css\";\n".to_string(),
+            )]),
+            source: PackSource::Project,
+            path: "/tmp/acme".into(),
+        };
+
+        let mut engine = TemplateEngine::from_config(&config);
+        engine.add_pack(
+            &pack,
+            BTreeMap::from([("brand_color".to_string(), "#4f46e5".to_string())]),
+        );
+        let mut vfs = VirtualFs::new(&config.path);
+        engine.stage(&config, &mut vfs).unwrap();
+
+        let read = |path: &str| vfs.read(&config.path.join(path)).unwrap();
+        assert_eq!(read("src/tokens.css"), "--brand: #4f46e5;\n");
+        assert_eq!(read(".github/workflows/ci.yml"), "os: ${{ matrix.os }}\n");
+        assert!(read("src/index.css").contains("./tokens.css"));
+        assert!(read("package.json").contains("\"name\": \"acme-site\""));
+    }
+
     #[test]
     fn vite_react_templates_exist() {
         let templates = TemplateEngine::get_templates(FrameworkTarget::ViteReact);
@@ -318,6 +1376,40 @@ mod tests {
         assert!(paths.contains(&"src/main.tsx"));
     }
 
+    #[test]
+    fn vue_templates_exist() {
+        let paths: Vec<_> = TemplateEngine::get_templates(FrameworkTarget::ViteVue)
+            .into_iter()
+            .map(|t| t.path)
+            .collect();
+        assert!(paths.iter().any(|p| p == "src/App.vue"));
+        assert!(paths.iter().any(|p| p == "src/main.ts"));
+
+        let paths: Vec<_> = TemplateEngine::get_templates(FrameworkTarget::Nuxt)
+            .into_iter()
+            .map(|t| t.path)
+            .collect();
+        assert!(paths.iter().any(|p| p == "nuxt.config.ts"));
+        assert!(paths.iter().any(|p| p == "pages/index.vue"));
+    }
+
+    #[test]
+    fn react_router_and_astro_templates_exist() {
+        let paths: Vec<_> = TemplateEngine::get_templates(FrameworkTarget::ReactRouter)
+            .into_iter()
+            .map(|t| t.path)
+            .collect();
+        assert!(paths.iter().any(|p| p == "app/routes.ts"));
+        assert!(paths.iter().any(|p| p == "app/routes/home.tsx"));
+
+        let paths: Vec<_> = TemplateEngine::get_templates(FrameworkTarget::Astro)
+            .into_iter()
+            .map(|t| t.path)
+            .collect();
+        assert!(paths.iter().any(|p| p == "astro.config.mjs"));
+        assert!(paths.iter().any(|p| p == "src/pages/index.astro"));
+    }
+
     #[test]
     fn scaffold_creates_files() {
         let dir = TempDir::new().unwrap();
@@ -339,6 +1431,53 @@ mod tests {
         assert!(content.contains("\"name\": \"test-project\""));
     }
 
+    #[test]
+    fn scaffold_tailwind_v3() {
+        let dir = TempDir::new().unwrap();
+        let base_path = Utf8Path::from_path(dir.path()).unwrap();
+
+        let config = ProjectConfig::new("v3-project", base_path)
+            .with_framework(FrameworkTarget::ViteReact)
+            .with_tailwind_version(TailwindVersion::V3);
+        TemplateEngine::from_config(&config)
+            .scaffold(&config)
+            .unwrap();
+
+        let css = fs::read_to_string(config.path.join("src/index.css")).unwrap();
+        assert!(css.starts_with("@tailwind base;"));
+
+        let tailwind_config = fs::read_to_string(config.path.join("tailwind.config.js")).unwrap();
+        assert!(tailwind_config.contains("'./src/**/*.{js,ts,jsx,tsx}'"));
+        assert!(config.path.join("postcss.config.js").exists());
+
+        let package_json = fs::read_to_string(config.path.join("package.json")).unwrap();
+        assert!(package_json.contains("\"tailwindcss\": \"^3.4.17\""));
+        assert!(!package_json.contains("@tailwindcss/vite"));
+        serde_json::from_str::<serde_json::Value>(&package_json).unwrap();
+
+        let vite_config = fs::read_to_string(config.path.join("vite.config.ts")).unwrap();
+        assert!(vite_config.contains("plugins: [react()]"));
+    }
+
+    #[test]
+    fn scaffold_tailwind_v4_has_no_config_files() {
+        let dir = TempDir::new().unwrap();
+        let base_path = Utf8Path::from_path(dir.path()).unwrap();
+
+        let config =
+            ProjectConfig::new("v4-project", base_path).with_framework(FrameworkTarget::ViteVue);
+        TemplateEngine::from_config(&config)
+            .scaffold(&config)
+            .unwrap();
+
+        assert!(!config.path.join("tailwind.config.js").exists());
+        let css = fs::read_to_string(config.path.join("src/style.css")).unwrap();
+        assert_eq!(css, "@import \"tailwindcss\";\n");
+
+        let package_json = fs::read_to_string(config.path.join("package.json")).unwrap();
+        serde_json::from_str::<serde_json::Value>(&package_json).unwrap();
+    }
+
     #[test]
     fn scaffold_creates_nested_dirs() {
         let dir = TempDir::new().unwrap();
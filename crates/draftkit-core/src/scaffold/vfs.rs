@@ -0,0 +1,526 @@
+//! In-memory staging for planned writes.
+//!
+//! Scaffolding and page generation can render into a [`VirtualFs`] instead
+//! of writing straight to disk. The staged files can then be previewed as a
+//! tree and unified diffs against what is already on disk, serialized as a
+//! [`WritePlan`] for CI and agents, or applied. Binary files are staged as
+//! bytes and reported without a line diff.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::Serialize;
+
+/// Lines of context around each diff hunk.
+const DIFF_CONTEXT: usize = 3;
+
+/// Largest LCS table built for a diff; bigger changes are shown as the old
+/// lines removed and the new ones added.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Files staged in memory, keyed by absolute path.
+#[derive(Debug, Clone, Default)]
+pub struct VirtualFs {
+    root: Utf8PathBuf,
+    files: BTreeMap<Utf8PathBuf, Vec<u8>>,
+}
+
+/// How a staged file differs from disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// The file does not exist yet
+    Create,
+    /// The file exists with different content
+    Modify,
+    /// The file exists with the same content
+    Unchanged,
+}
+
+/// A planned write, compared against disk.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileChange {
+    /// Path relative to the staging root
+    pub path: Utf8PathBuf,
+    /// How the write changes the file
+    pub kind: ChangeKind,
+    /// Size of the new content in bytes
+    pub bytes: usize,
+    /// Unified diff (empty when unchanged; a one-line note for binary files)
+    pub diff: String,
+}
+
+/// Serializable description of everything a run would do.
+#[derive(Debug, Clone, Serialize)]
+pub struct WritePlan {
+    /// Directory the paths are relative to
+    pub root: Utf8PathBuf,
+    /// Planned file writes, sorted by path
+    pub files: Vec<FileChange>,
+    /// Commands that would run after writing (e.g., package install)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<Vec<String>>,
+}
+
+impl WritePlan {
+    /// Add a command that would run after the files are written.
+    #[must_use]
+    pub fn with_command(mut self, command: Vec<String>) -> Self {
+        self.commands.push(command);
+        self
+    }
+}
+
+impl VirtualFs {
+    /// Create an empty staging area for files under `root`.
+    pub fn new(root: impl Into<Utf8PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            files: BTreeMap::new(),
+        }
+    }
+
+    /// Directory the staged files are reported relative to.
+    #[must_use]
+    pub fn root(&self) -> &Utf8Path {
+        &self.root
+    }
+
+    /// Stage a file, replacing anything staged at the same path.
+    pub fn write(&mut self, path: impl Into<Utf8PathBuf>, content: impl Into<String>) {
+        self.files.insert(path.into(), content.into().into_bytes());
+    }
+
+    /// Stage a file's raw bytes, such as an image copied from a template pack.
+    pub fn write_bytes(&mut self, path: impl Into<Utf8PathBuf>, content: impl Into<Vec<u8>>) {
+        self.files.insert(path.into(), content.into());
+    }
+
+    /// Staged text content at `path`, if any and valid UTF-8.
+    #[must_use]
+    pub fn read(&self, path: &Utf8Path) -> Option<&str> {
+        self.files
+            .get(path)
+            .and_then(|content| std::str::from_utf8(content).ok())
+    }
+
+    /// Number of staged files.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Whether nothing is staged.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Staged paths in sorted order.
+    pub fn paths(&self) -> impl Iterator<Item = &Utf8Path> {
+        self.files.keys().map(Utf8PathBuf::as_path)
+    }
+
+    /// Compare every staged file against disk.
+    #[must_use]
+    pub fn changes(&self) -> Vec<FileChange> {
+        self.files
+            .iter()
+            .map(|(path, content)| {
+                let relative = self.relative(path);
+                let existing = fs::read(path).ok();
+                let kind = match &existing {
+                    None => ChangeKind::Create,
+                    Some(old) if old == content => ChangeKind::Unchanged,
+                    Some(_) => ChangeKind::Modify,
+                };
+                let old_text = existing.as_deref().map(|old| std::str::from_utf8(old).ok());
+                let diff = match (old_text, std::str::from_utf8(content).ok()) {
+                    _ if kind == ChangeKind::Unchanged => String::new(),
+                    (None, Some(new)) => unified_diff(relative.as_str(), None, new),
+                    (Some(Some(old)), Some(new)) => unified_diff(relative.as_str(), Some(old), new),
+                    (old, _) => binary_diff(relative.as_str(), old.is_some()),
+                };
+                FileChange {
+                    diff,
+                    path: relative,
+                    kind,
+                    bytes: content.len(),
+                }
+            })
+            .collect()
+    }
+
+    /// Describe the staged writes.
+    #[must_use]
+    pub fn plan(&self) -> WritePlan {
+        WritePlan {
+            root: self.root.clone(),
+            files: self.changes(),
+            commands: vec![],
+        }
+    }
+
+    /// Write every staged file to disk, creating parent directories.
+    ///
+    /// Returns the paths written, in sorted order.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first I/O error encountered.
+    pub fn apply(&self) -> io::Result<Vec<Utf8PathBuf>> {
+        for (path, content) in &self.files {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, content)?;
+        }
+        Ok(self.files.keys().cloned().collect())
+    }
+
+    /// Render the staged paths as a tree under the root directory.
+    #[must_use]
+    pub fn tree(&self) -> String {
+        #[derive(Default)]
+        struct Node(BTreeMap<String, Self>);
+
+        fn render(node: &Node, prefix: &str, out: &mut String) {
+            let count = node.0.len();
+            for (i, (name, child)) in node.0.iter().enumerate() {
+                let last = i + 1 == count;
+                let branch = if last { "└── " } else { "├── " };
+                let slash = if child.0.is_empty() { "" } else { "/" };
+                out.push_str(&format!("{prefix}{branch}{name}{slash}\n"));
+                let extension = if last { "    " } else { "│   " };
+                render(child, &format!("{prefix}{extension}"), out);
+            }
+        }
+
+        let mut tree = Node::default();
+        for path in self.files.keys() {
+            let mut node = &mut tree;
+            for component in self.relative(path).components() {
+                node = node.0.entry(component.as_str().to_string()).or_default();
+            }
+        }
+
+        let mut out = format!("{}/\n", self.root.file_name().unwrap_or(self.root.as_str()));
+        render(&tree, "", &mut out);
+        out
+    }
+
+    /// `path` relative to the root, or unchanged when outside it.
+    fn relative(&self, path: &Utf8Path) -> Utf8PathBuf {
+        path.strip_prefix(&self.root).unwrap_or(path).to_path_buf()
+    }
+}
+
+/// One line of a line-level edit script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+/// Unified diff from `old` to `new` for a file at `path`.
+///
+/// A missing `old` diffs against `/dev/null`. Returns an empty string when
+/// the contents are identical.
+#[must_use]
+pub fn unified_diff(path: &str, old: Option<&str>, new: &str) -> String {
+    if old == Some(new) {
+        return String::new();
+    }
+
+    // Lines keep their newline, so adding or removing the final one is a change
+    let old_lines: Vec<&str> = old
+        .map(|old| old.split_inclusive('\n').collect())
+        .unwrap_or_default();
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+    let edits = line_edits(&old_lines, &new_lines);
+
+    let mut diff = match old {
+        Some(_) => format!("--- a/{path}\n+++ b/{path}\n"),
+        None => format!("--- /dev/null\n+++ b/{path}\n"),
+    };
+
+    // Line numbers before each edit
+    let mut positions = Vec::with_capacity(edits.len() + 1);
+    let (mut old_line, mut new_line) = (0, 0);
+    for edit in &edits {
+        positions.push((old_line, new_line));
+        match edit {
+            Edit::Equal(_) => {
+                old_line += 1;
+                new_line += 1;
+            }
+            Edit::Delete(_) => old_line += 1,
+            Edit::Insert(_) => new_line += 1,
+        }
+    }
+    positions.push((old_line, new_line));
+
+    let mut i = 0;
+    while i < edits.len() {
+        if matches!(edits[i], Edit::Equal(_)) {
+            i += 1;
+            continue;
+        }
+
+        // Extend the hunk while changes are within two contexts of each other
+        let start = i.saturating_sub(DIFF_CONTEXT);
+        let mut last_change = i;
+        let mut j = i;
+        while j < edits.len() {
+            if !matches!(edits[j], Edit::Equal(_)) {
+                last_change = j;
+            } else if j - last_change > 2 * DIFF_CONTEXT {
+                break;
+            }
+            j += 1;
+        }
+        let end = (last_change + DIFF_CONTEXT + 1).min(edits.len());
+
+        let (old_start, new_start) = positions[start];
+        let (old_end, new_end) = positions[end];
+        diff.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_start, old_end - old_start),
+            hunk_range(new_start, new_end - new_start)
+        ));
+        for edit in &edits[start..end] {
+            let (marker, line) = match edit {
+                Edit::Equal(line) => (' ', line),
+                Edit::Delete(line) => ('-', line),
+                Edit::Insert(line) => ('+', line),
+            };
+            diff.push(marker);
+            diff.push_str(line);
+            if !line.ends_with('\n') {
+                diff.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+
+        i = end;
+    }
+
+    diff
+}
+
+/// Diff note for a binary file, in git's wording.
+fn binary_diff(path: &str, exists: bool) -> String {
+    let old = if exists {
+        format!("a/{path}")
+    } else {
+        "/dev/null".to_string()
+    };
+    format!("Binary files {old} and b/{path} differ\n")
+}
+
+/// Hunk range in unified diff notation (1-based start, or 0 when empty).
+fn hunk_range(start: usize, count: usize) -> String {
+    let start = if count == 0 { start } else { start + 1 };
+    format!("{start},{count}")
+}
+
+/// Line edit script via longest common subsequence.
+///
+/// Common leading and trailing lines are trimmed first, which keeps the
+/// table small for the typical case of a few changed lines. Past
+/// [`MAX_DIFF_CELLS`] the remaining lines are all deleted and inserted.
+fn line_edits<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Edit<'a>> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+    let width = b.len() + 1;
+
+    let mut edits: Vec<Edit<'a>> = old[..prefix].iter().map(|l| Edit::Equal(l)).collect();
+    if (a.len() + 1).saturating_mul(width) > MAX_DIFF_CELLS {
+        edits.extend(a.iter().map(|l| Edit::Delete(l)));
+        edits.extend(b.iter().map(|l| Edit::Insert(l)));
+        edits.extend(old[old.len() - suffix..].iter().map(|l| Edit::Equal(l)));
+        return edits;
+    }
+
+    // lcs[i * width + j] = LCS length of a[i..] and b[j..]
+    let mut lcs = vec![0usize; (a.len() + 1) * width];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i * width + j] = if a[i] == b[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            edits.push(Edit::Equal(a[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+            edits.push(Edit::Delete(a[i]));
+            i += 1;
+        } else {
+            edits.push(Edit::Insert(b[j]));
+            j += 1;
+        }
+    }
+    edits.extend(a[i..].iter().map(|l| Edit::Delete(l)));
+    edits.extend(b[j..].iter().map(|l| Edit::Insert(l)));
+    edits.extend(old[old.len() - suffix..].iter().map(|l| Edit::Equal(l)));
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn diff_new_file() {
+        assert_eq!(
+            unified_diff("src/App.tsx", None, "a\nb\n"),
+            "--- /dev/null\n+++ b/src/App.tsx\n@@ -0,0 +1,2 @@\n+a\n+b\n"
+        );
+    }
+
+    #[test]
+    fn diff_identical_is_empty() {
+        assert_eq!(unified_diff("a.txt", Some("same\n"), "same\n"), "");
+    }
+
+    #[test]
+    fn diff_modified_lines_with_context() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n13\n14\n15\n16\n";
+        let new = "1\n2\nthree\n4\n5\n6\n7\n8\n9\n10\n11\n12\n13\n14\n15\n16\n17\n";
+
+        assert_eq!(
+            unified_diff("n.txt", Some(old), new),
+            "--- a/n.txt\n+++ b/n.txt\n\
+             @@ -1,6 +1,6 @@\n 1\n 2\n-3\n+three\n 4\n 5\n 6\n\
+             @@ -14,3 +14,4 @@\n 14\n 15\n 16\n+17\n"
+        );
+    }
+
+    #[test]
+    fn diff_trailing_newline_change() {
+        assert_eq!(
+            unified_diff("a.txt", Some("a\nb"), "a\nb\n"),
+            "--- a/a.txt\n+++ b/a.txt\n@@ -1,2 +1,2 @@\n a\n-b\n\\ No newline at end of file\n+b\n"
+        );
+        assert_eq!(
+            unified_diff("a.txt", Some("a\n"), "a"),
+            "--- a/a.txt\n+++ b/a.txt\n@@ -1,1 +1,1 @@\n-a\n+a\n\\ No newline at end of file\n"
+        );
+    }
+
+    #[test]
+    fn diff_large_rewrite_replaces_whole_file() {
+        let old: String = (0..3000).map(|i| format!("old {i}\n")).collect();
+        let new: String = (0..3000).map(|i| format!("new {i}\n")).collect();
+        let diff = unified_diff("lock.json", Some(&old), &new);
+        assert!(
+            diff.starts_with("--- a/lock.json\n+++ b/lock.json\n@@ -1,3000 +1,3000 @@\n-old 0\n")
+        );
+        assert_eq!(diff.lines().filter(|l| l.starts_with('-')).count(), 3001);
+        assert_eq!(diff.lines().filter(|l| l.starts_with('+')).count(), 3001);
+    }
+
+    #[test]
+    fn changes_compare_against_disk() {
+        let dir = TempDir::new().unwrap();
+        let root = Utf8PathBuf::try_from(dir.path().to_path_buf()).unwrap();
+        fs::write(root.join("same.txt"), "x\n").unwrap();
+        fs::write(root.join("edit.txt"), "old\n").unwrap();
+
+        let mut vfs = VirtualFs::new(&root);
+        vfs.write(root.join("same.txt"), "x\n");
+        vfs.write(root.join("edit.txt"), "new\n");
+        vfs.write(root.join("src/new.txt"), "hello\n");
+
+        let kinds: Vec<_> = vfs
+            .changes()
+            .into_iter()
+            .map(|c| (c.path, c.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("edit.txt".into(), ChangeKind::Modify),
+                ("same.txt".into(), ChangeKind::Unchanged),
+                ("src/new.txt".into(), ChangeKind::Create),
+            ]
+        );
+
+        // Nothing touches disk until applied
+        assert!(!root.join("src/new.txt").exists());
+        vfs.apply().unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("src/new.txt")).unwrap(),
+            "hello\n"
+        );
+    }
+
+    #[test]
+    fn binary_files_are_staged_as_bytes() {
+        let dir = TempDir::new().unwrap();
+        let root = Utf8PathBuf::try_from(dir.path().to_path_buf()).unwrap();
+        let png = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0xff];
+
+        let mut vfs = VirtualFs::new(&root);
+        vfs.write_bytes(root.join("public/logo.png"), png);
+        assert_eq!(vfs.read(&root.join("public/logo.png")), None);
+
+        let change = &vfs.changes()[0];
+        assert_eq!(change.kind, ChangeKind::Create);
+        assert_eq!(change.bytes, png.len());
+        assert_eq!(
+            change.diff,
+            "Binary files /dev/null and b/public/logo.png differ\n"
+        );
+
+        vfs.apply().unwrap();
+        assert_eq!(fs::read(root.join("public/logo.png")).unwrap(), png);
+        assert_eq!(vfs.changes()[0].kind, ChangeKind::Unchanged);
+    }
+
+    #[test]
+    fn tree_rendering() {
+        let mut vfs = VirtualFs::new("/work/site");
+        vfs.write("/work/site/package.json", "");
+        vfs.write("/work/site/src/App.tsx", "");
+        vfs.write("/work/site/src/main.tsx", "");
+
+        assert_eq!(
+            vfs.tree(),
+            "site/\n├── package.json\n└── src/\n    ├── App.tsx\n    └── main.tsx\n"
+        );
+    }
+
+    #[test]
+    fn plan_serializes_kinds() {
+        let mut vfs = VirtualFs::new("/nonexistent/site");
+        vfs.write("/nonexistent/site/index.html", "<p></p>\n");
+
+        let plan = vfs
+            .plan()
+            .with_command(vec!["npm".into(), "install".into()]);
+        let json = serde_json::to_value(&plan).unwrap();
+        assert_eq!(json["files"][0]["path"], "index.html");
+        assert_eq!(json["files"][0]["kind"], "create");
+        assert_eq!(json["commands"][0][1], "install");
+    }
+}
@@ -0,0 +1,503 @@
+//! Monorepo workspace detection and membership.
+//!
+//! A workspace root is the nearest directory declaring member packages,
+//! either in `pnpm-workspace.yaml` or in the `workspaces` field of
+//! `package.json` (npm, yarn and bun). Projects created inside a workspace
+//! install from its root and are added to its member globs when no existing
+//! glob covers them.
+
+use std::fs;
+
+use camino::{Utf8Path, Utf8PathBuf};
+
+use super::package_json::PackageJson;
+use super::{GenerateError, PackageManager, VirtualFs};
+
+/// pnpm's workspace file.
+pub const PNPM_WORKSPACE_FILE: &str = "pnpm-workspace.yaml";
+
+/// Where a workspace declares its members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceConfig {
+    /// `packages` in `pnpm-workspace.yaml`
+    PnpmYaml,
+    /// `workspaces` in `package.json`
+    PackageJson,
+}
+
+/// An enclosing monorepo workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    /// Workspace root directory
+    pub root: Utf8PathBuf,
+    /// Manager pinned or locked at the root, if it can be told
+    pub package_manager: Option<PackageManager>,
+    /// Member globs, relative to the root
+    pub packages: Vec<String>,
+    /// File declaring the members
+    pub config: WorkspaceConfig,
+}
+
+impl Workspace {
+    /// Find the workspace `dir` belongs to, checking `dir` and its ancestors.
+    #[must_use]
+    pub fn find(dir: &Utf8Path) -> Option<Self> {
+        dir.ancestors().find_map(Self::at)
+    }
+
+    /// The workspace rooted exactly at `dir`, if it declares one.
+    #[must_use]
+    pub fn at(dir: &Utf8Path) -> Option<Self> {
+        let package_json = read_json(&dir.join("package.json"));
+
+        let (packages, config) = if let Ok(yaml) = fs::read_to_string(dir.join(PNPM_WORKSPACE_FILE))
+        {
+            (pnpm_packages(&yaml), WorkspaceConfig::PnpmYaml)
+        } else {
+            let workspaces = package_json.as_ref()?.get("workspaces")?;
+            // Yarn also accepts `{ "packages": [...], "nohoist": [...] }`
+            let list = workspaces.get("packages").unwrap_or(workspaces);
+            let packages = list
+                .as_array()?
+                .iter()
+                .filter_map(|glob| glob.as_str().map(ToString::to_string))
+                .collect();
+            (packages, WorkspaceConfig::PackageJson)
+        };
+
+        let package_manager = package_json
+            .as_ref()
+            .and_then(PackageManager::from_package_json)
+            .or_else(|| PackageManager::detect_from_lockfile(dir))
+            .or_else(|| (config == WorkspaceConfig::PnpmYaml).then_some(PackageManager::Pnpm));
+
+        Some(Self {
+            root: dir.to_path_buf(),
+            package_manager,
+            packages,
+            config,
+        })
+    }
+
+    /// `dir` relative to the root, with `/` separators.
+    #[must_use]
+    pub fn member_path(&self, dir: &Utf8Path) -> Option<String> {
+        let relative = dir.strip_prefix(&self.root).ok()?;
+        let path = relative
+            .components()
+            .map(|c| c.as_str())
+            .collect::<Vec<_>>()
+            .join("/");
+        (!path.is_empty()).then_some(path)
+    }
+
+    /// Whether the member globs cover `dir`.
+    #[must_use]
+    pub fn includes(&self, dir: &Utf8Path) -> bool {
+        let Some(path) = self.member_path(dir) else {
+            return false;
+        };
+        let matches = |glob: &str| glob_matches(glob.trim_start_matches("./"), &path);
+
+        self.packages
+            .iter()
+            .filter(|glob| !glob.starts_with('!'))
+            .any(|glob| matches(glob))
+            && !self
+                .packages
+                .iter()
+                .filter_map(|glob| glob.strip_prefix('!'))
+                .any(matches)
+    }
+
+    /// Stage the workspace config with `dir` added as a member.
+    ///
+    /// Returns `false`, staging nothing, when `dir` is already covered or the
+    /// workspace config has no member list to add it to.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Io` when the workspace config cannot be read or updated.
+    pub fn stage_member(&self, dir: &Utf8Path, vfs: &mut VirtualFs) -> Result<bool, GenerateError> {
+        if self.includes(dir) {
+            return Ok(false);
+        }
+        let Some(member) = self.member_path(dir) else {
+            return Ok(false);
+        };
+
+        match self.config {
+            WorkspaceConfig::PnpmYaml => {
+                let path = self.root.join(PNPM_WORKSPACE_FILE);
+                let yaml = fs::read_to_string(&path)?;
+                vfs.write(path, add_pnpm_package(&yaml, &member));
+            }
+            WorkspaceConfig::PackageJson => {
+                let path = self.root.join("package.json");
+                let mut json = PackageJson::parse(&fs::read_to_string(&path)?)?;
+                let workspaces = json
+                    .as_object_mut()
+                    .and_then(|object| object.get_mut("workspaces"));
+                let list = workspaces.and_then(|workspaces| match workspaces {
+                    PackageJson::Object(object) => object.get_mut("packages")?.as_array_mut(),
+                    other => other.as_array_mut(),
+                });
+                let Some(list) = list else {
+                    return Ok(false);
+                };
+                list.push(PackageJson::string(member));
+                let content = json.to_pretty_string()?;
+                vfs.write(path, content);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Read a JSON file, or `None` if it is missing or malformed.
+fn read_json(path: &Utf8Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// Entries of the `packages:` list in `pnpm-workspace.yaml`.
+fn pnpm_packages(yaml: &str) -> Vec<String> {
+    if let Some((open, close)) = pnpm_flow_list(yaml) {
+        return yaml[open + 1..close]
+            .split(',')
+            .map(unquote)
+            .filter(|item| !item.is_empty())
+            .map(String::from)
+            .collect();
+    }
+
+    let mut in_packages = false;
+    let mut packages = Vec::new();
+    for line in yaml.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with([' ', '\t', '-']) {
+            in_packages = line.starts_with("packages:");
+            continue;
+        }
+        if in_packages && let Some(item) = trimmed.strip_prefix('-') {
+            packages.push(unquote(item.split(" #").next().unwrap_or(item)).to_string());
+        }
+    }
+    packages
+}
+
+/// Byte offsets of the brackets when `packages:` is a flow sequence
+/// (`packages: ['apps/*']`), which may span several lines.
+fn pnpm_flow_list(yaml: &str) -> Option<(usize, usize)> {
+    let mut offset = 0;
+    let key_end = yaml.split_inclusive('\n').find_map(|line| {
+        let start = offset;
+        offset += line.len();
+        line.starts_with("packages:")
+            .then(|| start + "packages:".len())
+    })?;
+    let value = &yaml[key_end..];
+    let open = key_end + value.find(|c: char| c != ' ' && c != '\t')?;
+    if yaml.as_bytes()[open] != b'[' {
+        return None;
+    }
+    let close = open + yaml[open..].find(']')?;
+    Some((open, close))
+}
+
+/// `yaml` with `member` appended to its `packages:` list.
+fn add_pnpm_package(yaml: &str, member: &str) -> String {
+    if let Some((open, close)) = pnpm_flow_list(yaml) {
+        return add_pnpm_flow_package(yaml, open, close, member);
+    }
+
+    let lines: Vec<&str> = yaml.lines().collect();
+    let Some(start) = lines.iter().position(|line| line.starts_with("packages:")) else {
+        let mut out = yaml.to_string();
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str(&format!("packages:\n  - '{member}'\n"));
+        return out;
+    };
+
+    // Match the indentation and quoting of the last entry
+    let last_item = lines[start + 1..]
+        .iter()
+        .take_while(|line| line.trim().is_empty() || line.starts_with([' ', '\t', '-']))
+        .enumerate()
+        .filter(|(_, line)| line.trim_start().starts_with('-'))
+        .last();
+    let (insert_at, entry) = match last_item {
+        Some((offset, line)) => {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let value = line.trim_start()[1..].trim();
+            let quote = value.chars().next().filter(|c| matches!(c, '\'' | '"'));
+            let quote = quote.map(String::from).unwrap_or_default();
+            (
+                start + offset + 2,
+                format!("{indent}- {quote}{member}{quote}"),
+            )
+        }
+        None => (start + 1, format!("  - '{member}'")),
+    };
+
+    let mut out: Vec<String> = lines.iter().map(ToString::to_string).collect();
+    out.insert(insert_at, entry);
+    let mut out = out.join("\n");
+    out.push('\n');
+    out
+}
+
+/// Insert `member` before the closing bracket of a flow `packages:` list,
+/// quoted like the first entry.
+fn add_pnpm_flow_package(yaml: &str, open: usize, close: usize, member: &str) -> String {
+    let items = &yaml[open + 1..close];
+    let quote = items
+        .trim_start()
+        .chars()
+        .next()
+        .filter(|c| matches!(c, '\'' | '"'))
+        .unwrap_or('\'');
+    let before = items.trim_end();
+    let entry = if before.trim().is_empty() {
+        format!("{quote}{member}{quote}")
+    } else if before.ends_with(',') {
+        format!(" {quote}{member}{quote},")
+    } else {
+        format!(", {quote}{member}{quote}")
+    };
+
+    let insert_at = open + 1 + before.len();
+    let mut out = yaml.to_string();
+    out.insert_str(insert_at, &entry);
+    out
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    value
+        .strip_prefix('\'')
+        .and_then(|v| v.strip_suffix('\''))
+        .or_else(|| value.strip_prefix('"').and_then(|v| v.strip_suffix('"')))
+        .unwrap_or(value)
+}
+
+/// Match a workspace glob (`*` within a segment, `**` across segments).
+fn glob_matches(glob: &str, path: &str) -> bool {
+    fn segments_match(glob: &[&str], path: &[&str]) -> bool {
+        match (glob.first(), path.first()) {
+            (None, None) => true,
+            (Some(&"**"), _) => {
+                segments_match(&glob[1..], path)
+                    || (!path.is_empty() && segments_match(glob, &path[1..]))
+            }
+            (Some(g), Some(p)) => segment_matches(g, p) && segments_match(&glob[1..], &path[1..]),
+            _ => false,
+        }
+    }
+
+    fn segment_matches(glob: &str, segment: &str) -> bool {
+        match glob.split_once('*') {
+            None => glob == segment,
+            Some((prefix, rest)) => {
+                let Some(tail) = segment.strip_prefix(prefix) else {
+                    return false;
+                };
+                (0..=tail.len())
+                    .filter(|&i| tail.is_char_boundary(i))
+                    .any(|i| segment_matches(rest, &tail[i..]))
+            }
+        }
+    }
+
+    let glob: Vec<&str> = glob.trim_end_matches('/').split('/').collect();
+    let path: Vec<&str> = path.split('/').collect();
+    segments_match(&glob, &path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn root_with(files: &[(&str, &str)]) -> (TempDir, Utf8PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let root = Utf8PathBuf::try_from(dir.path().to_path_buf()).unwrap();
+        for (path, content) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        (dir, root)
+    }
+
+    #[test]
+    fn globs() {
+        assert!(glob_matches("apps/*", "apps/web"));
+        assert!(!glob_matches("apps/*", "apps/web/nested"));
+        assert!(glob_matches("packages/**", "packages/ui/icons"));
+        assert!(glob_matches("**", "anything/at/all"));
+        assert!(glob_matches("apps/site-*", "apps/site-docs"));
+        assert!(!glob_matches("apps/site-*", "apps/web"));
+        assert!(glob_matches("tools/cli", "tools/cli"));
+    }
+
+    #[test]
+    fn finds_pnpm_workspace_from_nested_dir() {
+        let (_dir, root) = root_with(&[
+            (
+                PNPM_WORKSPACE_FILE,
+                "packages:\n  - 'apps/*'\n  - \"packages/*\" # shared\n  - '!**/test/**'\n",
+            ),
+            ("package.json", r#"{"name": "mono"}"#),
+        ]);
+        let apps = root.join("apps");
+        fs::create_dir_all(&apps).unwrap();
+
+        let workspace = Workspace::find(&apps).unwrap();
+        assert_eq!(workspace.root, root);
+        assert_eq!(workspace.config, WorkspaceConfig::PnpmYaml);
+        assert_eq!(workspace.package_manager, Some(PackageManager::Pnpm));
+        assert_eq!(workspace.packages, ["apps/*", "packages/*", "!**/test/**"]);
+        assert!(workspace.includes(&root.join("apps/site")));
+        assert!(!workspace.includes(&root.join("sites/site")));
+        assert!(!workspace.includes(&root.join("packages/test")));
+    }
+
+    #[test]
+    fn package_json_workspaces_and_pins() {
+        let (_dir, root) = root_with(&[(
+            "package.json",
+            r#"{"name": "mono", "packageManager": "yarn@4.5.0", "workspaces": {"packages": ["apps/*"]}}"#,
+        )]);
+
+        let workspace = Workspace::find(&root.join("apps/web")).unwrap();
+        assert_eq!(workspace.config, WorkspaceConfig::PackageJson);
+        assert_eq!(workspace.package_manager, Some(PackageManager::Yarn));
+        assert!(workspace.includes(&root.join("apps/web")));
+        assert_eq!(
+            Workspace::find(&root.parent().unwrap().join("elsewhere")),
+            None
+        );
+    }
+
+    #[test]
+    fn registers_uncovered_members() {
+        let (_dir, root) = root_with(&[
+            (
+                PNPM_WORKSPACE_FILE,
+                "packages:\n  - \"packages/*\"\n\ncatalog:\n  react: ^19.0.0\n",
+            ),
+            ("package.json", r#"{"name": "mono"}"#),
+        ]);
+        let workspace = Workspace::at(&root).unwrap();
+
+        let mut vfs = VirtualFs::new(&root);
+        assert!(
+            !workspace
+                .stage_member(&root.join("packages/ui"), &mut vfs)
+                .unwrap()
+        );
+        assert!(vfs.is_empty());
+
+        assert!(
+            workspace
+                .stage_member(&root.join("sites/marketing"), &mut vfs)
+                .unwrap()
+        );
+        assert_eq!(
+            vfs.read(&root.join(PNPM_WORKSPACE_FILE)).unwrap(),
+            "packages:\n  - \"packages/*\"\n  - \"sites/marketing\"\n\ncatalog:\n  react: ^19.0.0\n"
+        );
+    }
+
+    #[test]
+    fn registers_members_in_package_json() {
+        let (_dir, root) = root_with(&[(
+            "package.json",
+            "{\n  \"name\": \"mono\",\n  \"private\": true,\n  \"workspaces\": [\"apps/*\"]\n}\n",
+        )]);
+        let workspace = Workspace::at(&root).unwrap();
+
+        let mut vfs = VirtualFs::new(&root);
+        assert!(
+            workspace
+                .stage_member(&root.join("site"), &mut vfs)
+                .unwrap()
+        );
+        assert_eq!(
+            vfs.read(&root.join("package.json")).unwrap(),
+            "{\n  \"name\": \"mono\",\n  \"private\": true,\n  \"workspaces\": [\n    \"apps/*\",\n    \"site\"\n  ]\n}\n"
+        );
+    }
+
+    #[test]
+    fn stages_nothing_without_a_member_list() {
+        let (_dir, root) = root_with(&[(
+            "package.json",
+            r#"{"name": "mono", "workspaces": ["apps/*"]}"#,
+        )]);
+        let workspace = Workspace::at(&root).unwrap();
+        // Changed since the workspace was read
+        fs::write(
+            root.join("package.json"),
+            r#"{"name": "mono", "workspaces": {"nohoist": ["**/react-native"]}}"#,
+        )
+        .unwrap();
+
+        let mut vfs = VirtualFs::new(&root);
+        assert!(
+            !workspace
+                .stage_member(&root.join("site"), &mut vfs)
+                .unwrap()
+        );
+        assert!(vfs.is_empty());
+    }
+
+    #[test]
+    fn adds_packages_key_when_missing() {
+        assert_eq!(
+            add_pnpm_package("catalog:\n  react: ^19.0.0", "apps/site"),
+            "catalog:\n  react: ^19.0.0\npackages:\n  - 'apps/site'\n"
+        );
+        assert_eq!(
+            add_pnpm_package("packages:\n", "apps/site"),
+            "packages:\n  - 'apps/site'\n"
+        );
+    }
+
+    #[test]
+    fn reads_flow_style_packages() {
+        assert_eq!(
+            pnpm_packages("packages: ['apps/*', \"packages/*\"]\n"),
+            ["apps/*", "packages/*"]
+        );
+        assert_eq!(
+            pnpm_packages("packages: [\n  apps/*,\n  packages/*,\n]\ncatalog:\n  react: ^19.0.0\n"),
+            ["apps/*", "packages/*"]
+        );
+        assert!(pnpm_packages("packages: []\n").is_empty());
+    }
+
+    #[test]
+    fn adds_to_flow_style_packages() {
+        assert_eq!(
+            add_pnpm_package(
+                "packages: [\"apps/*\"]\ncatalog:\n  react: ^19.0.0\n",
+                "sites/marketing"
+            ),
+            "packages: [\"apps/*\", \"sites/marketing\"]\ncatalog:\n  react: ^19.0.0\n"
+        );
+        assert_eq!(
+            add_pnpm_package("packages: [\n  'apps/*',\n]\n", "site"),
+            "packages: [\n  'apps/*', 'site',\n]\n"
+        );
+        assert_eq!(
+            add_pnpm_package("packages: []\n", "site"),
+            "packages: ['site']\n"
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! Conversion of names to kebab-case slugs.
+//!
+//! Used for TailwindPlus URL segments, section file names and pattern IDs,
+//! so all of them agree on where words break.
+
+/// Convert a name to a lowercase, hyphen-separated slug.
+///
+/// Words break at any character that is not an ASCII letter or digit, and
+/// at case changes. A run of capitals is one word, so `HTMLPage` becomes
+/// `html-page`, and a plural acronym such as `FAQs` stays `faqs`.
+#[must_use]
+pub fn slugify(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut slug = String::with_capacity(name.len());
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+            continue;
+        }
+
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let next = chars.get(i + 1).copied();
+        let after_next = chars.get(i + 2).copied();
+        let starts_word = c.is_ascii_uppercase()
+            && prev.is_some_and(|p| {
+                if p.is_ascii_lowercase() || p.is_ascii_digit() {
+                    true
+                } else if p.is_ascii_uppercase() {
+                    // Last capital of an acronym followed by a word, unless
+                    // the "word" is just a plural "s"
+                    let plural =
+                        next == Some('s') && !after_next.is_some_and(|a| a.is_ascii_lowercase());
+                    next.is_some_and(|n| n.is_ascii_lowercase()) && !plural
+                } else {
+                    false
+                }
+            });
+        if starts_word && !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        slug.push(c.to_ascii_lowercase());
+    }
+
+    slug.trim_end_matches('-').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify_words_and_separators() {
+        assert_eq!(slugify("Application UI"), "application-ui");
+        assert_eq!(slugify("Input Groups"), "input-groups");
+        assert_eq!(slugify("E-commerce"), "e-commerce");
+        assert_eq!(slugify("my_site v2"), "my-site-v2");
+        assert_eq!(slugify("  Headers & Footers "), "headers-footers");
+        assert_eq!(slugify("landing"), "landing");
+    }
+
+    #[test]
+    fn test_slugify_case_changes() {
+        assert_eq!(slugify("PricingPage"), "pricing-page");
+        assert_eq!(slugify("heroSplitImage"), "hero-split-image");
+        assert_eq!(slugify("Grid3Column"), "grid3-column");
+    }
+
+    #[test]
+    fn test_slugify_acronyms() {
+        assert_eq!(slugify("HTMLPage"), "html-page");
+        assert_eq!(slugify("CTASection"), "cta-section");
+        assert_eq!(slugify("SimpleCTA"), "simple-cta");
+        assert_eq!(slugify("FAQs"), "faqs");
+        assert_eq!(slugify("FAQsSection"), "faqs-section");
+        assert_eq!(slugify("APIStatus"), "api-status");
+    }
+}
@@ -0,0 +1,430 @@
+//! Bulk prefetching of component code into the local cache.
+//!
+//! Component code is normally fetched lazily, one subcategory page per
+//! missing snippet. A sync walks the catalog instead and plans one request
+//! per subcategory page and variant, since each page returns every
+//! component in it, but only in one framework and mode. Pacing and
+//! progress display are left to the caller; this module decides what to
+//! fetch and remembers what was done.
+//!
+//! Progress is recorded in `cache/sync-state.json` after every request, so
+//! an interrupted sync resumes where it stopped. The file is removed once a
+//! sync completes.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+
+use camino::Utf8PathBuf;
+use serde::{Deserialize, Serialize};
+
+use crate::cache;
+use crate::components::{ComponentReader, ComponentRecord, Framework, Mode};
+use crate::fetch::{ComponentFetcher, FetchError, subcategory_url};
+use crate::slug::slugify;
+
+/// File recording the progress of an interrupted sync.
+pub const SYNC_STATE_FILE: &str = "sync-state.json";
+
+/// One snippet a sync wants in the cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncItem {
+    /// Component UUID
+    pub uuid: String,
+    /// Framework variant
+    pub framework: Framework,
+    /// Theme mode
+    pub mode: Mode,
+}
+
+impl SyncItem {
+    /// Whether the snippet is already cached.
+    #[must_use]
+    pub fn is_cached(&self) -> bool {
+        cache::is_cached(&self.uuid, self.framework, self.mode)
+    }
+}
+
+/// A subcategory page and the snippets wanted from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncPage {
+    /// Top-level category (e.g., "Application UI")
+    pub category: String,
+    /// Subcategory (e.g., "Forms")
+    pub subcategory: String,
+    /// Sub-subcategory (e.g., "Input Groups")
+    pub sub_subcategory: String,
+    /// Snippets wanted from this page
+    pub items: Vec<SyncItem>,
+}
+
+impl SyncPage {
+    /// URL of the page.
+    #[must_use]
+    pub fn url(&self) -> String {
+        subcategory_url(&self.category, &self.subcategory, &self.sub_subcategory)
+    }
+
+    /// Human-readable path, e.g. "Application UI / Forms / Input Groups".
+    #[must_use]
+    pub fn label(&self) -> String {
+        format!(
+            "{} / {} / {}",
+            self.category, self.subcategory, self.sub_subcategory
+        )
+    }
+
+    /// Framework and mode combinations wanted from this page, in catalog
+    /// order. The page is requested once for each.
+    #[must_use]
+    pub fn variants(&self) -> Vec<(Framework, Mode)> {
+        let mut variants = Vec::new();
+        for item in &self.items {
+            if !variants.contains(&(item.framework, item.mode)) {
+                variants.push((item.framework, item.mode));
+            }
+        }
+        variants
+    }
+
+    /// Wanted snippets that are not cached yet.
+    #[must_use]
+    pub fn missing(&self) -> Vec<&SyncItem> {
+        self.items.iter().filter(|item| !item.is_cached()).collect()
+    }
+
+    /// Wanted snippets in `framework` and `mode` that are not cached yet.
+    #[must_use]
+    pub fn missing_in(&self, framework: Framework, mode: Mode) -> Vec<&SyncItem> {
+        self.items
+            .iter()
+            .filter(|item| item.framework == framework && item.mode == mode && !item.is_cached())
+            .collect()
+    }
+
+    /// Request the page in `framework` and `mode`, caching every snippet
+    /// it returns. Returns the number of snippets cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns the fetcher's error when the page cannot be fetched or a
+    /// snippet cannot be cached.
+    pub async fn fetch(
+        &self,
+        fetcher: &ComponentFetcher,
+        framework: Framework,
+        mode: Mode,
+    ) -> Result<usize, FetchError> {
+        let snippets = fetcher
+            .fetch_snippets(
+                &self.category,
+                &self.subcategory,
+                &self.sub_subcategory,
+                framework,
+                mode,
+            )
+            .await?;
+        Ok(snippets.len())
+    }
+}
+
+/// The subcategory pages a sync fetches, in catalog order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncPlan {
+    /// Pages to fetch
+    pub pages: Vec<SyncPage>,
+    /// Frameworks and modes the plan covers, e.g. "react,vue:light,dark"
+    pub scope: String,
+}
+
+impl SyncPlan {
+    /// Plan a sync of the bundled catalog.
+    ///
+    /// `categories` filters by category path (case-insensitive, slug or
+    /// name, e.g. "marketing" or "application-ui/forms"); empty syncs
+    /// everything.
+    #[must_use]
+    pub fn new(frameworks: &[Framework], modes: &[Mode], categories: &[String]) -> Self {
+        let reader = ComponentReader::new();
+        let records = frameworks
+            .iter()
+            .flat_map(|&framework| reader.all(framework).iter().map(move |r| (framework, r)));
+        Self::from_records(records, frameworks, modes, categories)
+    }
+
+    /// Plan a sync of `records`, keeping the modes each component has.
+    #[must_use]
+    pub fn from_records<'a>(
+        records: impl IntoIterator<Item = (Framework, &'a ComponentRecord)>,
+        frameworks: &[Framework],
+        modes: &[Mode],
+        categories: &[String],
+    ) -> Self {
+        let mut pages: BTreeMap<(String, String, String), SyncPage> = BTreeMap::new();
+        for (framework, record) in records {
+            if !categories.is_empty()
+                && !categories
+                    .iter()
+                    .any(|filter| matches_category(record, filter))
+            {
+                continue;
+            }
+            let page = pages
+                .entry((
+                    record.category.clone(),
+                    record.subcategory.clone(),
+                    record.sub_subcategory.clone(),
+                ))
+                .or_insert_with(|| SyncPage {
+                    category: record.category.clone(),
+                    subcategory: record.subcategory.clone(),
+                    sub_subcategory: record.sub_subcategory.clone(),
+                    items: vec![],
+                });
+            page.items.extend(
+                modes
+                    .iter()
+                    .filter(|&&mode| record.has_mode(mode))
+                    .map(|&mode| SyncItem {
+                        uuid: record.uuid.clone(),
+                        framework,
+                        mode,
+                    }),
+            );
+        }
+
+        let names = |items: Vec<&str>| items.join(",");
+        Self {
+            pages: pages
+                .into_values()
+                .filter(|page| !page.items.is_empty())
+                .collect(),
+            scope: format!(
+                "{}:{}",
+                names(frameworks.iter().map(Framework::as_str).collect()),
+                names(modes.iter().map(Mode::as_str).collect())
+            ),
+        }
+    }
+
+    /// Total snippets the plan wants.
+    #[must_use]
+    pub fn item_count(&self) -> usize {
+        self.pages.iter().map(|page| page.items.len()).sum()
+    }
+}
+
+/// Progress of a sync that has not finished yet.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncState {
+    /// Scope of the sync this progress belongs to (see [`SyncPlan::scope`])
+    pub scope: String,
+    /// Requests already made, as "<url> <framework>:<mode>"
+    pub completed: BTreeSet<String>,
+}
+
+impl SyncState {
+    /// Path of the state file.
+    #[must_use]
+    pub fn path() -> Option<Utf8PathBuf> {
+        cache::cache_dir().map(|dir| dir.join(SYNC_STATE_FILE))
+    }
+
+    /// Saved progress for `plan`, or a fresh state when there is none or
+    /// it belongs to a sync with another scope.
+    #[must_use]
+    pub fn load(plan: &SyncPlan) -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str::<Self>(&json).ok())
+            .filter(|state| state.scope == plan.scope)
+            .unwrap_or_else(|| Self {
+                scope: plan.scope.clone(),
+                completed: BTreeSet::new(),
+            })
+    }
+
+    /// Whether `page` was fetched in `framework` and `mode` earlier in
+    /// this sync.
+    #[must_use]
+    pub fn is_done(&self, page: &SyncPage, framework: Framework, mode: Mode) -> bool {
+        self.completed.contains(&state_key(page, framework, mode))
+    }
+
+    /// Record `page` as fetched in `framework` and `mode`, and save.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state file cannot be written.
+    pub fn complete(
+        &mut self,
+        page: &SyncPage,
+        framework: Framework,
+        mode: Mode,
+    ) -> std::io::Result<()> {
+        self.completed.insert(state_key(page, framework, mode));
+        let Some(path) = Self::path() else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        fs::write(path, json)
+    }
+
+    /// Remove saved progress once a sync finishes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state file exists but cannot be removed.
+    pub fn clear() -> std::io::Result<()> {
+        match Self::path() {
+            Some(path) if path.exists() => fs::remove_file(path),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Key recording one request of a sync in [`SyncState::completed`].
+fn state_key(page: &SyncPage, framework: Framework, mode: Mode) -> String {
+    format!("{} {framework}:{mode}", page.url())
+}
+
+/// Whether `record` falls under a category filter like "marketing" or
+/// "Application UI/Forms", compared as the slugs in page URLs.
+fn matches_category(record: &ComponentRecord, filter: &str) -> bool {
+    let wanted: Vec<String> = filter
+        .split('/')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(slugify)
+        .collect();
+    let path = [
+        &record.category,
+        &record.subcategory,
+        &record.sub_subcategory,
+    ];
+    !wanted.is_empty()
+        && wanted.len() <= path.len()
+        && wanted
+            .iter()
+            .zip(path)
+            .all(|(want, part)| *want == slugify(part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(uuid: &str, path: [&str; 3], dark: bool) -> ComponentRecord {
+        serde_json::from_value(serde_json::json!({
+            "id": uuid,
+            "uuid": uuid,
+            "name": uuid,
+            "category": path[0],
+            "subcategory": path[1],
+            "sub_subcategory": path[2],
+            "has_light": true,
+            "has_dark": dark,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn plan_groups_components_by_page() {
+        let records = [
+            record("a", ["Application UI", "Forms", "Input Groups"], true),
+            record("b", ["Application UI", "Forms", "Input Groups"], false),
+            record("c", ["Marketing", "Sections", "Heroes"], true),
+        ];
+        let plan = SyncPlan::from_records(
+            records
+                .iter()
+                .flat_map(|r| [(Framework::React, r), (Framework::Vue, r)]),
+            &[Framework::React, Framework::Vue],
+            &[Mode::Light, Mode::Dark],
+            &[],
+        );
+
+        assert_eq!(plan.scope, "react,vue:light,dark");
+        assert_eq!(plan.pages.len(), 2);
+        let forms = &plan.pages[0];
+        assert_eq!(forms.label(), "Application UI / Forms / Input Groups");
+        assert!(
+            forms
+                .url()
+                .ends_with("/ui-blocks/application-ui/forms/input-groups")
+        );
+        assert_eq!(
+            forms.variants(),
+            [
+                (Framework::React, Mode::Light),
+                (Framework::React, Mode::Dark),
+                (Framework::Vue, Mode::Light),
+                (Framework::Vue, Mode::Dark),
+            ]
+        );
+        // a: light + dark, b: light only, for both frameworks
+        assert_eq!(forms.items.len(), 6);
+        assert_eq!(plan.item_count(), 10);
+    }
+
+    #[test]
+    fn plan_filters_by_category_path() {
+        let records = [
+            record("a", ["Application UI", "Forms", "Input Groups"], true),
+            record("b", ["Application UI", "Lists", "Tables"], true),
+            record("c", ["Marketing", "Sections", "Heroes"], true),
+            record("d", ["Marketing", "Page Sections", "FAQs"], true),
+        ];
+        let plan = |filters: &[&str]| {
+            let filters: Vec<String> = filters.iter().map(ToString::to_string).collect();
+            SyncPlan::from_records(
+                records.iter().map(|r| (Framework::Html, r)),
+                &[Framework::Html],
+                &[Mode::Light],
+                &filters,
+            )
+            .pages
+            .into_iter()
+            .map(|page| page.sub_subcategory)
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(plan(&["application-ui"]), ["Input Groups", "Tables"]);
+        assert_eq!(plan(&["Application UI/Forms"]), ["Input Groups"]);
+        assert_eq!(
+            plan(&["marketing", "application-ui/lists"]),
+            ["Tables", "FAQs", "Heroes"]
+        );
+        assert_eq!(plan(&["marketing/page-sections/faqs"]), ["FAQs"]);
+        assert!(plan(&["ecommerce"]).is_empty());
+    }
+
+    #[test]
+    fn state_tracks_completed_requests() {
+        let records = [record("a", ["Marketing", "Sections", "Heroes"], false)];
+        let plan = SyncPlan::from_records(
+            records.iter().map(|r| (Framework::React, r)),
+            &[Framework::React],
+            &[Mode::Light],
+            &[],
+        );
+        let page = &plan.pages[0];
+
+        let mut state = SyncState {
+            scope: plan.scope.clone(),
+            completed: BTreeSet::new(),
+        };
+        assert!(!state.is_done(page, Framework::React, Mode::Light));
+        state
+            .completed
+            .insert(state_key(page, Framework::React, Mode::Light));
+        assert!(state.is_done(page, Framework::React, Mode::Light));
+        assert!(!state.is_done(page, Framework::Vue, Mode::Light));
+        assert!(!state.is_done(page, Framework::React, Mode::Dark));
+
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(serde_json::from_str::<SyncState>(&json).unwrap(), state);
+    }
+}
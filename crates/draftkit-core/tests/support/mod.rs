@@ -0,0 +1,224 @@
+//! Local stand-in for TailwindPlus, serving responses from `tests/fixtures/tailwindplus`.
+//!
+//! Routes:
+//! - `GET /` serves `index.html` and sets `XSRF-TOKEN` and `laravel_session`
+//!   cookies
+//! - `GET /ui-blocks/...` serves `ui-blocks/....<language>.json`, or
+//!   `ui-blocks/....json` when the page has no per-language fixtures, as an
+//!   Inertia response, answering 409 unless the request carries the
+//!   fixture's Inertia version
+//! - `PUT /ui-blocks/{language,version,mode}` accepts preference changes,
+//!   remembering the language (initially `html`)
+//! - `GET /previews/*.png` serves preview images
+//!
+//! Responses queued with [`StandIn::queue`] are served first, e.g. to
+//! simulate rate limiting.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Inertia version of the fixture site
+pub const INERTIA_VERSION: &str = "fixture-1";
+
+/// Lifetime the stand-in gives the session cookie, in seconds
+pub const SESSION_MAX_AGE: i64 = 7200;
+
+/// A running stand-in server. It stops with the test process.
+pub struct StandIn {
+    /// Base URL, e.g. `http://127.0.0.1:40123`
+    pub base_url: String,
+    state: Arc<State>,
+}
+
+/// What the stand-in remembers between requests.
+struct State {
+    requests: Mutex<Vec<String>>,
+    language: Mutex<String>,
+    queued: Mutex<Vec<(String, Response)>>,
+}
+
+impl StandIn {
+    /// Start a server on a free local port.
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(State {
+            requests: Mutex::new(Vec::new()),
+            language: Mutex::new("html".to_string()),
+            queued: Mutex::new(Vec::new()),
+        });
+        let shared = Arc::clone(&state);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let state = Arc::clone(&shared);
+                thread::spawn(move || serve(stream, &state));
+            }
+        });
+        Self { base_url, state }
+    }
+
+    /// URL of `path` on the server.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url)
+    }
+
+    /// Requests received so far, as "METHOD /path".
+    pub fn requests(&self) -> Vec<String> {
+        self.state.requests.lock().unwrap().clone()
+    }
+
+    /// Answer the next request for `path` with an empty `status` response
+    /// carrying `headers`, instead of routing it. Queued responses for the
+    /// same path are served in order.
+    pub fn queue(&self, path: &str, status: u16, headers: &[(&'static str, &str)]) {
+        let response = headers.iter().fold(
+            Response::new(status, "text/plain", Vec::new()),
+            |response, (name, value)| response.with_header(name, value),
+        );
+        self.state
+            .queued
+            .lock()
+            .unwrap()
+            .push((path.to_string(), response));
+    }
+
+    /// The account's snippet language preference.
+    pub fn language(&self) -> String {
+        self.state.language.lock().unwrap().clone()
+    }
+}
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tailwindplus")
+}
+
+fn serve(stream: TcpStream, state: &State) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+        return;
+    }
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_lowercase(), value.trim().to_string()));
+        }
+    }
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    };
+    let length = header("content-length").map_or(0, |v| v.parse().unwrap_or(0));
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).unwrap();
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+    state
+        .requests
+        .lock()
+        .unwrap()
+        .push(format!("{method} {path}"));
+
+    let mut queued = state.queued.lock().unwrap();
+    let next = queued.iter().position(|(queued, _)| *queued == path);
+    let response = next.map(|index| queued.remove(index).1);
+    drop(queued);
+    if let Some(response) = response {
+        response.write(stream);
+        return;
+    }
+
+    let fixtures = fixtures_dir();
+    let response = match (method.as_str(), path.as_str()) {
+        ("GET", "/") => Response::file(&fixtures.join("index.html"), "text/html")
+            .with_header("set-cookie", "XSRF-TOKEN=stand-in%3D; path=/")
+            .with_header(
+                "set-cookie",
+                &format!("laravel_session=refreshed; Max-Age={SESSION_MAX_AGE}; path=/; httponly"),
+            ),
+        ("PUT", "/ui-blocks/language" | "/ui-blocks/version" | "/ui-blocks/mode") => {
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap_or_default();
+            if let Some(language) = body["snippet_lang"].as_str() {
+                *state.language.lock().unwrap() = language.to_string();
+            }
+            Response::new(200, "application/json", b"{}".to_vec())
+        }
+        ("GET", page) if page.starts_with("/ui-blocks/") => {
+            if header("x-inertia-version") == Some(INERTIA_VERSION) {
+                let page = fixtures.join(page.trim_start_matches('/'));
+                let language = state.language.lock().unwrap().clone();
+                let localized = page.with_extension(format!("{language}.json"));
+                let file = if localized.exists() {
+                    localized
+                } else {
+                    page.with_extension("json")
+                };
+                Response::file(&file, "application/json")
+            } else {
+                Response::new(409, "text/plain", Vec::new())
+            }
+        }
+        ("GET", image) if image.starts_with("/previews/") => {
+            Response::file(&fixtures.join(image.trim_start_matches('/')), "image/png")
+        }
+        _ => Response::not_found(),
+    };
+    response.write(stream);
+}
+
+struct Response {
+    status: u16,
+    headers: Vec<(&'static str, String)>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn new(status: u16, content_type: &str, body: Vec<u8>) -> Self {
+        Self {
+            status,
+            headers: vec![("content-type", content_type.to_string())],
+            body,
+        }
+    }
+
+    fn file(path: &Path, content_type: &str) -> Self {
+        std::fs::read(path).map_or_else(
+            |_| Self::not_found(),
+            |body| Self::new(200, content_type, body),
+        )
+    }
+
+    fn not_found() -> Self {
+        Self::new(404, "text/plain", b"Not found".to_vec())
+    }
+
+    fn with_header(mut self, name: &'static str, value: &str) -> Self {
+        self.headers.push((name, value.to_string()));
+        self
+    }
+
+    fn write(self, mut stream: TcpStream) {
+        let mut head = format!(
+            "HTTP/1.1 {} Stand-in\r\ncontent-length: {}\r\nconnection: close\r\n",
+            self.status,
+            self.body.len()
+        );
+        for (name, value) in self.headers {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+        head.push_str("\r\n");
+        let _ = stream.write_all(head.as_bytes());
+        let _ = stream.write_all(&self.body);
+    }
+}
@@ -0,0 +1,339 @@
+//! Fetching, cache population and composite previews against a local
+//! stand-in for TailwindPlus.
+
+mod support;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use draftkit_core::fetch::{ComponentFetcher, FetchError, RetryPolicy};
+use draftkit_core::http::HttpClient;
+use draftkit_core::preview::{CompositePreview, PreviewSource};
+use draftkit_core::sync::SyncPlan;
+use draftkit_core::{ComponentRecord, Framework, Mode, cache};
+use futures::future::BoxFuture;
+use support::StandIn;
+use tempfile::TempDir;
+
+const HEROES: [&str; 3] = ["Marketing", "Sections", "Heroes"];
+const HEROES_PAGE: &str = "GET /ui-blocks/marketing/sections/heroes";
+
+/// A cache directory of its own for one test.
+fn temp_cache_dir() -> (TempDir, Utf8PathBuf) {
+    let dir = TempDir::new().unwrap();
+    let path = Utf8PathBuf::try_from(dir.path().to_path_buf()).unwrap();
+    (dir, path)
+}
+
+fn fetcher(server: &StandIn, cache_dir: &Utf8Path) -> ComponentFetcher {
+    ComponentFetcher::new("session".into())
+        .with_base_url(&server.base_url)
+        .with_cache_dir(cache_dir)
+        .with_retry_policy(RetryPolicy {
+            max_retries: 1,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_secs(1),
+        })
+}
+
+/// Counts requests passed on to a `reqwest` client.
+#[derive(Default)]
+struct CountingClient {
+    inner: reqwest::Client,
+    count: AtomicUsize,
+}
+
+impl HttpClient for CountingClient {
+    fn execute(
+        &self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'_, reqwest::Result<reqwest::Response>> {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        Box::pin(self.inner.execute(request))
+    }
+}
+
+#[tokio::test]
+async fn fetch_component_caches_the_whole_page() {
+    let (_dir, cache_dir) = temp_cache_dir();
+    let server = StandIn::start();
+    let fetcher = fetcher(&server, &cache_dir);
+    let [category, subcategory, sub_subcategory] = HEROES;
+
+    // No init: the stand-in answers 409 until the fetcher reads the version
+    let code = fetcher
+        .fetch_component(
+            "hero-1",
+            category,
+            subcategory,
+            sub_subcategory,
+            Framework::Html,
+            Mode::Light,
+        )
+        .await
+        .unwrap();
+    assert!(code.contains("Simple centered"));
+    assert_eq!(server.requests(), [HEROES_PAGE, "GET /", HEROES_PAGE]);
+
+    // The sibling came with the same page
+    let sibling = cache::get_cached_in(&cache_dir, "hero-2", Framework::Html, Mode::Light).unwrap();
+    assert!(sibling.contains("Split with screenshot"));
+    let code = fetcher
+        .fetch_component(
+            "hero-2",
+            category,
+            subcategory,
+            sub_subcategory,
+            Framework::Html,
+            Mode::Light,
+        )
+        .await
+        .unwrap();
+    assert_eq!(code, sibling);
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn fetch_snippets_negotiates_framework_and_memoizes() {
+    let (_dir, cache_dir) = temp_cache_dir();
+    let server = StandIn::start();
+    let fetcher = fetcher(&server, &cache_dir);
+    fetcher.init().await.unwrap();
+    let [category, subcategory, sub_subcategory] = HEROES;
+
+    // The fixture only has HTML, so asking for React switches the language
+    let snippets = fetcher
+        .fetch_snippets(
+            category,
+            subcategory,
+            sub_subcategory,
+            Framework::React,
+            Mode::Light,
+        )
+        .await
+        .unwrap();
+    assert_eq!(snippets.len(), 2);
+    assert!(snippets.iter().all(|s| s.framework == Framework::Html));
+    // The language is switched back once the page is fetched
+    assert_eq!(
+        server.requests(),
+        [
+            "GET /",
+            HEROES_PAGE,
+            "PUT /ui-blocks/language",
+            HEROES_PAGE,
+            "PUT /ui-blocks/language"
+        ]
+    );
+    assert_eq!(server.language(), "html");
+
+    fetcher
+        .fetch_snippets(
+            category,
+            subcategory,
+            sub_subcategory,
+            Framework::React,
+            Mode::Light,
+        )
+        .await
+        .unwrap();
+    assert_eq!(server.requests().len(), 5);
+}
+
+#[tokio::test]
+async fn retries_server_errors_and_rate_limits() {
+    let (_dir, cache_dir) = temp_cache_dir();
+    let server = StandIn::start();
+    let fetcher = fetcher(&server, &cache_dir).with_retry_policy(RetryPolicy {
+        max_retries: 2,
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_secs(1),
+    });
+    fetcher.init().await.unwrap();
+    let [category, subcategory, sub_subcategory] = HEROES;
+    let page = HEROES_PAGE.trim_start_matches("GET ");
+    server.queue(page, 503, &[]);
+    server.queue(page, 429, &[("retry-after", "0")]);
+
+    let snippets = fetcher
+        .fetch_snippets(
+            category,
+            subcategory,
+            sub_subcategory,
+            Framework::Html,
+            Mode::Light,
+        )
+        .await
+        .unwrap();
+    assert_eq!(snippets.len(), 2);
+    assert_eq!(
+        server.requests(),
+        ["GET /", HEROES_PAGE, HEROES_PAGE, HEROES_PAGE]
+    );
+}
+
+#[tokio::test]
+async fn classifies_failures_and_fails_fast_on_long_rate_limits() {
+    let (_dir, cache_dir) = temp_cache_dir();
+    let server = StandIn::start();
+    let fetcher = fetcher(&server, &cache_dir).with_retry_policy(RetryPolicy {
+        max_retries: 1,
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(10),
+    });
+    fetcher.init().await.unwrap();
+    let [category, subcategory, sub_subcategory] = HEROES;
+    let page = HEROES_PAGE.trim_start_matches("GET ");
+    let fetch = |sub_subcategory| {
+        fetcher.fetch_snippets(
+            category,
+            subcategory,
+            sub_subcategory,
+            Framework::Html,
+            Mode::Light,
+        )
+    };
+
+    server.queue(page, 419, &[]);
+    assert!(matches!(
+        fetch(sub_subcategory).await,
+        Err(FetchError::SessionExpired)
+    ));
+
+    // A Retry-After longer than max_delay fails at once instead of
+    // retrying before the server allows it
+    server.queue(page, 429, &[("retry-after", "3600")]);
+    let started = std::time::Instant::now();
+    let error = fetch(sub_subcategory).await.unwrap_err();
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(matches!(
+        error,
+        FetchError::RateLimited {
+            retry_after: Some(wait)
+        } if wait == Duration::from_secs(3600)
+    ));
+    assert!(error.to_string().contains("retry after 3600s"));
+
+    let error = fetch("Missing").await.unwrap_err();
+    assert!(matches!(error, FetchError::Http(_)));
+    assert!(!error.is_retryable());
+    assert_eq!(server.requests().len(), 4);
+}
+
+#[tokio::test]
+async fn negotiation_restores_preferences_when_the_refetch_fails() {
+    let (_dir, cache_dir) = temp_cache_dir();
+    let server = StandIn::start();
+    let fetcher = fetcher(&server, &cache_dir);
+    fetcher.init().await.unwrap();
+
+    // The React variant of this page is malformed
+    let error = fetcher
+        .fetch_snippets(
+            "Marketing",
+            "Sections",
+            "Pricing",
+            Framework::React,
+            Mode::Light,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(error, FetchError::Parse(_)));
+    let pricing = "GET /ui-blocks/marketing/sections/pricing";
+    assert_eq!(
+        server.requests(),
+        [
+            "GET /",
+            pricing,
+            "PUT /ui-blocks/language",
+            pricing,
+            "PUT /ui-blocks/language"
+        ]
+    );
+    assert_eq!(server.language(), "html");
+}
+
+#[tokio::test]
+async fn sync_requests_a_page_once_per_framework() {
+    let (_dir, cache_dir) = temp_cache_dir();
+    let server = StandIn::start();
+    let fetcher = fetcher(&server, &cache_dir);
+    fetcher.init().await.unwrap();
+    let record: ComponentRecord = serde_json::from_value(serde_json::json!({
+        "id": "cta-simple-centered",
+        "uuid": "cta-1",
+        "name": "Simple centered",
+        "category": "Marketing",
+        "subcategory": "Sections",
+        "sub_subcategory": "CTA Sections",
+        "has_light": true,
+        "has_dark": false,
+    }))
+    .unwrap();
+    let plan = SyncPlan::from_records(
+        [(Framework::React, &record), (Framework::Vue, &record)],
+        &[Framework::React, Framework::Vue],
+        &[Mode::Light],
+        &[],
+    );
+    let page = &plan.pages[0];
+
+    for (framework, mode) in page.variants() {
+        assert_eq!(page.fetch(&fetcher, framework, mode).await.unwrap(), 1);
+    }
+    assert_eq!(server.language(), "html");
+    let react = cache::get_cached_in(&cache_dir, "cta-1", Framework::React, Mode::Light).unwrap();
+    assert!(react.contains("className=\"bg-indigo-600\""));
+    let vue = cache::get_cached_in(&cache_dir, "cta-1", Framework::Vue, Mode::Light).unwrap();
+    assert!(vue.starts_with("<template>"));
+}
+
+#[tokio::test]
+async fn check_session_reports_sign_in_and_expiry() {
+    let server = StandIn::start();
+    let before = unix_now();
+
+    let (_dir, cache_dir) = temp_cache_dir();
+
+    let check = fetcher(&server, &cache_dir).check_session().await.unwrap();
+    assert_eq!(check.signed_in, Some(true));
+    let expires_at = check.expires_at.unwrap();
+    assert!(expires_at >= before + support::SESSION_MAX_AGE);
+    assert!(expires_at <= unix_now() + support::SESSION_MAX_AGE);
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        .cast_signed()
+}
+
+#[tokio::test]
+async fn composite_preview_stitches_and_caches_images() {
+    let (_dir, cache_dir) = temp_cache_dir();
+    let server = StandIn::start();
+    let client = Arc::new(CountingClient::default());
+    let preview = CompositePreview::new()
+        .with_cache_dir(&cache_dir)
+        .with_http_client(Arc::clone(&client));
+    let sources =
+        [("hero-1", "Simple centered"), ("hero-2", "Split")].map(|(id, name)| PreviewSource {
+            component_id: format!("stand-in-{id}"),
+            preview_url: server.url(&format!("/previews/{id}-light.png")),
+            name: name.to_string(),
+        });
+
+    let image = preview.generate(&sources, Mode::Light).await.unwrap();
+    assert_eq!((image.width, image.height), (3, 3));
+    assert!(image.data.starts_with(b"\x89PNG"));
+    assert_eq!(client.count.load(Ordering::SeqCst), 2);
+
+    // Served from the preview cache the second time
+    let again = preview.generate(&sources, Mode::Light).await.unwrap();
+    assert_eq!(again.data, image.data);
+    assert_eq!(client.count.load(Ordering::SeqCst), 2);
+}
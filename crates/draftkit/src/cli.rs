@@ -7,6 +7,7 @@
 #![allow(clippy::missing_const_for_fn)]
 
 use console::{Style, Term};
+use draftkit_core::scaffold::{ChangeKind, WritePlan};
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
 use std::time::Duration;
 
@@ -155,6 +156,51 @@ impl Styler {
         println!("{styled_key} {value}");
     }
 
+    /// Print a unified diff with added and removed lines colored.
+    pub fn print_diff(&self, diff: &str) {
+        for line in diff.lines() {
+            if line.starts_with("+++") || line.starts_with("---") {
+                println!("{}", self.bold().apply_to(line));
+            } else if line.starts_with('+') {
+                println!("{}", self.success().apply_to(line));
+            } else if line.starts_with('-') {
+                println!("{}", self.error().apply_to(line));
+            } else if line.starts_with("@@") {
+                println!("{}", self.info().apply_to(line));
+            } else {
+                println!("{line}");
+            }
+        }
+    }
+
+    /// Print a dry-run plan: the file tree, a diff per changed file, and
+    /// the commands that would run.
+    pub fn print_plan(&self, tree: &str, plan: &WritePlan) {
+        self.print_header("Dry run");
+        println!();
+        print!("{tree}");
+
+        for change in &plan.files {
+            if !change.diff.is_empty() {
+                println!();
+                self.print_diff(&change.diff);
+            }
+        }
+
+        let count = |kind| plan.files.iter().filter(|c| c.kind == kind).count();
+        println!();
+        self.print_info(&format!(
+            "{} to create, {} to modify, {} unchanged",
+            count(ChangeKind::Create),
+            count(ChangeKind::Modify),
+            count(ChangeKind::Unchanged)
+        ));
+        for command in &plan.commands {
+            self.print_info(&format!("Would run: {}", command.join(" ")));
+        }
+        self.print_info("No files were written");
+    }
+
     // ─────────────────────────────────────────────────────────────
     // Progress Indicators
     // ─────────────────────────────────────────────────────────────
@@ -0,0 +1,216 @@
+//! Add command implementation
+//!
+//! Adds a generated page to an existing project without touching the
+//! user's files.
+//!
+//! ```bash
+//! draftkit add pricing --pattern saas-landing --dry-run
+//! draftkit adopt about --pattern saas-landing --route /company/about
+//! ```
+//!
+//! The page and its sections are written to new files only, and only the
+//! packages the project is missing are installed.
+
+use anyhow::{Context, Result, bail};
+use camino::Utf8PathBuf;
+use clap::Args;
+use draftkit_core::{
+    Config, GenerateLock, GenerateOptions, Mode, ProjectInspection, TailwindVersion, VirtualFs,
+    scaffold::{IconLibrary, MissingPackage, route_for_page},
+};
+
+use super::generate::{
+    generate_pattern_page, icon_options, install_commands, install_packages, lock_entry,
+    parse_mode, pattern_generator, pattern_recipe, print_placeholder_notice,
+    print_tailwind_conflicts, print_unmapped_icons, record_lock, report_dry_run,
+};
+use crate::cli::Styler;
+
+#[derive(Args)]
+pub struct AddArgs {
+    /// Page name (e.g., "pricing", "about")
+    pub page_name: String,
+
+    /// Generate from pattern
+    #[arg(long, short)]
+    pub pattern: String,
+
+    /// Route for the new page (defaults to /<page-name>)
+    #[arg(long)]
+    pub route: Option<String>,
+
+    /// Apply preset aesthetic
+    #[arg(long)]
+    pub preset: Option<String>,
+
+    /// Theme mode
+    #[arg(long, short, default_value = "light", value_parser = parse_mode)]
+    pub mode: Mode,
+
+    /// Rewrite Heroicons to another icon library (heroicons, lucide, phosphor, tabler)
+    #[arg(long, value_name = "LIBRARY")]
+    pub icons: Option<IconLibrary>,
+
+    /// Use components that need Tailwind v4 even in a Tailwind v3 project
+    #[arg(long)]
+    pub allow_v4_only: bool,
+
+    /// Show the files and packages that would be added without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Skip installing missing packages
+    #[arg(long)]
+    pub no_install: bool,
+
+    /// Describe the planned writes as JSON
+    #[arg(long, requires = "dry_run")]
+    pub json: bool,
+}
+
+/// Add a generated page to an existing project
+pub async fn cmd_add(args: AddArgs, app_config: &Config, styler: &Styler) -> Result<()> {
+    let cwd = std::env::current_dir().context("Failed to get current directory")?;
+    let project_path = Utf8PathBuf::try_from(cwd).context("Path is not valid UTF-8")?;
+
+    let inspection = ProjectInspection::inspect(&project_path)?;
+    let config = inspection.project_config();
+    let route = args
+        .route
+        .clone()
+        .unwrap_or_else(|| route_for_page(&args.page_name));
+
+    if !args.json {
+        print_inspection(&inspection, &route, styler);
+    }
+
+    let mut options = GenerateOptions::with_pattern(&args.pattern)
+        .with_mode(args.mode)
+        .with_split_sections()
+        .with_sections_dir(inspection.sections_dir(&route));
+    options.output_path = Some(inspection.page_path(&route));
+    options.preset = args.preset.clone();
+    options.import_alias = inspection.import_alias.clone();
+    options.icons = icon_options(app_config, args.preset.as_deref(), args.icons)?;
+    options.allow_v4_only = args.allow_v4_only;
+
+    let generator = pattern_generator(&config)?;
+    let recipe = pattern_recipe(&args.pattern, styler)?;
+    if !args.json {
+        print_tailwind_conflicts(&generator, &recipe, &config, &options, styler);
+        print_placeholder_notice(&generator, styler);
+    }
+    let page = generate_pattern_page(&generator, &recipe, &config, &options).await?;
+
+    // Adoption only ever creates files
+    let existing = page.existing_files();
+    if !existing.is_empty() {
+        for path in &existing {
+            let relative = path.strip_prefix(&config.path).unwrap_or(path);
+            println!("  {} {relative}", styler.dim().apply_to("exists"));
+        }
+        bail!("Refusing to overwrite existing files. Choose another --route or page name.");
+    }
+
+    let mut missing = inspection.missing_packages();
+    for package in inspection.missing_dependencies(&page.dependencies) {
+        if !missing.iter().any(|m| m.name == package.name) {
+            missing.push(package);
+        }
+    }
+
+    if args.dry_run {
+        let mut vfs = VirtualFs::new(&config.path);
+        generator.stage_page(&page, &mut vfs);
+        let mut lock = GenerateLock::load(&config.path)?;
+        lock.record(&args.page_name, lock_entry(&config, &page, &options), None);
+        lock.stage(&config.path, &mut vfs)?;
+        let commands = if args.no_install {
+            vec![]
+        } else {
+            install_commands(&inspection, &missing)
+        };
+        return report_dry_run(&vfs, commands, args.json, styler);
+    }
+
+    generator.write_page(&page)?;
+    for section in &page.sections {
+        let relative = section
+            .path
+            .strip_prefix(&config.path)
+            .unwrap_or(&section.path);
+        println!("  {} {relative}", styler.dim().apply_to("created"));
+    }
+    let relative = page.path.strip_prefix(&config.path).unwrap_or(&page.path);
+    println!("  {} {relative}", styler.dim().apply_to("created"));
+
+    let mut lock = GenerateLock::load(&config.path)?;
+    record_lock(&mut lock, &args.page_name, &config, &page, &options, None)?;
+
+    print_unmapped_icons(&page.unmapped_icons, styler);
+    if args.no_install {
+        print_missing_packages(&missing, styler);
+    } else {
+        install_packages(&inspection, &missing, styler)?;
+    }
+
+    let hints: Vec<String> = inspection
+        .route_hint(&route, &page.path)
+        .into_iter()
+        .chain(inspection.setup_hints())
+        .collect();
+    if !hints.is_empty() {
+        println!();
+        styler.print_info("Next steps:");
+        for hint in hints {
+            println!("  - {hint}");
+        }
+    }
+
+    println!();
+    styler.print_success(&format!("Page '{}' added at {route}", args.page_name));
+
+    Ok(())
+}
+
+/// Print what inspection found
+fn print_inspection(inspection: &ProjectInspection, route: &str, styler: &Styler) {
+    styler.print_header("Inspecting project");
+    println!();
+    const KV_WIDTH: usize = 12;
+    styler.print_kv("Project", &inspection.name, KV_WIDTH);
+    styler.print_kv("Framework", inspection.framework.as_str(), KV_WIDTH);
+    styler.print_kv(
+        "Packages",
+        inspection.package_manager.to_string().as_str(),
+        KV_WIDTH,
+    );
+    let tailwind = match inspection.tailwind {
+        Some(TailwindVersion::V3) => "v3",
+        Some(TailwindVersion::V4) => "v4",
+        None => "not installed",
+    };
+    styler.print_kv("Tailwind", tailwind, KV_WIDTH);
+    if let Some(ref alias) = inspection.import_alias {
+        styler.print_kv("Alias", &alias.prefix, KV_WIDTH);
+    }
+    if let Some(ref workspace) = inspection.workspace {
+        styler.print_kv("Workspace", workspace.root.as_str(), KV_WIDTH);
+    }
+    styler.print_kv("Route", route, KV_WIDTH);
+    println!();
+}
+
+/// List packages that still need to be installed
+fn print_missing_packages(missing: &[MissingPackage], styler: &Styler) {
+    if missing.is_empty() {
+        return;
+    }
+    println!();
+    styler.print_warning("Missing packages:");
+    for package in missing {
+        let kind = if package.dev { " (dev)" } else { "" };
+        println!("  - {}{kind}", package.spec());
+    }
+    println!();
+}
@@ -3,20 +3,34 @@
 //! Uses Playwright to open a browser for TailwindPlus login, then captures
 //! the session cookie for use when fetching components. This approach means
 //! we never handle user credentials directly - they enter them in the browser.
+//! The session is kept in the OS keyring or an encrypted file; see
+//! [`crate::session`].
+//!
+//! For CI and other headless machines, the cookie can instead be piped in
+//! with `--cookie-stdin`, imported from a browser cookie export with
+//! `--cookie-file`, or passed per run in `DRAFTKIT_SESSION`. Every new
+//! session is checked against TailwindPlus before it is stored, which also
+//! records when it really expires.
+//!
+//! Sessions belong to the active [profile](draftkit_core::profile); `--list`
+//! shows every profile and the state of its session.
 
-use std::fs;
-use std::io::{self, Write as _};
+use std::io::{self, Read as _, Write as _};
+use std::path::PathBuf;
 use std::time::Duration;
 
 use anyhow::{Context, Result, bail};
 use clap::Args;
-use draftkit_core::data_dir::data_dir;
+use draftkit_core::Config;
+use draftkit_core::fetch::ComponentFetcher;
+use draftkit_core::profile::{self, DEFAULT_PROFILE, active_profile};
 use playwright_rs::Playwright;
 use serde::{Deserialize, Serialize};
 
 use crate::cli::Styler;
+use crate::session::{Backend, SessionStorage};
 
-/// Session file name within data directory
+/// Plaintext session file written by earlier versions, migrated on first use
 const SESSION_FILE: &str = "session.json";
 
 /// TailwindPlus login URL
@@ -32,7 +46,13 @@ const AUTH_SUCCESS_PATTERNS: &[&str] = &[
 /// Laravel session cookie name
 const SESSION_COOKIE_NAME: &str = "laravel_session";
 
-/// Session data stored in keyring
+/// Domain TailwindPlus sets its session cookie for
+const SESSION_DOMAIN: &str = "tailwindcss.com";
+
+/// Environment variable with a session cookie, used instead of the stored session
+pub const SESSION_ENV: &str = "DRAFTKIT_SESSION";
+
+/// A TailwindPlus login session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
     /// The Laravel session cookie value
@@ -73,6 +93,15 @@ impl Session {
             false
         }
     }
+
+    /// Expiry as a UTC date and time, if known.
+    pub fn expires_display(&self) -> Option<String> {
+        self.expires_at.map(|expires| {
+            chrono::DateTime::from_timestamp(expires, 0)
+                .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+                .unwrap_or_else(|| "unknown".into())
+        })
+    }
 }
 
 #[derive(Args)]
@@ -85,53 +114,187 @@ pub struct AuthArgs {
     #[arg(long)]
     pub status: bool,
 
+    /// With --status, check the session against TailwindPlus and record its expiry
+    #[arg(long, requires = "status")]
+    pub verify: bool,
+
     /// Force re-authentication even if session exists
     #[arg(long)]
     pub refresh: bool,
+
+    /// Read the session cookie from stdin instead of opening a browser
+    #[arg(long, conflicts_with = "cookie_file")]
+    pub cookie_stdin: bool,
+
+    /// Import the session from a browser cookie export (Netscape cookies.txt or JSON)
+    #[arg(long, value_name = "PATH")]
+    pub cookie_file: Option<PathBuf>,
+
+    /// List profiles and the state of their sessions
+    #[arg(long, conflicts_with_all = ["status", "logout", "refresh", "cookie_stdin", "cookie_file"])]
+    pub list: bool,
 }
 
-/// Get the path to the session file
-fn session_path() -> Result<camino::Utf8PathBuf> {
-    data_dir()
-        .map(|dir| dir.join(SESSION_FILE))
+/// Open session storage of `profile`
+fn session_storage_for(profile: &str) -> Result<SessionStorage> {
+    profile::profile_dir(profile)
+        .map(|dir| SessionStorage::open(profile, &dir, SESSION_FILE))
         .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))
 }
 
+/// Open session storage of the active profile
+fn session_storage() -> Result<SessionStorage> {
+    session_storage_for(active_profile())
+}
+
+/// Get the session from `DRAFTKIT_SESSION` or storage, and where it came from
+fn load_session() -> Result<Option<(Session, Backend)>> {
+    if let Ok(cookie) = std::env::var(SESSION_ENV)
+        && !cookie.trim().is_empty()
+    {
+        let session = Session {
+            cookie: strip_cookie_name(&cookie).to_string(),
+            expires_at: None,
+            domain: SESSION_DOMAIN.into(),
+        };
+        return Ok(Some((session, Backend::Environment)));
+    }
+    session_storage()?.load()
+}
+
 /// Get stored session if it exists
 pub fn get_session() -> Result<Option<Session>> {
-    let path = session_path()?;
+    Ok(load_session()?.map(|(session, _)| session))
+}
 
-    if !path.exists() {
-        return Ok(None);
-    }
+/// Store session, returning where it was stored
+fn store_session(session: &Session) -> Result<Backend> {
+    // Profiles are listed by directory, also when the session is in the keyring
+    profile::create_profile_dir(active_profile()).context("Failed to create profile directory")?;
+    session_storage()?.save(session)
+}
 
-    let json = fs::read_to_string(&path).context("Failed to read session file")?;
-    let session: Session = serde_json::from_str(&json).context("Failed to parse session file")?;
-    Ok(Some(session))
+/// Remove stored session
+fn remove_session() -> Result<()> {
+    session_storage()?.remove()
 }
 
-/// Store session in data directory
-fn store_session(session: &Session) -> Result<()> {
-    let path = session_path()?;
+/// Accept a cookie pasted as `laravel_session=VALUE` as well as a bare value
+fn strip_cookie_name(cookie: &str) -> &str {
+    let cookie = cookie.trim();
+    cookie
+        .strip_prefix(SESSION_COOKIE_NAME)
+        .and_then(|rest| rest.strip_prefix('='))
+        .unwrap_or(cookie)
+}
 
-    // Ensure parent directory exists
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).context("Failed to create data directory")?;
+/// Read the session cookie from stdin
+fn read_cookie_stdin() -> Result<Session> {
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .context("Failed to read cookie from stdin")?;
+    let cookie = strip_cookie_name(&input);
+    if cookie.is_empty() {
+        bail!("No cookie on stdin");
     }
+    Ok(Session {
+        cookie: cookie.to_string(),
+        expires_at: None,
+        domain: SESSION_DOMAIN.into(),
+    })
+}
 
-    let json = serde_json::to_string_pretty(session).context("Failed to serialize session")?;
-    fs::write(&path, json).context("Failed to write session file")?;
-    Ok(())
+/// Whether a cookie for `domain` is sent to TailwindPlus: the domain itself
+/// or a subdomain, with or without the leading dot of cookie exports.
+fn is_session_domain(domain: &str) -> bool {
+    let domain = domain.strip_prefix('.').unwrap_or(domain);
+    domain == SESSION_DOMAIN
+        || domain
+            .strip_suffix(SESSION_DOMAIN)
+            .is_some_and(|sub| sub.ends_with('.'))
 }
 
-/// Remove stored session
-fn remove_session() -> Result<()> {
-    let path = session_path()?;
+/// Find the session cookie in a browser cookie export.
+///
+/// Accepts Netscape `cookies.txt`, JSON arrays as written by cookie editor
+/// extensions, and Playwright storage state.
+fn parse_cookie_file(contents: &str) -> Result<Session> {
+    let trimmed = contents.trim_start();
+    let found = if trimmed.starts_with('[') || trimmed.starts_with('{') {
+        let json: serde_json::Value =
+            serde_json::from_str(trimmed).context("Failed to parse JSON cookie file")?;
+        let cookies = json
+            .get("cookies")
+            .unwrap_or(&json)
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        cookies.iter().find_map(|cookie| {
+            let field = |name: &str| cookie.get(name).and_then(serde_json::Value::as_str);
+            let domain = field("domain").unwrap_or(SESSION_DOMAIN);
+            let expires = ["expirationDate", "expires"]
+                .iter()
+                .find_map(|name| cookie.get(*name).and_then(serde_json::Value::as_f64));
+            (field("name") == Some(SESSION_COOKIE_NAME) && is_session_domain(domain)).then(|| {
+                let value = field("value").unwrap_or_default();
+                (value.to_string(), domain.to_string(), expires)
+            })
+        })
+    } else {
+        contents.lines().find_map(|line| {
+            let line = line.strip_prefix("#HttpOnly_").unwrap_or(line);
+            if line.starts_with('#') {
+                return None;
+            }
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [domain, _, _, _, expires, name, value] = fields[..] else {
+                return None;
+            };
+            (name == SESSION_COOKIE_NAME && is_session_domain(domain)).then(|| {
+                (
+                    value.trim().to_string(),
+                    domain.to_string(),
+                    expires.parse().ok(),
+                )
+            })
+        })
+    };
 
-    if path.exists() {
-        fs::remove_file(&path).context("Failed to remove session file")?;
+    let (cookie, domain, expires) = found.ok_or_else(|| {
+        anyhow::anyhow!("No '{SESSION_COOKIE_NAME}' cookie for {SESSION_DOMAIN} in the cookie file")
+    })?;
+    if cookie.is_empty() {
+        bail!("The '{SESSION_COOKIE_NAME}' cookie in the cookie file is empty");
     }
-    Ok(())
+    Ok(Session {
+        cookie,
+        // Zero or negative means a browser-session cookie
+        expires_at: expires.filter(|&e| e > 0.0).map(|e| e as i64),
+        domain: domain.trim_start_matches('.').to_string(),
+    })
+}
+
+/// Check `session` with TailwindPlus and record its real expiry.
+///
+/// Fails if TailwindPlus answers with its login page.
+async fn verify_session(mut session: Session, styler: &Styler) -> Result<Session> {
+    let spinner = styler.spinner("Verifying session...");
+    let check = ComponentFetcher::new(session.cookie.clone())
+        .check_session()
+        .await;
+    spinner.finish_and_clear();
+
+    let check = check.context("Failed to verify the session with TailwindPlus")?;
+    if check.signed_in == Some(false) {
+        bail!(
+            "TailwindPlus did not accept the session. Run `draftkit auth --refresh` to log in again."
+        );
+    }
+    if check.expires_at.is_some() {
+        session.expires_at = check.expires_at;
+    }
+    Ok(session)
 }
 
 /// Prompt for manual cookie input (fallback when browser unavailable)
@@ -265,10 +428,14 @@ async fn browser_auth(styler: &Styler) -> Result<Session> {
 }
 
 /// Run the auth command
-pub async fn cmd_auth(args: AuthArgs, styler: &Styler) -> Result<()> {
+pub async fn cmd_auth(args: AuthArgs, config: &Config, styler: &Styler) -> Result<()> {
+    if args.list {
+        return list_profiles(config, styler);
+    }
+
     // Handle --status flag
     if args.status {
-        return show_status(styler);
+        return show_status(args.verify, styler).await;
     }
 
     // Handle --logout flag
@@ -278,6 +445,19 @@ pub async fn cmd_auth(args: AuthArgs, styler: &Styler) -> Result<()> {
         return Ok(());
     }
 
+    // Non-interactive import
+    if args.cookie_stdin || args.cookie_file.is_some() {
+        let session = match &args.cookie_file {
+            Some(path) => {
+                let contents = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                parse_cookie_file(&contents)?
+            }
+            None => read_cookie_stdin()?,
+        };
+        return save_session(verify_session(session, styler).await?, styler);
+    }
+
     // Check for existing session (unless --refresh)
     if !args.refresh
         && let Some(session) = get_session()?
@@ -315,17 +495,23 @@ pub async fn cmd_auth(args: AuthArgs, styler: &Styler) -> Result<()> {
         }
     };
 
-    // Store session
-    store_session(&session)?;
+    let session = verify_session(session, styler).await?;
+    save_session(session, styler)
+}
+
+/// Store a verified session and report it
+fn save_session(session: Session, styler: &Styler) -> Result<()> {
+    let backend = store_session(&session)?;
 
     println!();
     styler.print_success("Authentication successful!");
+    println!("  Session stored in: {backend}");
+    if active_profile() != DEFAULT_PROFILE {
+        println!("  Profile: {}", active_profile());
+    }
 
-    if let Some(expires) = session.expires_at {
-        let expires_dt = chrono::DateTime::from_timestamp(expires, 0)
-            .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
-            .unwrap_or_else(|| "unknown".into());
-        println!("  Session valid until: {expires_dt}");
+    if let Some(expires) = session.expires_display() {
+        println!("  Session valid until: {expires}");
     }
 
     println!();
@@ -335,51 +521,112 @@ pub async fn cmd_auth(args: AuthArgs, styler: &Styler) -> Result<()> {
 }
 
 /// Show authentication status
-fn show_status(styler: &Styler) -> Result<()> {
+async fn show_status(verify: bool, styler: &Styler) -> Result<()> {
     styler.print_header("Session Status");
     println!();
 
-    match get_session()? {
-        Some(session) => {
-            if session.is_expired() {
-                styler.print_error("Session expired");
-                println!();
-                println!("Run `draftkit auth` to log in again.");
-            } else if session.is_expiring_soon() {
-                styler.print_warning("Session expiring soon");
-                println!();
-                if let Some(expires) = session.expires_at {
-                    let expires_dt = chrono::DateTime::from_timestamp(expires, 0)
-                        .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
-                        .unwrap_or_else(|| "unknown".into());
-                    styler.print_kv("Expires", &expires_dt, 12);
-                }
-                println!();
-                println!("Run `draftkit auth --refresh` to renew.");
-            } else {
-                styler.print_success("Session valid");
-                println!();
-                if let Some(expires) = session.expires_at {
-                    let expires_dt = chrono::DateTime::from_timestamp(expires, 0)
-                        .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
-                        .unwrap_or_else(|| "unknown".into());
-                    styler.print_kv("Expires", &expires_dt, 12);
-                } else {
-                    styler.print_kv("Expires", "session cookie", 12);
-                }
-                styler.print_kv("Domain", &session.domain, 12);
-            }
+    let Some((mut session, backend)) = load_session()? else {
+        styler.print_info("Not authenticated");
+        println!();
+        println!("Run `draftkit auth` to log in to your TailwindPlus account.");
+        return Ok(());
+    };
+
+    if verify {
+        let known_expiry = session.expires_at;
+        session = verify_session(session, styler).await?;
+        // The environment variable has nowhere to record the expiry
+        if session.expires_at != known_expiry && backend != Backend::Environment {
+            store_session(&session)?;
         }
-        None => {
-            styler.print_info("Not authenticated");
-            println!();
-            println!("Run `draftkit auth` to log in to your TailwindPlus account.");
+    }
+
+    if session.is_expired() {
+        styler.print_error("Session expired");
+        println!();
+        println!("Run `draftkit auth` to log in again.");
+    } else if session.is_expiring_soon() {
+        styler.print_warning("Session expiring soon");
+        println!();
+        if let Some(expires) = session.expires_display() {
+            styler.print_kv("Expires", &expires, 12);
+        }
+        println!();
+        println!("Run `draftkit auth --refresh` to renew.");
+    } else {
+        styler.print_success("Session valid");
+        println!();
+        let expires = session
+            .expires_display()
+            .unwrap_or_else(|| "session cookie".into());
+        styler.print_kv("Expires", &expires, 12);
+        styler.print_kv("Domain", &session.domain, 12);
+        styler.print_kv("Stored in", &backend.to_string(), 12);
+        styler.print_kv("Profile", active_profile(), 12);
+        if verify {
+            styler.print_kv("Verified", "signed in to TailwindPlus", 12);
         }
     }
 
     Ok(())
 }
 
+/// One-line state of a stored session.
+fn describe_session(session: &Session) -> String {
+    if session.is_expired() {
+        "expired".into()
+    } else if session.is_expiring_soon() {
+        format!(
+            "expiring soon ({})",
+            session.expires_display().unwrap_or_default()
+        )
+    } else {
+        session.expires_display().map_or_else(
+            || "valid".into(),
+            |expires| format!("valid until {expires}"),
+        )
+    }
+}
+
+/// Show every profile with the state of its session
+fn list_profiles(config: &Config, styler: &Styler) -> Result<()> {
+    styler.print_header("Profiles");
+    println!();
+
+    let active = active_profile();
+    let mut names = profile::list_profiles();
+    for name in config.profiles.keys().map(String::as_str).chain([active]) {
+        if !names.iter().any(|n| n == name) && profile::validate_name(name).is_ok() {
+            names.push(name.to_string());
+        }
+    }
+    names[1..].sort();
+    let width = names.iter().map(String::len).max().unwrap_or_default();
+
+    for name in &names {
+        let marker = if name == active { "*" } else { " " };
+        let state = match session_storage_for(name).and_then(|storage| storage.peek()) {
+            Ok(Some((session, backend))) => format!("{} ({backend})", describe_session(&session)),
+            Ok(None) => "not authenticated".into(),
+            Err(e) => format!("unreadable: {e:#}"),
+        };
+        let account = config
+            .profiles
+            .get(name)
+            .and_then(|p| p.licensed_to.as_deref())
+            .map(|account| format!(" [{account}]"))
+            .unwrap_or_default();
+        println!("{marker} {name:<width$}  {state}{account}");
+    }
+
+    println!();
+    println!(
+        "Select a profile with `--profile NAME` or {}.",
+        profile::PROFILE_ENV
+    );
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -389,7 +636,11 @@ mod tests {
         let _args = AuthArgs {
             logout: false,
             status: false,
+            verify: false,
             refresh: false,
+            cookie_stdin: false,
+            cookie_file: None,
+            list: false,
         };
     }
 
@@ -398,6 +649,82 @@ mod tests {
         assert_eq!(SESSION_FILE, "session.json");
     }
 
+    #[test]
+    fn test_session_expires_display() {
+        let session = Session {
+            cookie: "abc".into(),
+            expires_at: Some(1_900_000_000),
+            domain: "tailwindcss.com".into(),
+        };
+        assert_eq!(
+            session.expires_display().as_deref(),
+            Some("2030-03-17 17:46 UTC")
+        );
+
+        let session = Session {
+            expires_at: None,
+            ..session
+        };
+        assert_eq!(session.expires_display(), None);
+    }
+
+    #[test]
+    fn test_parse_netscape_cookie_file() {
+        let contents = "# Netscape HTTP Cookie File\n\
+            .tailwindcss.com\tTRUE\t/\tTRUE\t1900000000\tXSRF-TOKEN\txsrf\n\
+            #HttpOnly_.tailwindcss.com\tTRUE\t/\tTRUE\t1900000000\tlaravel_session\tsecret\n";
+        let session = parse_cookie_file(contents).unwrap();
+        assert_eq!(session.cookie, "secret");
+        assert_eq!(session.expires_at, Some(1_900_000_000));
+        assert_eq!(session.domain, "tailwindcss.com");
+    }
+
+    #[test]
+    fn test_parse_json_cookie_file() {
+        // Cookie editor extensions
+        let session = parse_cookie_file(
+            r#"[{"name": "laravel_session", "value": "secret", "domain": ".tailwindcss.com", "expirationDate": 1900000000.5}]"#,
+        )
+        .unwrap();
+        assert_eq!(session.cookie, "secret");
+        assert_eq!(session.expires_at, Some(1_900_000_000));
+
+        // Playwright storage state, with a browser-session cookie
+        let session = parse_cookie_file(
+            r#"{"cookies": [{"name": "laravel_session", "value": "secret", "domain": "tailwindcss.com", "expires": -1}], "origins": []}"#,
+        )
+        .unwrap();
+        assert_eq!(session.expires_at, None);
+
+        let error = parse_cookie_file(
+            r#"[{"name": "laravel_session", "value": "x", "domain": "example.com"}]"#,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("No 'laravel_session' cookie"));
+    }
+
+    #[test]
+    fn test_parse_cookie_file_rejects_look_alike_domains() {
+        for domain in [
+            "tailwindcss.com.evil.io",
+            "nottailwindcss.com",
+            ".evil-tailwindcss.com",
+        ] {
+            let contents =
+                format!("{domain}\tTRUE\t/\tTRUE\t1900000000\tlaravel_session\tstolen\n");
+            assert!(parse_cookie_file(&contents).is_err(), "{domain}");
+        }
+
+        let contents = "www.tailwindcss.com\tFALSE\t/\tTRUE\t1900000000\tlaravel_session\tsecret\n";
+        assert_eq!(parse_cookie_file(contents).unwrap().cookie, "secret");
+    }
+
+    #[test]
+    fn test_strip_cookie_name() {
+        assert_eq!(strip_cookie_name("laravel_session=abc\n"), "abc");
+        assert_eq!(strip_cookie_name("  abc "), "abc");
+    }
+
     #[test]
     fn test_session_serialization() {
         let session = Session {
@@ -1,26 +1,57 @@
 //! Cache command implementation
 //!
-//! Manages the local component cache where fetched TailwindPlus components
-//! are stored for offline access.
-
-use std::fs;
+//! Manages the local caches of fetched TailwindPlus component code and
+//! preview images. Only the `cache/` directory of the active profile is
+//! touched; sessions and runtime data elsewhere in the data directory are
+//! left alone.
+//!
+//! ```bash
+//! draftkit cache stats
+//! draftkit cache list --framework react
+//! draftkit cache show <id> --framework react --mode light
+//! draftkit cache clear --previews
+//! draftkit cache prune --older-than 30d
+//! draftkit cache verify --fix
+//! draftkit cache sync --framework react,vue --mode light,dark --category marketing
+//! ```
+
+use std::collections::HashMap;
+use std::time::Duration;
 
 use anyhow::{Context, Result, bail};
 use camino::Utf8PathBuf;
-use clap::Args;
+use clap::{Args, Subcommand};
 use dialoguer::Confirm;
-use draftkit_core::data_dir;
-
+use draftkit_core::{
+    ComponentReader, ComponentRecord, Framework, Mode,
+    cache::{self, CachedComponent},
+    fetch::{ComponentFetcher, FetchError, RetryPolicy},
+    preview, profile,
+    sync::{SyncPage, SyncPlan, SyncState},
+};
+
+use super::auth;
 use crate::cli::Styler;
 
+/// Retries for a sync, which is patient with rate limiting since it makes
+/// many requests in a row.
+const SYNC_RETRY_POLICY: RetryPolicy = RetryPolicy {
+    max_retries: 5,
+    base_delay: Duration::from_secs(15),
+    max_delay: Duration::from_secs(5 * 60),
+};
+
 /// Manage the local component cache.
 #[derive(Args)]
 pub struct CacheArgs {
-    /// Show cache statistics (size, component count)
+    #[command(subcommand)]
+    pub command: Option<CacheCommand>,
+
+    /// Show cache statistics (same as `cache stats`)
     #[arg(long)]
     pub stats: bool,
 
-    /// Clear all cached components
+    /// Clear cached components (same as `cache clear --components`)
     #[arg(long)]
     pub clear: bool,
 
@@ -29,25 +60,136 @@ pub struct CacheArgs {
     pub path: bool,
 }
 
-/// Calculate cache statistics by walking the directory tree.
-fn calculate_cache_stats(cache_dir: &Utf8PathBuf) -> Result<(u64, u64)> {
-    let mut file_count = 0u64;
-    let mut total_bytes = 0u64;
+#[derive(Subcommand)]
+pub enum CacheCommand {
+    /// Prefetch component code for offline use
+    ///
+    /// TailwindPlus serves each page in the framework, Tailwind version and
+    /// mode last picked on your account. To fetch other variants, sync
+    /// changes those account preferences for a request and changes them
+    /// back afterwards, also when the request fails.
+    Sync(SyncArgs),
+    /// Show statistics for the component and preview caches
+    Stats,
+    /// Remove cached components and/or previews
+    Clear(ClearArgs),
+    /// List cached components
+    List(ListArgs),
+    /// Show the cached variants of a component, or print one
+    Show(ShowArgs),
+    /// Remove cache entries older than a given age
+    Prune(PruneArgs),
+    /// Check cached component files and preview images for damage
+    Verify(VerifyArgs),
+}
 
-    if cache_dir.exists() {
-        for entry in walkdir::WalkDir::new(cache_dir)
-            .into_iter()
-            .filter_map(Result::ok)
-            .filter(|e| e.file_type().is_file())
-        {
-            file_count += 1;
-            if let Ok(metadata) = entry.metadata() {
-                total_bytes += metadata.len();
-            }
-        }
-    }
+#[derive(Args)]
+pub struct ClearArgs {
+    /// Clear cached component code (the default)
+    #[arg(long)]
+    pub components: bool,
+
+    /// Clear cached preview images, which all profiles share
+    #[arg(long)]
+    pub previews: bool,
+
+    /// Clear components, previews and the progress of an interrupted sync
+    #[arg(long)]
+    pub all: bool,
+
+    /// Don't ask for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+#[derive(Args)]
+pub struct ListArgs {
+    /// Only list components cached for this framework
+    #[arg(long, value_parser = parse_framework)]
+    pub framework: Option<Framework>,
+}
+
+#[derive(Args)]
+pub struct ShowArgs {
+    /// Component UUID or catalog ID
+    pub id: String,
+
+    /// Framework of the variant to print
+    #[arg(long, value_parser = parse_framework, requires = "mode")]
+    pub framework: Option<Framework>,
+
+    /// Mode of the variant to print
+    #[arg(long, value_parser = parse_mode, requires = "framework")]
+    pub mode: Option<Mode>,
+}
+
+#[derive(Args)]
+pub struct PruneArgs {
+    /// Remove entries last fetched longer ago than this, e.g. 30d, 12h or 2w
+    #[arg(long, value_parser = parse_age)]
+    pub older_than: Duration,
+}
+
+#[derive(Args)]
+pub struct VerifyArgs {
+    /// Remove damaged entries so they are fetched again
+    #[arg(long)]
+    pub fix: bool,
+}
+
+#[derive(Args)]
+pub struct SyncArgs {
+    /// Frameworks to fetch (comma-separated)
+    #[arg(long, value_delimiter = ',', default_value = "html,react,vue", value_parser = parse_framework)]
+    pub framework: Vec<Framework>,
+
+    /// Theme modes to fetch (comma-separated)
+    #[arg(long, value_delimiter = ',', default_value = "light,dark,system", value_parser = parse_mode)]
+    pub mode: Vec<Mode>,
+
+    /// Only sync this category path, e.g. "marketing" or "application-ui/forms" (repeatable)
+    #[arg(long)]
+    pub category: Vec<String>,
+
+    /// Pause between requests, in milliseconds
+    #[arg(long, default_value_t = 1000)]
+    pub delay: u64,
+
+    /// Refetch pages whose components are already cached
+    #[arg(long)]
+    pub force: bool,
+
+    /// Ignore the progress of an interrupted sync and start over
+    #[arg(long)]
+    pub restart: bool,
+}
 
-    Ok((file_count, total_bytes))
+fn parse_framework(s: &str) -> Result<Framework, String> {
+    Framework::parse(s)
+        .ok_or_else(|| format!("Unknown framework '{s}'. Valid options: html, react, vue"))
+}
+
+fn parse_mode(s: &str) -> Result<Mode, String> {
+    Mode::parse(s)
+        .ok_or_else(|| format!("Unknown mode '{s}'. Valid options: light, dark, system, none"))
+}
+
+/// Parse an age such as `30d`: a number followed by s, m, h, d or w.
+fn parse_age(s: &str) -> Result<Duration, String> {
+    let invalid =
+        || format!("Invalid age '{s}'. Use a number and a unit (s, m, h, d, w), e.g. 30d");
+    let split = s.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+    let (number, unit) = s.split_at(split);
+    let number: u64 = number.parse().map_err(|_| invalid())?;
+    let unit_secs = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    Ok(Duration::from_secs(number.saturating_mul(unit_secs)))
 }
 
 /// Format bytes into human-readable size.
@@ -67,15 +209,28 @@ fn format_size(bytes: u64) -> String {
     }
 }
 
-/// Get the cache directory path.
+/// Get the cache directory path of the active profile.
 fn cache_dir() -> Option<Utf8PathBuf> {
-    data_dir::data_dir().map(|d| d.to_path_buf())
+    cache::cache_dir()
 }
 
 /// Execute the cache command.
-pub fn cmd_cache(args: CacheArgs, color_mode: &str) -> Result<()> {
+pub async fn cmd_cache(args: CacheArgs, color_mode: &str) -> Result<()> {
     let styler = Styler::new(color_mode);
 
+    match args.command {
+        Some(CacheCommand::Sync(sync_args)) => return cmd_cache_sync(sync_args, &styler).await,
+        Some(CacheCommand::Stats) => return cmd_cache_stats(&styler),
+        Some(CacheCommand::Clear(clear_args)) => return cmd_cache_clear(&clear_args, &styler),
+        Some(CacheCommand::List(list_args)) => return cmd_cache_list(&list_args, &styler),
+        Some(CacheCommand::Show(show_args)) => return cmd_cache_show(&show_args, &styler),
+        Some(CacheCommand::Prune(prune_args)) => return cmd_cache_prune(&prune_args, &styler),
+        Some(CacheCommand::Verify(verify_args)) => {
+            return cmd_cache_verify(&verify_args, &styler);
+        }
+        None => {}
+    }
+
     // Default to --stats if no flags provided
     let show_stats = args.stats || (!args.clear && !args.path);
 
@@ -84,7 +239,13 @@ pub fn cmd_cache(args: CacheArgs, color_mode: &str) -> Result<()> {
     }
 
     if args.clear {
-        return cmd_cache_clear(&styler);
+        let clear_args = ClearArgs {
+            components: true,
+            previews: false,
+            all: false,
+            yes: false,
+        };
+        return cmd_cache_clear(&clear_args, &styler);
     }
 
     if show_stats {
@@ -94,8 +255,9 @@ pub fn cmd_cache(args: CacheArgs, color_mode: &str) -> Result<()> {
     Ok(())
 }
 
-/// Print cache statistics.
+/// Print statistics for the component and preview caches.
 fn cmd_cache_stats(styler: &Styler) -> Result<()> {
+    const KV_WIDTH: usize = 10;
     let Some(cache_path) = cache_dir() else {
         styler.print_error("Could not determine cache directory");
         bail!("No data directory available");
@@ -103,74 +265,413 @@ fn cmd_cache_stats(styler: &Styler) -> Result<()> {
 
     styler.print_header("Component Cache");
     println!();
+    let stats = cache::get_stats();
+    styler.print_kv("Location", cache_path.join("components").as_str(), KV_WIDTH);
+    styler.print_kv("Profile", profile::active_profile(), KV_WIDTH);
+    styler.print_kv(
+        "Cached",
+        &format!(
+            "{} components ({} files)",
+            stats.component_count, stats.file_count
+        ),
+        KV_WIDTH,
+    );
+    styler.print_kv("Size", &format_size(stats.total_bytes), KV_WIDTH);
+    println!();
 
-    if !cache_path.exists() {
-        styler.print_kv("Location", cache_path.as_str(), 10);
-        styler.print_kv("Status", "Not initialized", 10);
-        println!();
-        styler.print_info("No components have been cached yet.");
+    styler.print_header("Preview Cache");
+    println!();
+    let stats = preview::get_preview_cache_stats();
+    if let Some(dir) = preview::previews_cache_dir() {
+        styler.print_kv("Location", dir.as_str(), KV_WIDTH);
+    }
+    styler.print_kv(
+        "Cached",
+        &format!(
+            "{} components ({} images)",
+            stats.component_count, stats.file_count
+        ),
+        KV_WIDTH,
+    );
+    styler.print_kv("Size", &format_size(stats.total_bytes), KV_WIDTH);
+
+    Ok(())
+}
+
+/// Clear the selected caches after confirming.
+fn cmd_cache_clear(args: &ClearArgs, styler: &Styler) -> Result<()> {
+    let previews = args.previews || args.all;
+    let components = args.components || args.all || !previews;
+
+    let mut targets = Vec::new();
+    let mut total_bytes = 0;
+    if components {
+        let stats = cache::get_stats();
+        if stats.file_count > 0 {
+            targets.push(format!("{} cached components", stats.component_count));
+            total_bytes += stats.total_bytes;
+        }
+    }
+    if previews {
+        let stats = preview::get_preview_cache_stats();
+        if stats.file_count > 0 {
+            targets.push(format!("{} preview images", stats.file_count));
+            total_bytes += stats.total_bytes;
+        }
+    }
+
+    if targets.is_empty() {
+        if args.all {
+            SyncState::clear().context("Failed to clear sync progress")?;
+        }
+        styler.print_info("Cache is already empty.");
         return Ok(());
     }
 
-    let (file_count, total_bytes) =
-        calculate_cache_stats(&cache_path).context("Failed to calculate cache statistics")?;
+    if !args.yes {
+        let message = format!(
+            "Clear {} ({})? This cannot be undone.",
+            targets.join(" and "),
+            format_size(total_bytes)
+        );
+        let confirmed = Confirm::new()
+            .with_prompt(message)
+            .default(false)
+            .interact()
+            .unwrap_or(false);
+
+        if !confirmed {
+            styler.print_info("Cache clear cancelled.");
+            return Ok(());
+        }
+    }
 
-    styler.print_kv("Location", cache_path.as_str(), 10);
-    styler.print_kv("Files", &file_count.to_string(), 10);
-    styler.print_kv("Size", &format_size(total_bytes), 10);
+    let spinner = styler.spinner("Clearing cache...");
+    let mut freed = 0;
+    if components {
+        freed += cache::clear_cache().context("Failed to clear component cache")?;
+    }
+    if previews {
+        freed += preview::clear_preview_cache().context("Failed to clear preview cache")?;
+    }
+    if args.all {
+        SyncState::clear().context("Failed to clear sync progress")?;
+    }
+    spinner.finish_and_clear();
 
+    styler.print_success(&format!("Cache cleared (freed {})", format_size(freed)));
     Ok(())
 }
 
-/// Clear all cached components.
-fn cmd_cache_clear(styler: &Styler) -> Result<()> {
-    let Some(cache_path) = cache_dir() else {
-        styler.print_error("Could not determine cache directory");
-        bail!("No data directory available");
-    };
+/// Catalog records by UUID, for naming cached components.
+fn records_by_uuid() -> HashMap<&'static str, &'static ComponentRecord> {
+    let reader = ComponentReader::new();
+    [Framework::React, Framework::Vue, Framework::Html]
+        .into_iter()
+        .flat_map(|framework| reader.all(framework))
+        .map(|record| (record.uuid.as_str(), record))
+        .collect()
+}
 
-    if !cache_path.exists() {
-        styler.print_info("Cache is already empty.");
+/// Variants of a cached component, e.g. `react: dark, light; vue: light`.
+fn describe_variants(component: &CachedComponent) -> String {
+    let mut groups: Vec<(Framework, Vec<&str>)> = Vec::new();
+    for variant in &component.variants {
+        match groups.iter_mut().find(|(f, _)| *f == variant.framework) {
+            Some((_, modes)) => modes.push(variant.mode.as_str()),
+            None => groups.push((variant.framework, vec![variant.mode.as_str()])),
+        }
+    }
+    groups
+        .iter()
+        .map(|(framework, modes)| format!("{}: {}", framework.as_str(), modes.join(", ")))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// List cached components with their variants and size.
+fn cmd_cache_list(args: &ListArgs, styler: &Styler) -> Result<()> {
+    let components: Vec<CachedComponent> = cache::list_cached()
+        .into_iter()
+        .filter(|c| {
+            args.framework
+                .is_none_or(|framework| c.variants.iter().any(|v| v.framework == framework))
+        })
+        .collect();
+
+    if components.is_empty() {
+        styler.print_info("No components have been cached yet.");
         return Ok(());
     }
 
-    let (file_count, total_bytes) =
-        calculate_cache_stats(&cache_path).context("Failed to calculate cache statistics")?;
+    let records = records_by_uuid();
+    let id_width = components
+        .iter()
+        .map(|c| c.id.len())
+        .max()
+        .unwrap_or_default();
+    for component in &components {
+        let name = records
+            .get(component.id.as_str())
+            .map_or("(not in catalog)", |record| record.name.as_str());
+        println!(
+            "{:<id_width$}  {}  {}",
+            component.id,
+            styler.bold().apply_to(name),
+            styler.dim().apply_to(format!(
+                "{} · {}",
+                describe_variants(component),
+                format_size(component.total_bytes())
+            ))
+        );
+    }
+    println!();
+    println!("{} cached components", components.len());
+    Ok(())
+}
 
-    if file_count == 0 {
-        styler.print_info("Cache is already empty.");
+/// Find a cached component by UUID or catalog ID.
+fn find_cached(id: &str) -> Option<CachedComponent> {
+    cache::cached_component(id).or_else(|| {
+        let reader = ComponentReader::new();
+        [Framework::React, Framework::Vue, Framework::Html]
+            .into_iter()
+            .find_map(|framework| reader.find_by_id(framework, id))
+            .and_then(|record| cache::cached_component(&record.uuid))
+    })
+}
+
+/// Show the cached variants of a component, or print one of them.
+fn cmd_cache_show(args: &ShowArgs, styler: &Styler) -> Result<()> {
+    let Some(component) = find_cached(&args.id) else {
+        bail!("Nothing is cached for '{}'", args.id);
+    };
+
+    if let (Some(framework), Some(mode)) = (args.framework, args.mode) {
+        let Some(code) = cache::get_cached(&component.id, framework, mode) else {
+            bail!(
+                "No {} {} variant is cached for '{}'",
+                framework.as_str(),
+                mode.as_str(),
+                args.id
+            );
+        };
+        print!("{code}");
         return Ok(());
     }
 
-    // Confirm before clearing
-    let message = format!(
-        "Clear {} cached files ({})? This cannot be undone.",
-        file_count,
-        format_size(total_bytes)
+    const KV_WIDTH: usize = 10;
+    styler.print_header("Cached Component");
+    println!();
+    styler.print_kv("UUID", &component.id, KV_WIDTH);
+    if let Some(record) = records_by_uuid().get(component.id.as_str()) {
+        styler.print_kv("Name", &record.name, KV_WIDTH);
+        styler.print_kv("ID", &record.id, KV_WIDTH);
+    }
+    println!();
+    for variant in &component.variants {
+        let fetched = variant.modified.map_or_else(
+            || "unknown".to_string(),
+            |time| {
+                chrono::DateTime::<chrono::Utc>::from(time)
+                    .format("%Y-%m-%d %H:%M UTC")
+                    .to_string()
+            },
+        );
+        println!(
+            "  {:<6} {:<7} {:>10}  {}",
+            variant.framework.as_str(),
+            variant.mode.as_str(),
+            format_size(variant.bytes),
+            styler.dim().apply_to(format!("fetched {fetched}"))
+        );
+    }
+    println!();
+    println!(
+        "Print a variant with `draftkit cache show {} --framework <FRAMEWORK> --mode <MODE>`.",
+        args.id
     );
+    Ok(())
+}
 
-    let confirmed = Confirm::new()
-        .with_prompt(message)
-        .default(false)
-        .interact()
-        .unwrap_or(false);
+/// Remove component and preview cache entries older than the given age.
+fn cmd_cache_prune(args: &PruneArgs, styler: &Styler) -> Result<()> {
+    let components = cache::prune_older_than(args.older_than);
+    let previews = preview::prune_preview_cache(args.older_than);
 
-    if !confirmed {
-        styler.print_info("Cache clear cancelled.");
+    let skipped = components.skipped + previews.skipped;
+    if skipped > 0 {
+        styler.print_warning(&format!(
+            "Skipped {skipped} cache entries that could not be read or removed"
+        ));
+    }
+
+    let files = components.file_count + previews.file_count;
+    if files == 0 {
+        styler.print_info("Nothing to prune.");
         return Ok(());
     }
+    styler.print_success(&format!(
+        "Pruned {} component files and {} preview images (freed {})",
+        components.file_count,
+        previews.file_count,
+        format_size(components.total_bytes + previews.total_bytes)
+    ));
+    Ok(())
+}
 
-    // Remove the cache directory
-    let spinner = styler.spinner("Clearing cache...");
+/// Report damaged cache entries, removing them with `--fix`.
+fn cmd_cache_verify(args: &VerifyArgs, styler: &Styler) -> Result<()> {
+    let mut problems = cache::verify();
+    problems.extend(preview::verify_preview_cache());
+    if problems.is_empty() {
+        styler.print_success(&format!(
+            "Cache is healthy ({} components, {} preview images)",
+            cache::get_stats().component_count,
+            preview::get_preview_cache_stats().file_count
+        ));
+        return Ok(());
+    }
 
-    fs::remove_dir_all(&cache_path).context("Failed to remove cache directory")?;
+    for problem in &problems {
+        println!("  {}: {}", problem.kind, problem.path);
+    }
+    println!();
 
-    spinner.finish_and_clear();
+    if !args.fix {
+        bail!(
+            "Found {} damaged cache entries. Run `draftkit cache verify --fix` to remove them.",
+            problems.len()
+        );
+    }
+    for problem in &problems {
+        problem
+            .remove()
+            .with_context(|| format!("Failed to remove {}", problem.path))?;
+    }
     styler.print_success(&format!(
-        "Cache cleared (freed {})",
-        format_size(total_bytes)
+        "Removed {} damaged cache entries; they will be fetched again when needed",
+        problems.len()
     ));
+    Ok(())
+}
+
+/// Fetch every planned subcategory page once per framework and mode,
+/// caching all its snippets.
+async fn cmd_cache_sync(args: SyncArgs, styler: &Styler) -> Result<()> {
+    let plan = SyncPlan::new(&args.framework, &args.mode, &args.category);
+    if plan.pages.is_empty() {
+        bail!("No catalog components match the given frameworks, modes and categories");
+    }
+
+    if args.restart {
+        SyncState::clear().context("Failed to reset sync progress")?;
+    }
+    let mut state = SyncState::load(&plan);
+    let resumed = state.completed.len();
+    let planned: Vec<(&SyncPage, Framework, Mode)> = plan
+        .pages
+        .iter()
+        .flat_map(|page| {
+            page.variants()
+                .into_iter()
+                .map(move |(framework, mode)| (page, framework, mode))
+        })
+        .collect();
+    let requests: Vec<_> = planned
+        .iter()
+        .copied()
+        .filter(|&(page, framework, mode)| {
+            !state.is_done(page, framework, mode)
+                && (args.force || !page.missing_in(framework, mode).is_empty())
+        })
+        .collect();
+
+    styler.print_header("Syncing component cache");
+    println!();
+    const KV_WIDTH: usize = 10;
+    let names = |items: Vec<&str>| items.join(", ");
+    styler.print_kv(
+        "Framework",
+        &names(args.framework.iter().map(Framework::as_str).collect()),
+        KV_WIDTH,
+    );
+    styler.print_kv(
+        "Mode",
+        &names(args.mode.iter().map(Mode::as_str).collect()),
+        KV_WIDTH,
+    );
+    styler.print_kv(
+        "Requests",
+        &format!(
+            "{} to fetch of {} ({} pages)",
+            requests.len(),
+            planned.len(),
+            plan.pages.len()
+        ),
+        KV_WIDTH,
+    );
+    if resumed > 0 {
+        styler.print_kv("Resuming", &format!("{resumed} requests done"), KV_WIDTH);
+    }
+    println!();
 
+    if requests.is_empty() {
+        SyncState::clear().context("Failed to clear sync progress")?;
+        styler.print_success(&format!(
+            "Cache is up to date ({} snippets)",
+            plan.item_count()
+        ));
+        return Ok(());
+    }
+
+    let session = auth::get_session()?
+        .ok_or_else(|| anyhow::anyhow!(FetchError::NotAuthenticated.to_string()))?;
+    if session.is_expired() {
+        bail!(FetchError::SessionExpired.to_string());
+    }
+    let fetcher = ComponentFetcher::new(session.cookie).with_retry_policy(SYNC_RETRY_POLICY);
+    fetcher.init().await?;
+
+    let progress = styler.progress_bar(requests.len() as u64, "Syncing");
+    let mut fetched = 0;
+    for (index, &(page, framework, mode)) in requests.iter().enumerate() {
+        if index > 0 {
+            tokio::time::sleep(Duration::from_millis(args.delay)).await;
+        }
+        let label = format!("{} ({framework}, {mode})", page.label());
+        progress.set_message(label.clone());
+        let snippets = match page.fetch(&fetcher, framework, mode).await {
+            Ok(snippets) => snippets,
+            Err(e) => {
+                progress.abandon();
+                return Err(e).with_context(|| {
+                    format!("Failed to fetch {label}. Run `draftkit cache sync` again to resume.")
+                });
+            }
+        };
+        fetched += snippets;
+        state
+            .complete(page, framework, mode)
+            .context("Failed to save sync progress")?;
+        progress.inc(1);
+    }
+    progress.finish_and_clear();
+    SyncState::clear().context("Failed to clear sync progress")?;
+
+    let unavailable: usize = requests
+        .iter()
+        .map(|&(page, framework, mode)| page.missing_in(framework, mode).len())
+        .sum();
+    styler.print_success(&format!(
+        "Synced {} requests ({fetched} snippets cached)",
+        requests.len()
+    ));
+    if unavailable > 0 {
+        styler.print_warning(&format!(
+            "{unavailable} requested snippets were not returned by TailwindPlus"
+        ));
+    }
     Ok(())
 }
 
@@ -187,6 +688,8 @@ fn cmd_cache_path() -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use camino::Utf8Path;
+    use draftkit_core::profile::DEFAULT_PROFILE;
 
     #[test]
     fn test_format_size_bytes() {
@@ -215,6 +718,28 @@ mod tests {
         assert_eq!(format_size(1024 * 1024 * 1024 * 2), "2.00 GB");
     }
 
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("30d"), Ok(Duration::from_secs(30 * 24 * 60 * 60)));
+        assert_eq!(parse_age("12h"), Ok(Duration::from_secs(12 * 60 * 60)));
+        assert_eq!(parse_age("2w"), Ok(Duration::from_secs(14 * 24 * 60 * 60)));
+        assert!(parse_age("30").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("30 days").is_err());
+    }
+
+    #[test]
+    fn test_cache_dir_is_not_the_data_dir() {
+        // Clearing the cache must never reach sessions or runtime data
+        let temp = tempfile::tempdir().unwrap();
+        let data = Utf8Path::from_path(temp.path()).unwrap();
+        for profile in [DEFAULT_PROFILE, "client-a"] {
+            let cache = cache::cache_dir_in(data, profile);
+            assert_ne!(cache, profile::profile_dir_in(data, profile));
+            assert!(cache.starts_with(data));
+        }
+    }
+
     #[test]
     fn test_cache_dir_returns_some() {
         // Should return Some on most systems
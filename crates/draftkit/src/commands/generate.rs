@@ -4,22 +4,51 @@
 //!
 //! ```bash
 //! draftkit generate index --pattern saas-landing --preset Minimalist
+//! draftkit generate --site site.toml
 //! ```
+//!
+//! Sections are built from TailwindPlus components when signed in (see
+//! `draftkit auth`), reading the component cache first, and from
+//! placeholders otherwise. Fetched React and Vue sections are written as
+//! their own component files, since whole component modules cannot share
+//! one file.
+//!
+//! Pattern pages are recorded in `.draftkit/lock.toml`, so re-running the
+//! same command reproduces the same sections until `--update` is passed.
+//!
+//! Packages the generated sections import are added to `package.json` with
+//! versions compatible with the project, or installed with `--install`.
+//!
+//! Heroicons in fetched components can be rewritten to Lucide, Phosphor or
+//! Tabler with `--icons`, the `[icons]` config table, or a preset.
+
+use std::process::Command;
 
 use anyhow::{Context, Result, bail};
 use camino::{Utf8Path, Utf8PathBuf};
 use clap::Args;
 use draftkit_core::{
-    FrameworkTarget, GenerateOptions, Mode, PackageManager, PageGenerator, ProjectConfig,
-    TailwindVersion, intelligence::PatternMatcher, patterns::PatternLoader,
+    Config, GenerateError, GenerateLock, GenerateOptions, GeneratedPage, Mode, PageGenerator,
+    ProjectConfig, ProjectInspection, SitePlan, VirtualFs,
+    fetch::ComponentFetcher,
+    intelligence::{PatternMatcher, Recipe},
+    patterns::PatternLoader,
+    preset::PresetLoader,
+    scaffold::{IconLibrary, IconOptions, LockedPage, MissingPackage},
 };
 
+use super::auth;
 use crate::cli::Styler;
 
 #[derive(Args)]
 pub struct GenerateArgs {
     /// Page name to generate (e.g., "index", "about", "pricing")
-    pub page_name: String,
+    #[arg(required_unless_present = "site")]
+    pub page_name: Option<String>,
+
+    /// Generate every page of a site plan (TOML)
+    #[arg(long, conflicts_with_all = ["page_name", "pattern", "recipe", "output"])]
+    pub site: Option<Utf8PathBuf>,
 
     /// Generate from pattern
     #[arg(long, short)]
@@ -37,16 +66,53 @@ pub struct GenerateArgs {
     #[arg(long)]
     pub slots: Option<String>,
 
-    /// Theme mode
-    #[arg(long, short, default_value = "light", value_parser = parse_mode)]
-    pub mode: Mode,
+    /// Theme mode (defaults to the mode the page was last generated in, then light)
+    #[arg(long, short, value_parser = parse_mode)]
+    pub mode: Option<Mode>,
 
     /// Output path (overrides framework default)
     #[arg(long, short)]
     pub output: Option<Utf8PathBuf>,
+
+    /// Write each section to its own component file (always done for fetched
+    /// React and Vue components)
+    #[arg(long, conflicts_with = "site")]
+    pub split: bool,
+
+    /// Regenerate only this section type's file, leaving the page and other sections untouched
+    #[arg(long, value_name = "TYPE", requires_all = ["split", "pattern"])]
+    pub section: Option<String>,
+
+    /// Re-pick this section type instead of reusing the locked choice (repeatable)
+    #[arg(long, value_name = "TYPE", requires = "pattern")]
+    pub update: Vec<String>,
+
+    /// Overwrite generated files edited since the last run, or a site layout or router draftkit did not write
+    #[arg(long)]
+    pub force: bool,
+
+    /// Preview the files that would be written, with diffs, without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Describe the planned writes as JSON
+    #[arg(long, requires = "dry_run")]
+    pub json: bool,
+
+    /// Install missing packages with the package manager instead of adding them to package.json
+    #[arg(long)]
+    pub install: bool,
+
+    /// Rewrite Heroicons to another icon library (heroicons, lucide, phosphor, tabler)
+    #[arg(long, value_name = "LIBRARY")]
+    pub icons: Option<IconLibrary>,
+
+    /// Use components that need Tailwind v4 even in a Tailwind v3 project
+    #[arg(long)]
+    pub allow_v4_only: bool,
 }
 
-fn parse_mode(s: &str) -> Result<Mode, String> {
+pub(crate) fn parse_mode(s: &str) -> Result<Mode, String> {
     match s.to_lowercase().as_str() {
         "light" => Ok(Mode::Light),
         "dark" => Ok(Mode::Dark),
@@ -59,50 +125,106 @@ fn parse_mode(s: &str) -> Result<Mode, String> {
 }
 
 /// Generate a page from patterns
-pub fn cmd_generate(args: GenerateArgs, styler: &Styler) -> Result<()> {
+pub async fn cmd_generate(args: GenerateArgs, app_config: &Config, styler: &Styler) -> Result<()> {
     // Determine project context
     let cwd = std::env::current_dir().context("Failed to get current directory")?;
     let project_path = Utf8PathBuf::try_from(cwd).context("Path is not valid UTF-8")?;
 
     // Detect project configuration
-    let config = detect_project_config(&project_path)?;
+    let inspection = inspect_project(&project_path)?;
+    let config = inspection.project_config();
 
-    styler.print_header("Generating page");
-    println!();
-    const KV_WIDTH: usize = 12;
-    styler.print_kv("Page", &args.page_name, KV_WIDTH);
-    styler.print_kv("Framework", config.framework.as_str(), KV_WIDTH);
+    // Parse slots if provided
+    let slots = match args.slots {
+        Some(ref slots_json) => {
+            serde_json::from_str(slots_json).context("Invalid JSON in --slots")?
+        }
+        None => std::collections::HashMap::new(),
+    };
 
-    if let Some(ref pattern) = args.pattern {
-        styler.print_kv("Pattern", pattern, KV_WIDTH);
+    if let Some(ref site_path) = args.site {
+        let options = GenerateOptions {
+            mode: args.mode.unwrap_or_default(),
+            slots,
+            ..Default::default()
+        };
+        return generate_site(
+            site_path,
+            &inspection,
+            &config,
+            &options,
+            app_config,
+            &args,
+            styler,
+        )
+        .await;
     }
-    if let Some(ref preset) = args.preset {
-        styler.print_kv("Preset", preset, KV_WIDTH);
+
+    let page_name = args.page_name.as_deref().unwrap_or("index");
+    let icons = icon_options(app_config, args.preset.as_deref(), args.icons)?;
+    let mut lock = GenerateLock::load(&config.path)?;
+    let mode = args
+        .mode
+        .or_else(|| lock.mode(page_name))
+        .unwrap_or_default();
+
+    if !args.json {
+        styler.print_header("Generating page");
+        println!();
+        const KV_WIDTH: usize = 12;
+        styler.print_kv("Page", page_name, KV_WIDTH);
+        styler.print_kv("Framework", config.framework.as_str(), KV_WIDTH);
+
+        if let Some(ref pattern) = args.pattern {
+            styler.print_kv("Pattern", pattern, KV_WIDTH);
+        }
+        if let Some(ref preset) = args.preset {
+            styler.print_kv("Preset", preset, KV_WIDTH);
+        }
+        if icons.is_active()
+            && let Some(library) = icons.library
+        {
+            styler.print_kv("Icons", library.as_str(), KV_WIDTH);
+        }
+        styler.print_kv("Mode", mode.as_str(), KV_WIDTH);
+        println!();
     }
-    styler.print_kv("Mode", args.mode.as_str(), KV_WIDTH);
-    println!();
 
     // Build generation options
-    let mut options = GenerateOptions {
-        mode: args.mode,
+    let options = GenerateOptions {
+        mode,
         pattern: args.pattern.clone(),
         preset: args.preset.clone(),
+        slots,
         output_path: args.output.clone(),
-        ..Default::default()
+        split_sections: args.split,
+        locked_components: std::collections::HashMap::new(),
+        allow_v4_only: args.allow_v4_only,
+        sections_dir: None,
+        import_alias: None,
+        icons,
     };
 
-    // Parse slots if provided
-    if let Some(ref slots_json) = args.slots {
-        let slots: std::collections::HashMap<String, draftkit_core::SlotValue> =
-            serde_json::from_str(slots_json).context("Invalid JSON in --slots")?;
-        options.slots = slots;
-    }
-
     // Generate the page
-    let generator = PageGenerator::new();
+    let generator = pattern_generator(&config)?;
 
     let page = if let Some(ref pattern_id) = args.pattern {
-        generate_from_pattern(&generator, &config, pattern_id, &options, styler)?
+        let mut recipe = pattern_recipe(pattern_id, styler)?;
+        let locked_components = lock.apply(page_name, &mut recipe, &args.update);
+
+        let options = GenerateOptions {
+            locked_components,
+            ..options.clone()
+        };
+        if !args.json {
+            print_tailwind_conflicts(&generator, &recipe, &config, &options, styler);
+            print_placeholder_notice(&generator, styler);
+        }
+
+        let spinner = styler.spinner("Assembling page...");
+        let page = generate_pattern_page(&generator, &recipe, &config, &options).await?;
+        spinner.finish_with_message("Page assembled");
+        page
     } else {
         // No pattern - generate placeholder
         let spinner = styler.spinner("Generating placeholder page...");
@@ -111,45 +233,510 @@ pub fn cmd_generate(args: GenerateArgs, styler: &Styler) -> Result<()> {
         page
     };
 
+    // Only the sections of one type when regenerating a single section
+    let sections = match args.section {
+        Some(ref section_type) => {
+            let sections: Vec<_> = page
+                .sections
+                .iter()
+                .filter(|s| s.section_type == *section_type)
+                .collect();
+            if sections.is_empty() {
+                bail!("Pattern has no '{section_type}' section");
+            }
+            Some(sections)
+        }
+        None => None,
+    };
+
+    // Refuse to clobber generated files that were edited by hand
+    let modified: Vec<_> = lock
+        .modified_files(page_name, &config.path, &page)
+        .into_iter()
+        .filter(|path| {
+            sections
+                .as_ref()
+                .is_none_or(|sections| sections.iter().any(|s| s.path == *path))
+        })
+        .collect();
+    if !modified.is_empty() {
+        if !args.json {
+            for path in &modified {
+                let relative = path.strip_prefix(&config.path).unwrap_or(path);
+                println!("  {} {relative}", styler.dim().apply_to("modified"));
+            }
+        }
+        if !args.force {
+            bail!("Generated files were edited since the last run. Use --force to overwrite them.");
+        }
+        if !args.json {
+            styler.print_warning("Overwriting local edits (--force)");
+        }
+    }
+
+    if args.dry_run {
+        let mut vfs = VirtualFs::new(&config.path);
+        match sections {
+            Some(ref sections) => sections.iter().for_each(|s| vfs.write(&s.path, &s.content)),
+            None => generator.stage_page(&page, &mut vfs),
+        }
+        if args.pattern.is_some() {
+            lock.record(
+                page_name,
+                lock_entry(&config, &page, &options),
+                args.section.as_deref(),
+            );
+            lock.stage(&config.path, &mut vfs)?;
+        }
+        let missing = inspection.missing_dependencies(&page.dependencies);
+        let commands = stage_missing(&inspection, &missing, args.install, &mut vfs)?;
+        return report_dry_run(&vfs, commands, args.json, styler);
+    }
+
+    if let (Some(section_type), Some(sections)) = (&args.section, sections) {
+        for section in sections {
+            generator.write_section(section)?;
+            println!("  {} {}", styler.dim().apply_to("wrote"), section.path);
+        }
+        record_lock(
+            &mut lock,
+            page_name,
+            &config,
+            &page,
+            &options,
+            Some(section_type),
+        )?;
+        println!();
+        styler.print_success(&format!("Section '{section_type}' regenerated"));
+        return Ok(());
+    }
+
     // Write the page
     let spinner = styler.spinner("Writing page...");
     generator.write_page(&page)?;
     spinner.finish_with_message(format!("Wrote {}", page.path));
+    for section in &page.sections {
+        println!("  {} {}", styler.dim().apply_to("wrote"), section.path);
+    }
 
-    // Report any dependencies that need to be installed
-    if !page.dependencies.is_empty() {
-        println!();
-        styler.print_warning("Additional dependencies may be required:");
-        for dep in &page.dependencies {
-            println!("  - {dep}");
+    if args.pattern.is_some() {
+        record_lock(&mut lock, page_name, &config, &page, &options, None)?;
+    }
+
+    print_unmapped_icons(&page.unmapped_icons, styler);
+    let missing = inspection.missing_dependencies(&page.dependencies);
+    resolve_dependencies(&inspection, &missing, args.install, styler)?;
+
+    println!();
+    styler.print_success(&format!("Page '{page_name}' generated!"));
+
+    Ok(())
+}
+
+/// Generate all pages of a site plan
+async fn generate_site(
+    site_path: &Utf8Path,
+    inspection: &ProjectInspection,
+    config: &ProjectConfig,
+    options: &GenerateOptions,
+    app_config: &Config,
+    args: &GenerateArgs,
+    styler: &Styler,
+) -> Result<()> {
+    let plan = SitePlan::from_file(site_path)?;
+    let preset = args.preset.as_deref().or(plan.preset.as_deref());
+
+    if !args.json {
+        print_site_header(site_path, &plan, preset, config, options, styler);
+    }
+
+    // One preset for the whole site keeps variants consistent across pages
+    let variant_preferences = match preset {
+        Some(name) => {
+            let mut presets = PresetLoader::new()?;
+            presets.set_stack(vec![name.to_string()])?;
+            presets.merged_variant_preferences()
         }
-        println!();
+        None => std::collections::HashMap::new(),
+    };
 
-        let add_cmd = config.package_manager.add_cmd(
-            &page
-                .dependencies
-                .iter()
-                .map(String::as_str)
-                .collect::<Vec<_>>(),
-            false,
+    let spinner = styler.spinner("Building recipes...");
+    let patterns = PatternLoader::new()?;
+    let recipes = plan.build_recipes(&patterns, &variant_preferences)?;
+    spinner.finish_with_message(format!("Built {} page recipes", recipes.len()));
+
+    let generator = pattern_generator(config)?;
+    let options = GenerateOptions {
+        preset: preset.map(str::to_string),
+        icons: icon_options(app_config, preset, args.icons)?,
+        ..options.clone()
+    };
+    if !args.json {
+        print_placeholder_notice(&generator, styler);
+    }
+
+    let spinner = styler.spinner("Assembling pages...");
+    let site = if generator.has_fetcher() {
+        generator
+            .generate_site_async(&plan, &recipes, config, &options)
+            .await
+            .map_err(v4_only_hint)?
+    } else {
+        generator.generate_site(&plan, &recipes, config, &options)?
+    };
+    spinner.finish_with_message(format!("Assembled {} pages", site.pages.len()));
+
+    let missing = inspection.missing_dependencies(&site.dependencies);
+
+    // Shared files replace project files such as App.tsx, so only clobber
+    // ones we wrote; pages and their sections only if unedited since
+    let mut lock = GenerateLock::load(&config.path)?;
+    let unlocked = lock.unlocked_files(&config.path, site.shared.iter().map(|f| &f.path));
+    let modified: Vec<_> = site
+        .pages
+        .iter()
+        .flat_map(|page| lock.modified_files(&page.name, &config.path, page))
+        .collect();
+    if !unlocked.is_empty() || !modified.is_empty() {
+        if !args.json {
+            for path in &unlocked {
+                let relative = path.strip_prefix(&config.path).unwrap_or(path);
+                println!("  {} {relative}", styler.dim().apply_to("exists"));
+            }
+            for path in &modified {
+                let relative = path.strip_prefix(&config.path).unwrap_or(path);
+                println!("  {} {relative}", styler.dim().apply_to("modified"));
+            }
+        }
+        if !args.force {
+            bail!(
+                "The site would replace files draftkit did not generate or that were edited since. Use --force to overwrite them."
+            );
+        }
+        if !args.json {
+            styler.print_warning("Overwriting existing files (--force)");
+        }
+    }
+    for file in &site.shared {
+        lock.record_file(&config.path, &file.path, &file.content);
+    }
+    for (page, site_page) in site.pages.iter().zip(&recipes) {
+        let entry = LockedPage::from_generated(
+            &config.path,
+            page,
+            site_page.page.pattern.clone(),
+            options.preset.clone(),
+            options.mode,
         );
-        println!("Run: {}", add_cmd.join(" "));
+        lock.record(&page.name, entry, None);
+    }
+
+    if args.dry_run {
+        let mut vfs = VirtualFs::new(&config.path);
+        for file in site.files() {
+            generator.stage_page(file, &mut vfs);
+        }
+        lock.stage(&config.path, &mut vfs)?;
+        let commands = stage_missing(inspection, &missing, args.install, &mut vfs)?;
+        return report_dry_run(&vfs, commands, args.json, styler);
+    }
+
+    for file in site.files() {
+        generator.write_page(file)?;
+        for path in std::iter::once(&file.path).chain(file.sections.iter().map(|s| &s.path)) {
+            let relative = path.strip_prefix(&config.path).unwrap_or(path);
+            println!("  {} {relative}", styler.dim().apply_to("wrote"));
+        }
+    }
+    lock.save(&config.path)?;
+
+    let mut unmapped: Vec<String> = site
+        .files()
+        .flat_map(|f| f.unmapped_icons.iter().cloned())
+        .collect();
+    unmapped.sort();
+    unmapped.dedup();
+    print_unmapped_icons(&unmapped, styler);
+
+    resolve_dependencies(inspection, &missing, args.install, styler)?;
+
+    println!();
+    styler.print_success(&format!("Site generated ({} pages)", site.pages.len()));
+
+    Ok(())
+}
+
+/// Print the summary shown before generating a site
+fn print_site_header(
+    site_path: &Utf8Path,
+    plan: &SitePlan,
+    preset: Option<&str>,
+    config: &ProjectConfig,
+    options: &GenerateOptions,
+    styler: &Styler,
+) {
+    styler.print_header("Generating site");
+    println!();
+    const KV_WIDTH: usize = 12;
+    styler.print_kv("Site", site_path.as_str(), KV_WIDTH);
+    styler.print_kv("Pages", &plan.pages.len().to_string(), KV_WIDTH);
+    styler.print_kv("Framework", config.framework.as_str(), KV_WIDTH);
+    if let Some(preset) = preset {
+        styler.print_kv("Preset", preset, KV_WIDTH);
+    }
+    styler.print_kv("Mode", options.mode.as_str(), KV_WIDTH);
+    println!();
+}
+
+/// Print or serialize the writes staged by a dry run
+pub(crate) fn report_dry_run(
+    vfs: &VirtualFs,
+    commands: Vec<Vec<String>>,
+    json: bool,
+    styler: &Styler,
+) -> Result<()> {
+    let plan = commands
+        .into_iter()
+        .fold(vfs.plan(), draftkit_core::WritePlan::with_command);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&plan)?);
+    } else {
+        styler.print_plan(&vfs.tree(), &plan);
+    }
+    Ok(())
+}
+
+/// Icon settings from config, then the preset, then `--icons`
+pub(crate) fn icon_options(
+    app_config: &Config,
+    preset: Option<&str>,
+    library: Option<IconLibrary>,
+) -> Result<IconOptions> {
+    let mut icons = app_config.icons.clone();
+    if let Some(name) = preset {
+        let mut presets = PresetLoader::new()?;
+        presets.set_stack(vec![name.to_string()])?;
+        icons.merge(&presets.merged_icon_options());
+    }
+    if library.is_some() {
+        icons.library = library;
+    }
+    Ok(icons)
+}
+
+/// Warn about Heroicons the icon library has no mapping for
+pub(crate) fn print_unmapped_icons(unmapped: &[String], styler: &Styler) {
+    if unmapped.is_empty() {
+        return;
+    }
+    println!();
+    styler.print_warning("No icon mapping for (kept as Heroicons):");
+    for icon in unmapped {
+        println!("  - {icon}");
+    }
+    println!("Map them under [icons.mappings] in your draftkit config or preset.");
+}
+
+/// Install `missing`, or declare it in `package.json` for the next install
+fn resolve_dependencies(
+    inspection: &ProjectInspection,
+    missing: &[MissingPackage],
+    install: bool,
+    styler: &Styler,
+) -> Result<()> {
+    if missing.is_empty() {
+        return Ok(());
     }
 
     println!();
-    styler.print_success(&format!("Page '{}' generated!", args.page_name));
+    if install {
+        return install_packages(inspection, missing, styler);
+    }
 
+    let mut vfs = VirtualFs::new(&inspection.root);
+    inspection.stage_dependencies(missing, &mut vfs)?;
+    vfs.apply()?;
+    styler.print_info("Added to package.json:");
+    for package in missing {
+        println!("  - {}", package.spec());
+    }
+    println!();
+    println!(
+        "Run: {} (or pass --install)",
+        inspection.package_manager.install_cmd().join(" ")
+    );
     Ok(())
 }
 
-/// Generate a page from a pattern
-fn generate_from_pattern(
+/// Stage a dry run's dependency changes: the install commands with
+/// `install`, otherwise the `package.json` update
+fn stage_missing(
+    inspection: &ProjectInspection,
+    missing: &[MissingPackage],
+    install: bool,
+    vfs: &mut VirtualFs,
+) -> Result<Vec<Vec<String>>> {
+    if install {
+        return Ok(install_commands(inspection, missing));
+    }
+    inspection.stage_dependencies(missing, vfs)?;
+    Ok(vec![])
+}
+
+/// Package manager commands that add the missing packages, run from the
+/// workspace root in monorepos
+pub(crate) fn install_commands(
+    inspection: &ProjectInspection,
+    missing: &[MissingPackage],
+) -> Vec<Vec<String>> {
+    [false, true]
+        .into_iter()
+        .filter_map(|dev| {
+            let specs: Vec<String> = missing
+                .iter()
+                .filter(|p| p.dev == dev)
+                .map(MissingPackage::spec)
+                .collect();
+            let specs: Vec<&str> = specs.iter().map(String::as_str).collect();
+            (!specs.is_empty()).then(|| inspection.add_cmd(&specs, dev))
+        })
+        .collect()
+}
+
+/// Install missing packages with the project's package manager
+pub(crate) fn install_packages(
+    inspection: &ProjectInspection,
+    missing: &[MissingPackage],
+    styler: &Styler,
+) -> Result<()> {
+    for add_cmd in install_commands(inspection, missing) {
+        let spinner = styler.spinner(&format!("Running {}...", add_cmd.join(" ")));
+        let status = Command::new(&add_cmd[0])
+            .args(&add_cmd[1..])
+            .current_dir(inspection.command_dir())
+            .status()
+            .context("Failed to run package manager")?;
+
+        if !status.success() {
+            spinner.finish_with_message("Install failed");
+            bail!("Run manually: {}", add_cmd.join(" "));
+        }
+        spinner.finish_with_message("Packages installed");
+    }
+    Ok(())
+}
+
+/// Warn about sections whose components need Tailwind v4 in a v3 project
+pub(crate) fn print_tailwind_conflicts(
     generator: &PageGenerator,
+    recipe: &Recipe,
     config: &ProjectConfig,
-    pattern_id: &str,
     options: &GenerateOptions,
     styler: &Styler,
-) -> Result<draftkit_core::GeneratedPage> {
+) {
+    const MAX_ALTERNATIVES: usize = 3;
+
+    for conflict in generator.tailwind_conflicts(recipe, config, options) {
+        styler.print_warning(&format!(
+            "Section '{}' matches '{}', which uses Tailwind v4-only features: {}",
+            conflict.section_type,
+            conflict.component_id,
+            conflict.v4_only.join(", ")
+        ));
+        if conflict.alternatives.is_empty() {
+            println!("  No v3-compatible alternative in the same category");
+        } else {
+            let shown: Vec<&str> = conflict
+                .alternatives
+                .iter()
+                .take(MAX_ALTERNATIVES)
+                .map(String::as_str)
+                .collect();
+            println!("  v3-compatible alternatives: {}", shown.join(", "));
+        }
+    }
+}
+
+/// Generator for pattern pages: backed by TailwindPlus components when a
+/// session is available, otherwise limited to placeholder sections
+pub(crate) fn pattern_generator(config: &ProjectConfig) -> Result<PageGenerator> {
+    Ok(match auth::get_session()? {
+        Some(session) if !session.is_expired() => PageGenerator::with_fetcher(
+            ComponentFetcher::new(session.cookie).with_tailwind_version(config.tailwind_version),
+        ),
+        _ => PageGenerator::new(),
+    })
+}
+
+/// Build a pattern page with real components if the generator can fetch
+/// them, placeholder sections otherwise
+pub(crate) async fn generate_pattern_page(
+    generator: &PageGenerator,
+    recipe: &Recipe,
+    config: &ProjectConfig,
+    options: &GenerateOptions,
+) -> Result<GeneratedPage> {
+    let page = if generator.has_fetcher() {
+        generator
+            .generate_from_recipe_async(recipe, config, options)
+            .await
+            .map_err(v4_only_hint)?
+    } else {
+        generator.generate_from_recipe(recipe, config, options)?
+    };
+    Ok(page)
+}
+
+/// Point at the way around a v4-only component in a v3 project
+fn v4_only_hint(error: GenerateError) -> anyhow::Error {
+    match error {
+        e @ GenerateError::TailwindIncompatible(_) => anyhow::anyhow!(
+            "{e}. Choose a v3-compatible variant, or pass --allow-v4-only to use it anyway."
+        ),
+        e => e.into(),
+    }
+}
+
+/// Explain that sections are placeholders when not signed in
+pub(crate) fn print_placeholder_notice(generator: &PageGenerator, styler: &Styler) {
+    if !generator.has_fetcher() {
+        styler.print_info(
+            "Not signed in to TailwindPlus: sections are placeholders. Run `draftkit auth` to use real components.",
+        );
+    }
+}
+
+/// Record a written page in the project lockfile
+pub(crate) fn record_lock(
+    lock: &mut GenerateLock,
+    page_name: &str,
+    config: &ProjectConfig,
+    page: &GeneratedPage,
+    options: &GenerateOptions,
+    only: Option<&str>,
+) -> Result<()> {
+    lock.record(page_name, lock_entry(config, page, options), only);
+    lock.save(&config.path)?;
+    Ok(())
+}
+
+/// Lockfile entry for a generated page
+pub(crate) fn lock_entry(
+    config: &ProjectConfig,
+    page: &GeneratedPage,
+    options: &GenerateOptions,
+) -> LockedPage {
+    LockedPage::from_generated(
+        &config.path,
+        page,
+        options.pattern.clone(),
+        options.preset.clone(),
+        options.mode,
+    )
+}
+
+/// Load a pattern and build its recipe
+pub(crate) fn pattern_recipe(pattern_id: &str, styler: &Styler) -> Result<Recipe> {
     let spinner = styler.spinner(&format!("Loading pattern '{pattern_id}'..."));
 
     // Load the pattern
@@ -177,92 +764,18 @@ fn generate_from_pattern(
         recipe.coherence.score
     ));
 
-    // Generate page from recipe
-    let spinner = styler.spinner("Assembling page...");
-    let page = generator.generate_from_recipe(&recipe, config, options)?;
-    spinner.finish_with_message("Page assembled");
-
-    Ok(page)
-}
-
-/// Detect project configuration from the current directory
-fn detect_project_config(project_path: &Utf8Path) -> Result<ProjectConfig> {
-    // Check for package.json
-    let package_json = project_path.join("package.json");
-    if !package_json.exists() {
-        bail!("No package.json found. Run 'draftkit init' first or cd into a project directory.");
-    }
-
-    // Read package.json to get project name
-    let content = std::fs::read_to_string(&package_json)?;
-    let json: serde_json::Value = serde_json::from_str(&content)?;
-    let name = json["name"].as_str().unwrap_or("project").to_string();
-
-    // Detect framework from project structure
-    let framework = detect_framework(project_path);
-
-    // Detect package manager from lockfile
-    let package_manager = PackageManager::detect(project_path, None);
-
-    // Detect Tailwind version from package.json
-    let tailwind_version = detect_tailwind_version(&json);
-
-    // Build a config that points to the existing project
-    let mut config = ProjectConfig::new(&name, project_path.parent().unwrap_or(project_path));
-    config.path = project_path.to_path_buf();
-    config.framework = framework;
-    config.package_manager = package_manager;
-    config.tailwind_version = tailwind_version;
-
-    Ok(config)
+    Ok(recipe)
 }
 
-/// Detect the framework from project structure
-fn detect_framework(project_path: &Utf8Path) -> FrameworkTarget {
-    // Check for Next.js
-    if project_path.join("next.config.js").exists()
-        || project_path.join("next.config.ts").exists()
-        || project_path.join("next.config.mjs").exists()
-    {
-        return FrameworkTarget::NextJs;
-    }
-
-    // Check for Vite
-    if project_path.join("vite.config.ts").exists() || project_path.join("vite.config.js").exists()
-    {
-        return FrameworkTarget::ViteReact;
+/// Inspect the project at `project_path`
+fn inspect_project(project_path: &Utf8Path) -> Result<ProjectInspection> {
+    match ProjectInspection::inspect(project_path) {
+        Ok(inspection) => Ok(inspection),
+        Err(GenerateError::NotAProject(_)) => bail!(
+            "No package.json found. Run 'draftkit init' first or cd into a project directory."
+        ),
+        Err(e) => Err(e.into()),
     }
-
-    // Check for src/App.tsx (React)
-    if project_path.join("src/App.tsx").exists() || project_path.join("src/App.jsx").exists() {
-        return FrameworkTarget::ViteReact;
-    }
-
-    // Default to HTML for simple projects
-    FrameworkTarget::Html
-}
-
-/// Detect Tailwind version from package.json dependencies
-fn detect_tailwind_version(json: &serde_json::Value) -> TailwindVersion {
-    let deps = json
-        .get("devDependencies")
-        .or_else(|| json.get("dependencies"));
-
-    if let Some(deps) = deps
-        && let Some(version) = deps.get("tailwindcss").and_then(|v| v.as_str())
-    {
-        // Check if version starts with ^4, ~4, 4, etc.
-        let version_clean = version.trim_start_matches(['^', '~', '>', '=', '<', ' ']);
-        if version_clean.starts_with('4') {
-            return TailwindVersion::V4;
-        }
-        if version_clean.starts_with('3') {
-            return TailwindVersion::V3;
-        }
-    }
-
-    // Default to v4
-    TailwindVersion::V4
 }
 
 #[cfg(test)]
@@ -281,30 +794,4 @@ mod tests {
     fn parse_mode_invalid() {
         assert!(parse_mode("invalid").is_err());
     }
-
-    #[test]
-    fn detect_tailwind_v4() {
-        let json: serde_json::Value = serde_json::json!({
-            "devDependencies": {
-                "tailwindcss": "^4.0.0"
-            }
-        });
-        assert_eq!(detect_tailwind_version(&json), TailwindVersion::V4);
-    }
-
-    #[test]
-    fn detect_tailwind_v3() {
-        let json: serde_json::Value = serde_json::json!({
-            "devDependencies": {
-                "tailwindcss": "^3.4.0"
-            }
-        });
-        assert_eq!(detect_tailwind_version(&json), TailwindVersion::V3);
-    }
-
-    #[test]
-    fn detect_tailwind_default() {
-        let json: serde_json::Value = serde_json::json!({});
-        assert_eq!(detect_tailwind_version(&json), TailwindVersion::V4);
-    }
 }
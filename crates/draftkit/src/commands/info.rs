@@ -1,7 +1,8 @@
 //! Info command implementation
 
 use clap::Args;
-use draftkit_core::{ComponentReader, Framework, get_manifest, manifest_source};
+use draftkit_core::profile::active_profile;
+use draftkit_core::{ComponentReader, Config, Framework, get_manifest, manifest_source};
 use serde::Serialize;
 
 #[derive(Args)]
@@ -32,6 +33,7 @@ impl PackageInfo {
 #[derive(Serialize)]
 struct EmbeddedDataInfo {
     source: String,
+    profile: String,
     licensed_to: String,
     download_date: String,
     tailwind_version: String,
@@ -56,7 +58,7 @@ struct FullInfo {
     embedded_data: Option<EmbeddedDataInfo>,
 }
 
-fn get_embedded_data_info() -> Option<EmbeddedDataInfo> {
+fn get_embedded_data_info(config: &Config) -> Option<EmbeddedDataInfo> {
     let manifest = get_manifest()?;
     let source = manifest_source();
     let reader = ComponentReader::new();
@@ -67,7 +69,12 @@ fn get_embedded_data_info() -> Option<EmbeddedDataInfo> {
 
     Some(EmbeddedDataInfo {
         source: source.to_string(),
-        licensed_to: manifest.downloaded_by.clone(),
+        profile: active_profile().to_string(),
+        licensed_to: config
+            .profiles
+            .get(active_profile())
+            .and_then(|p| p.licensed_to.clone())
+            .unwrap_or_else(|| manifest.licensed_to().to_string()),
         download_date: manifest.download_date().to_string(),
         tailwind_version: manifest.versions.tailwind.clone(),
         elements_version: manifest.versions.elements.clone(),
@@ -82,9 +89,9 @@ fn get_embedded_data_info() -> Option<EmbeddedDataInfo> {
 }
 
 /// Print package information
-pub fn cmd_info(args: InfoArgs) -> anyhow::Result<()> {
+pub fn cmd_info(args: InfoArgs, config: &Config) -> anyhow::Result<()> {
     let package = PackageInfo::new();
-    let embedded_data = get_embedded_data_info();
+    let embedded_data = get_embedded_data_info(config);
 
     if args.json {
         let full_info = FullInfo {
@@ -101,6 +108,7 @@ pub fn cmd_info(args: InfoArgs) -> anyhow::Result<()> {
 
         if let Some(data) = embedded_data {
             println!("Data (source: {}):", data.source);
+            println!("  Profile:              {}", data.profile);
             println!("  TailwindPlus Account: {}", data.licensed_to);
             println!("  Download date:        {}", data.download_date);
             println!("  Tailwind:             v{}", data.tailwind_version);
@@ -127,11 +135,11 @@ mod tests {
 
     #[test]
     fn test_cmd_info_text_succeeds() {
-        assert!(cmd_info(InfoArgs { json: false }).is_ok());
+        assert!(cmd_info(InfoArgs { json: false }, &Config::default()).is_ok());
     }
 
     #[test]
     fn test_cmd_info_json_succeeds() {
-        assert!(cmd_info(InfoArgs { json: true }).is_ok());
+        assert!(cmd_info(InfoArgs { json: true }, &Config::default()).is_ok());
     }
 }
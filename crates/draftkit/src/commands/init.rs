@@ -1,21 +1,31 @@
 //! Init command implementation
 //!
 //! Scaffolds a new project with Tailwind CSS and optional pattern generation.
+//! Inside a monorepo, the project joins the enclosing workspace and installs
+//! from its root.
 //!
 //! ```bash
 //! draftkit init my-site --framework vite-react --pattern saas-landing
+//! draftkit init my-site --template acme --var brand_color=#e11d48
 //! ```
 
+use std::collections::HashMap;
+use std::io::IsTerminal;
 use std::process::Command;
 
 use anyhow::{Context, Result, bail};
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use clap::Args;
+use dialoguer::Input;
 use draftkit_core::{
-    FrameworkTarget, PackageManager, ProjectConfig, TailwindVersion, TemplateEngine,
-    intelligence::PatternMatcher, patterns::PatternLoader,
+    FrameworkTarget, GeneratedPage, PackageManager, PageGenerator, ProjectConfig, TailwindVersion,
+    TemplateEngine, VirtualFs,
+    intelligence::PatternMatcher,
+    patterns::PatternLoader,
+    scaffold::{TemplatePack, Workspace},
 };
 
+use super::generate::report_dry_run;
 use crate::cli::Styler;
 
 #[derive(Args)]
@@ -23,9 +33,9 @@ pub struct InitArgs {
     /// Project name (directory name)
     pub name: String,
 
-    /// Target framework
-    #[arg(long, short, default_value = "vite-react", value_parser = parse_framework)]
-    pub framework: FrameworkTarget,
+    /// Target framework (default: vite-react, or the one the template pack is built on)
+    #[arg(long, short, value_parser = parse_framework)]
+    pub framework: Option<FrameworkTarget>,
 
     /// Package manager (auto-detect, npm, pnpm, yarn, bun)
     #[arg(long, short = 'm', value_parser = parse_package_manager)]
@@ -43,6 +53,14 @@ pub struct InitArgs {
     #[arg(long, default_value = "v4", value_parser = parse_tailwind_version)]
     pub tailwind: TailwindVersion,
 
+    /// Layer a template pack from .draftkit/templates/ or ~/.config/draftkit/templates/
+    #[arg(long)]
+    pub template: Option<String>,
+
+    /// Set a template pack variable (repeatable)
+    #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var, requires = "template")]
+    pub vars: Vec<(String, String)>,
+
     /// Skip running package install
     #[arg(long)]
     pub skip_install: bool,
@@ -50,12 +68,20 @@ pub struct InitArgs {
     /// Accept defaults non-interactively
     #[arg(long, short)]
     pub yes: bool,
+
+    /// Preview the files that would be created without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Describe the planned writes as JSON
+    #[arg(long, requires = "dry_run")]
+    pub json: bool,
 }
 
 fn parse_framework(s: &str) -> Result<FrameworkTarget, String> {
     FrameworkTarget::parse(s).ok_or_else(|| {
         format!(
-            "Unknown framework '{}'. Valid options: html, vite-react, nextjs",
+            "Unknown framework '{}'. Valid options: html, vite-react, vite-vue, nextjs, nuxt, react-router, astro",
             s
         )
     })
@@ -75,6 +101,13 @@ fn parse_tailwind_version(s: &str) -> Result<TailwindVersion, String> {
         .ok_or_else(|| format!("Unknown Tailwind version '{}'. Valid options: v3, v4", s))
 }
 
+fn parse_var(s: &str) -> Result<(String, String), String> {
+    s.split_once('=')
+        .filter(|(key, _)| !key.is_empty())
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .ok_or_else(|| format!("Expected KEY=VALUE, got '{s}'"))
+}
+
 /// Initialize a new project
 pub fn cmd_init(args: InitArgs, styler: &Styler) -> Result<()> {
     // Determine base directory (current working directory)
@@ -94,26 +127,50 @@ pub fn cmd_init(args: InitArgs, styler: &Styler) -> Result<()> {
     let package_manager = args
         .package_manager
         .unwrap_or_else(|| PackageManager::detect(&base_dir, None));
-
-    styler.print_header("Creating new project");
-    println!();
-    const KV_WIDTH: usize = 16;
-    styler.print_kv("Name", &args.name, KV_WIDTH);
-    styler.print_kv("Framework", args.framework.as_str(), KV_WIDTH);
-    styler.print_kv("Package Manager", package_manager.as_str(), KV_WIDTH);
-    styler.print_kv("Tailwind", args.tailwind.as_str(), KV_WIDTH);
-
-    if let Some(ref pattern) = args.pattern {
-        styler.print_kv("Pattern", pattern, KV_WIDTH);
-    }
-    if let Some(ref preset) = args.preset {
-        styler.print_kv("Preset", preset, KV_WIDTH);
+    let workspace = Workspace::find(&base_dir);
+
+    let pack = args
+        .template
+        .as_deref()
+        .map(|name| TemplatePack::find(name, &base_dir))
+        .transpose()?;
+    let framework = match (args.framework, pack.as_ref().and_then(|p| p.framework)) {
+        (Some(chosen), Some(required)) if chosen != required => bail!(
+            "Template '{}' is built on {}, not {}",
+            args.template.as_deref().unwrap_or_default(),
+            required.as_str(),
+            chosen.as_str()
+        ),
+        (chosen, required) => chosen.or(required).unwrap_or_default(),
+    };
+
+    if !args.json {
+        styler.print_header("Creating new project");
+        println!();
+        const KV_WIDTH: usize = 16;
+        styler.print_kv("Name", &args.name, KV_WIDTH);
+        styler.print_kv("Framework", framework.as_str(), KV_WIDTH);
+        styler.print_kv("Package Manager", package_manager.as_str(), KV_WIDTH);
+        styler.print_kv("Tailwind", args.tailwind.as_str(), KV_WIDTH);
+
+        if let Some(ref pattern) = args.pattern {
+            styler.print_kv("Pattern", pattern, KV_WIDTH);
+        }
+        if let Some(ref preset) = args.preset {
+            styler.print_kv("Preset", preset, KV_WIDTH);
+        }
+        if let Some(ref pack) = pack {
+            styler.print_kv("Template", &pack.name, KV_WIDTH);
+        }
+        if let Some(ref workspace) = workspace {
+            styler.print_kv("Workspace", workspace.root.as_str(), KV_WIDTH);
+        }
+        println!();
     }
-    println!();
 
     // Build project configuration
     let mut config = ProjectConfig::new(&args.name, &base_dir)
-        .with_framework(args.framework)
+        .with_framework(framework)
         .with_package_manager(package_manager)
         .with_tailwind_version(args.tailwind);
 
@@ -127,20 +184,81 @@ pub fn cmd_init(args: InitArgs, styler: &Styler) -> Result<()> {
         config = config.skip_install();
     }
 
+    let mut engine = TemplateEngine::from_config(&config);
+    if let Some(ref pack) = pack {
+        // Prompt only when someone is there to answer
+        let interactive = !args.yes && !args.json && std::io::stdin().is_terminal();
+        let provided: HashMap<String, String> = args.vars.iter().cloned().collect();
+        let values = pack.resolve_variables(&provided, |variable| {
+            if !interactive {
+                return Ok(None);
+            }
+            let mut input = Input::<String>::new().with_prompt(variable.prompt());
+            if let Some(ref default) = variable.default {
+                input = input.default(default.clone());
+            }
+            Ok(Some(input.interact_text()?))
+        })?;
+        engine.add_pack(pack, values);
+    }
+    let generator = PageGenerator::new();
+
+    if args.dry_run {
+        let mut vfs = VirtualFs::new(workspace.as_ref().map_or(&config.path, |w| &w.root));
+        engine.stage(&config, &mut vfs)?;
+        if let Some(ref workspace) = workspace {
+            workspace.stage_member(&config.path, &mut vfs)?;
+        }
+        if let Some(ref pattern_id) = args.pattern {
+            let page = initial_page(&generator, &config, pattern_id, styler)?;
+            generator.stage_page(&page, &mut vfs);
+        }
+        let commands = if args.skip_install {
+            vec![]
+        } else {
+            vec![
+                package_manager
+                    .install_cmd()
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+            ]
+        };
+        return report_dry_run(&vfs, commands, args.json, styler);
+    }
+
     // Scaffold the project
     let spinner = styler.spinner("Scaffolding project...");
-    let engine = TemplateEngine::from_config(&config);
     let created_files = engine.scaffold(&config)?;
     spinner.finish_with_message(format!("Created {} files", created_files.len()));
 
     // Generate initial page from pattern if specified
     if let Some(ref pattern_id) = args.pattern {
-        generate_initial_page(&config, pattern_id, styler)?;
+        let page = initial_page(&generator, &config, pattern_id, styler)?;
+        generator.write_page(&page)?;
     }
 
+    // Join the enclosing workspace, installing from its root
+    let install_dir = match workspace {
+        Some(ref workspace) => {
+            let mut vfs = VirtualFs::new(&workspace.root);
+            let member = workspace.member_path(&config.path).unwrap_or_default();
+            if workspace.stage_member(&config.path, &mut vfs)? {
+                vfs.apply()?;
+                styler.print_info(&format!("Added {member} to the workspace packages"));
+            } else if !workspace.includes(&config.path) {
+                styler.print_warning(&format!(
+                    "Found no workspace package list to add {member} to; add it yourself"
+                ));
+            }
+            workspace.root.clone()
+        }
+        None => config.path.clone(),
+    };
+
     // Run package install unless skipped
     if !args.skip_install {
-        run_install(&config, styler)?;
+        run_install(&config, &install_dir, styler)?;
     }
 
     // Print success and next steps
@@ -152,14 +270,18 @@ pub fn cmd_init(args: InitArgs, styler: &Styler) -> Result<()> {
 
     if args.skip_install {
         let install_cmd = package_manager.install_cmd().join(" ");
-        println!("  {}", install_cmd);
+        if workspace.is_some() {
+            println!("  {} (from {install_dir})", install_cmd);
+        } else {
+            println!("  {}", install_cmd);
+        }
     }
 
     let dev_cmd = package_manager.dev_cmd().join(" ");
     println!("  {}", dev_cmd);
     println!();
 
-    let port = args.framework.default_port();
+    let port = framework.default_port();
     styler.print_info(&format!(
         "Your site will be available at http://localhost:{port}"
     ));
@@ -168,7 +290,12 @@ pub fn cmd_init(args: InitArgs, styler: &Styler) -> Result<()> {
 }
 
 /// Generate the initial page from a pattern
-fn generate_initial_page(config: &ProjectConfig, pattern_id: &str, styler: &Styler) -> Result<()> {
+fn initial_page(
+    generator: &PageGenerator,
+    config: &ProjectConfig,
+    pattern_id: &str,
+    styler: &Styler,
+) -> Result<GeneratedPage> {
     let spinner = styler.spinner(&format!("Generating page from '{pattern_id}' pattern..."));
 
     // Load the pattern
@@ -182,22 +309,18 @@ fn generate_initial_page(config: &ProjectConfig, pattern_id: &str, styler: &Styl
     let recipe = matcher.generate_recipe(&loaded_pattern.pattern, &Default::default());
 
     // Generate page content
-    let generator = draftkit_core::PageGenerator::new();
     let page = generator.generate_from_recipe(&recipe, config, &Default::default())?;
 
-    // Write the page
-    generator.write_page(&page)?;
-
     spinner.finish_with_message(format!(
         "Generated page with {} sections",
         recipe.sections.len()
     ));
 
-    Ok(())
+    Ok(page)
 }
 
-/// Run package manager install
-fn run_install(config: &ProjectConfig, styler: &Styler) -> Result<()> {
+/// Run package manager install in `dir`
+fn run_install(config: &ProjectConfig, dir: &Utf8Path, styler: &Styler) -> Result<()> {
     let spinner = styler.spinner(&format!(
         "Installing dependencies with {}...",
         config.package_manager
@@ -206,7 +329,7 @@ fn run_install(config: &ProjectConfig, styler: &Styler) -> Result<()> {
     let install_cmd = config.package_manager.install_cmd();
     let status = Command::new(install_cmd[0])
         .args(&install_cmd[1..])
-        .current_dir(&config.path)
+        .current_dir(dir)
         .status()
         .context("Failed to run package manager")?;
 
@@ -245,6 +368,16 @@ mod tests {
         assert!(parse_framework("invalid").is_err());
     }
 
+    #[test]
+    fn parse_var_splits_on_first_equals() {
+        assert_eq!(
+            parse_var("brand=a=b").unwrap(),
+            ("brand".to_string(), "a=b".to_string())
+        );
+        assert!(parse_var("brand").is_err());
+        assert!(parse_var("=value").is_err());
+    }
+
     #[test]
     fn parse_package_manager_valid() {
         assert_eq!(parse_package_manager("npm").unwrap(), PackageManager::Npm);
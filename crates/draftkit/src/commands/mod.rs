@@ -1,9 +1,11 @@
 //! Command implementations
 
+pub mod add;
 pub mod auth;
 pub mod cache;
 pub mod generate;
 pub mod info;
 pub mod init;
+pub mod patterns;
 pub mod presets;
 pub mod serve;
@@ -0,0 +1,160 @@
+//! Patterns command implementation
+//!
+//! Tools for working with page patterns. `infer` turns an existing React or
+//! HTML page into a reusable pattern file.
+
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::{Args, Subcommand};
+use draftkit_core::patterns::infer_pattern;
+use draftkit_core::slug::slugify;
+
+use crate::cli::Styler;
+
+/// Page file stems that say nothing about the page itself.
+const GENERIC_STEMS: &[&str] = &["App", "app", "page", "index", "Index"];
+
+#[derive(Args)]
+pub struct PatternsArgs {
+    #[command(subcommand)]
+    pub command: PatternsCommand,
+}
+
+#[derive(Subcommand)]
+pub enum PatternsCommand {
+    /// Infer a pattern from an existing page or project
+    Infer(InferArgs),
+}
+
+#[derive(Args)]
+pub struct InferArgs {
+    /// Page file (.tsx, .jsx, .html) or project directory to analyze
+    pub path: Utf8PathBuf,
+
+    /// Pattern ID (default: derived from the page or project name)
+    #[arg(long)]
+    pub id: Option<String>,
+
+    /// Output directory (default: .draftkit/patterns/)
+    #[arg(long, short)]
+    pub output: Option<Utf8PathBuf>,
+
+    /// Overwrite existing file
+    #[arg(long)]
+    pub force: bool,
+
+    /// Print the pattern TOML instead of writing it
+    #[arg(long)]
+    pub stdout: bool,
+}
+
+pub fn cmd_patterns(args: PatternsArgs, styler: &Styler) -> anyhow::Result<()> {
+    match args.command {
+        PatternsCommand::Infer(infer_args) => cmd_infer(infer_args, styler),
+    }
+}
+
+fn cmd_infer(args: InferArgs, styler: &Styler) -> anyhow::Result<()> {
+    let id = args.id.unwrap_or_else(|| default_pattern_id(&args.path));
+    let inference = infer_pattern(&args.path, &id)?;
+    let toml = inference.to_toml()?;
+
+    if args.stdout {
+        print!("{toml}");
+        return Ok(());
+    }
+
+    let output_dir = args
+        .output
+        .unwrap_or_else(|| Utf8PathBuf::from(".draftkit/patterns"));
+    std::fs::create_dir_all(&output_dir)?;
+
+    let output_path = output_dir.join(format!("{id}.toml"));
+    if output_path.exists() && !args.force {
+        anyhow::bail!("File already exists: {output_path}. Use --force to overwrite.");
+    }
+
+    styler.print_header(&format!("Inferred pattern: {id}"));
+    println!();
+    styler.print_kv("Source", inference.page_path.as_str(), 10);
+    println!();
+
+    for (i, section) in inference.sections.iter().enumerate() {
+        let variant = &inference.pattern.sections[i].variants[0].id;
+        println!(
+            "  {}. {} {}",
+            i + 1,
+            styler.bold().apply_to(section.section_type.as_str()),
+            styler
+                .dim()
+                .apply_to(format!("({} → {variant})", section.name))
+        );
+        println!(
+            "     weight {:.2}  formality {:.2}  color {:.2}  spacing {:.2}",
+            section.style.visual_weight,
+            section.style.formality,
+            section.style.color_intensity,
+            section.style.spacing_density
+        );
+    }
+
+    if !inference.skipped.is_empty() {
+        println!();
+        styler.print_warning(&format!(
+            "Skipped unrecognized components: {}",
+            inference.skipped.join(", ")
+        ));
+    }
+
+    std::fs::write(&output_path, toml)?;
+
+    println!();
+    styler.print_success(&format!("Created pattern: {output_path}"));
+    styler.print_info(&format!(
+        "Generate with: draftkit generate index --pattern {id}"
+    ));
+
+    Ok(())
+}
+
+/// Derive a pattern ID from the page file name, falling back to the
+/// project directory for generic entry points like `App.tsx`.
+fn default_pattern_id(path: &Utf8Path) -> String {
+    let absolute = path
+        .canonicalize_utf8()
+        .unwrap_or_else(|_| path.to_path_buf());
+
+    let name = if absolute.is_dir() {
+        absolute.file_name()
+    } else {
+        absolute.file_stem().and_then(|stem| {
+            if GENERIC_STEMS.contains(&stem) {
+                project_dir_name(&absolute)
+            } else {
+                Some(stem)
+            }
+        })
+    };
+
+    slugify(name.unwrap_or("inferred"))
+}
+
+/// Name of the nearest ancestor directory containing a package.json.
+fn project_dir_name(page: &Utf8Path) -> Option<&str> {
+    page.ancestors()
+        .skip(1)
+        .find(|dir| dir.join("package.json").is_file())
+        .or_else(|| page.parent())
+        .and_then(Utf8Path::file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_handles_case_and_separators() {
+        assert_eq!(slugify("PricingPage"), "pricing-page");
+        assert_eq!(slugify("my_site v2"), "my-site-v2");
+        assert_eq!(slugify("landing"), "landing");
+    }
+}
@@ -120,6 +120,7 @@ struct PatternInfo {
     name: String,
     description: String,
     source: String,
+    extends: Option<String>,
     tags: Vec<String>,
     section_count: usize,
 }
@@ -172,6 +173,7 @@ fn cmd_list(args: ListArgs, styler: &Styler) -> anyhow::Result<()> {
                 name: p.pattern.name.clone(),
                 description: p.pattern.description.clone(),
                 source: format_pattern_source(p.source),
+                extends: p.pattern.extends.clone(),
                 tags: p.pattern.tags.clone(),
                 section_count: p.pattern.sections.len(),
             })
@@ -213,6 +215,9 @@ fn cmd_list(args: ListArgs, styler: &Styler) -> anyhow::Result<()> {
                 let name = styler.bold().apply_to(&p.name);
                 println!("  {name} {source_tag}");
                 println!("    ID: {}", p.id);
+                if let Some(ref extends) = p.extends {
+                    println!("    Extends: {extends}");
+                }
                 if !p.description.is_empty() {
                     println!("    {}", styler.dim().apply_to(&p.description));
                 }
@@ -3,6 +3,7 @@
 pub mod cli;
 pub mod commands;
 pub mod server;
+pub mod session;
 
 // Re-export domain types from draftkit-core for convenience
 pub use draftkit_core::{
@@ -36,10 +37,17 @@ pub struct Cli {
     /// Colorize output: auto|always|never
     #[arg(long, global = true, default_value = "auto")]
     pub color: String,
+
+    /// Profile whose session and cache to use (default: $DRAFTKIT_PROFILE)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
+    /// Add a generated page to an existing project
+    #[command(alias = "adopt")]
+    Add(commands::add::AddArgs),
     /// Authenticate with TailwindPlus
     Auth(commands::auth::AuthArgs),
     /// Manage the local component cache
@@ -50,6 +58,8 @@ pub enum Commands {
     Info(commands::info::InfoArgs),
     /// Initialize a new project
     Init(commands::init::InitArgs),
+    /// Work with page patterns
+    Patterns(commands::patterns::PatternsArgs),
     /// Manage patterns and presets
     Presets(commands::presets::PresetsArgs),
     /// Run the MCP server
@@ -114,6 +124,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_parse_add_command() {
+        let result = Cli::try_parse_from(["draftkit", "adopt", "pricing", "--pattern", "saas"]);
+        assert!(result.is_ok());
+        if let Ok(cli) = result {
+            assert!(matches!(cli.command, Commands::Add(_)));
+        }
+
+        let result = Cli::try_parse_from(["draftkit", "add", "pricing"]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn cli_parse_auth_command() {
         let result = Cli::try_parse_from(["draftkit", "auth"]);
@@ -193,4 +215,35 @@ mod tests {
         let result = Cli::try_parse_from(["draftkit", "cache", "--path"]);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn cli_parse_cache_sync() {
+        let result = Cli::try_parse_from([
+            "draftkit",
+            "cache",
+            "sync",
+            "--framework",
+            "react,vue",
+            "--mode",
+            "light",
+            "--category",
+            "marketing",
+        ]);
+        assert!(result.is_ok());
+
+        let result = Cli::try_parse_from(["draftkit", "cache", "sync", "--framework", "svelte"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cli_parse_profile() {
+        let cli = Cli::try_parse_from(["draftkit", "auth", "--profile", "client-a"]).unwrap();
+        assert_eq!(cli.profile.as_deref(), Some("client-a"));
+
+        let result = Cli::try_parse_from(["draftkit", "auth", "--list"]);
+        assert!(result.is_ok());
+
+        let result = Cli::try_parse_from(["draftkit", "auth", "--list", "--logout"]);
+        assert!(result.is_err());
+    }
 }
@@ -4,6 +4,7 @@
 use clap::Parser;
 use draftkit::{Cli, Commands, cli::Styler, commands};
 use draftkit_core::config::ConfigLoader;
+use tracing::Instrument;
 
 mod observability;
 
@@ -19,6 +20,8 @@ async fn main() -> anyhow::Result<()> {
         .with_project_search(std::env::current_dir()?)
         .load()?;
 
+    draftkit_core::profile::select_profile(cli.profile.as_deref(), config.profile.as_deref())?;
+
     let obs_config = observability::ObservabilityConfig::from_env_with_overrides(
         config.otel_endpoint.clone(),
         config
@@ -36,13 +39,27 @@ async fn main() -> anyhow::Result<()> {
     let styler = Styler::new(color_mode);
 
     let result = match cli.command {
-        Commands::Auth(args) => commands::auth::cmd_auth(args, &styler).await,
-        Commands::Cache(args) => span.in_scope(|| commands::cache::cmd_cache(args, color_mode)),
+        Commands::Add(args) => {
+            commands::add::cmd_add(args, &config, &styler)
+                .instrument(span.clone())
+                .await
+        }
+        Commands::Auth(args) => commands::auth::cmd_auth(args, &config, &styler).await,
+        Commands::Cache(args) => {
+            commands::cache::cmd_cache(args, color_mode)
+                .instrument(span.clone())
+                .await
+        }
         Commands::Generate(args) => {
-            span.in_scope(|| commands::generate::cmd_generate(args, &styler))
+            commands::generate::cmd_generate(args, &config, &styler)
+                .instrument(span.clone())
+                .await
         }
-        Commands::Info(args) => span.in_scope(|| commands::info::cmd_info(args)),
+        Commands::Info(args) => span.in_scope(|| commands::info::cmd_info(args, &config)),
         Commands::Init(args) => span.in_scope(|| commands::init::cmd_init(args, &styler)),
+        Commands::Patterns(args) => {
+            span.in_scope(|| commands::patterns::cmd_patterns(args, &styler))
+        }
         Commands::Presets(args) => span.in_scope(|| commands::presets::cmd_presets(args, &styler)),
         Commands::Serve(args) => {
             // Serve command runs async and needs different observability setup
@@ -13,7 +13,8 @@ use rmcp::{ServerHandler, tool, tool_handler, tool_router};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, PoisonError};
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 
 use draftkit_core::catalyst::{self, CatalystLanguage};
@@ -36,8 +37,16 @@ pub struct DraftkitServer {
     component_reader: ComponentReader,
     /// Lazily initialized fetcher for on-demand component fetching
     fetcher: Arc<Mutex<Option<ComponentFetcher>>>,
+    /// Stored session and when it was read, see [`Self::stored_session`]
+    session: Arc<std::sync::Mutex<SessionCache>>,
 }
 
+/// The stored session and when it was read from the keyring
+type SessionCache = Option<(Instant, Option<auth::Session>)>;
+
+/// How long a session read from the keyring is reused
+const SESSION_TTL: Duration = Duration::from_secs(60);
+
 // Tool parameter structs
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
@@ -307,14 +316,30 @@ impl DraftkitServer {
                     .to_string(),
                     None,
                 ),
-                FetchError::SessionExpired => McpError::internal_error(
-                    "Session expired. Run `draftkit auth --refresh` to renew.".to_string(),
-                    None,
-                ),
+                FetchError::SessionExpired => {
+                    // A stored session that still looks valid was ended early
+                    let hint = self
+                        .stored_session()
+                        .filter(|session| !session.is_expired())
+                        .and_then(|session| expiry_warning(&session))
+                        .map(|warning| format!("\n\n{warning}"))
+                        .unwrap_or_default();
+                    McpError::internal_error(
+                        format!("Session expired. Run `draftkit auth --refresh` to renew.{hint}"),
+                        None,
+                    )
+                }
                 FetchError::ComponentNotFound(msg) => {
                     McpError::resource_not_found(format!("Component not found: {msg}"), None)
                 }
-                other => McpError::internal_error(format!("Fetch failed: {other}"), None),
+                other => {
+                    let hint = self
+                        .stored_session()
+                        .and_then(|session| expiry_warning(&session))
+                        .map(|warning| format!("\n\n{warning}"))
+                        .unwrap_or_default();
+                    McpError::internal_error(format!("Fetch failed: {other}{hint}"), None)
+                }
             })?;
 
         // Preview URL comes from embedded metadata
@@ -856,6 +881,9 @@ impl DraftkitServer {
 - Version: {}
 - Build date: {}
 
+## TailwindPlus Session
+- {}
+
 ## UI Blocks
 - Total components: {}
 - Frameworks: React, Vue, HTML
@@ -900,6 +928,7 @@ impl DraftkitServer {
 19. **recommend_components** - Get component recommendations for multi-page sites"#,
             env!("CARGO_PKG_VERSION"),
             compile_time_date(),
+            session_status(self.stored_session().as_ref()),
             component_count,
             category_summary.join("\n"),
             catalyst_components.len(),
@@ -1169,7 +1198,7 @@ impl DraftkitServer {
         let page_types: Vec<PageType> = params
             .page_types
             .iter()
-            .map(|s| parse_page_type(s))
+            .map(|s| PageType::parse(s))
             .collect();
 
         // Create component matcher for recommendations
@@ -1180,12 +1209,12 @@ impl DraftkitServer {
 
         for page_type in &page_types {
             // Map page type to section types typically needed
-            let section_types = page_type_to_sections(*page_type);
+            let section_types = page_type.typical_sections();
 
             let mut sections = Vec::new();
             for section_type in section_types {
                 // Get component recommendations for this section
-                let recommendations = component_matcher.match_section(&section_type, "", 3);
+                let recommendations = component_matcher.match_section(section_type, "", 3);
 
                 let components: Vec<serde_json::Value> = recommendations
                     .iter()
@@ -1328,85 +1357,36 @@ impl DraftkitServer {
     }
 }
 
-/// Map a page type to the section types typically needed for that page.
-fn page_type_to_sections(page_type: PageType) -> Vec<String> {
-    match page_type {
-        PageType::Home => vec![
-            "header".to_string(),
-            "hero".to_string(),
-            "feature".to_string(),
-            "testimonial".to_string(),
-            "cta".to_string(),
-            "footer".to_string(),
-        ],
-        PageType::Pricing => vec![
-            "header".to_string(),
-            "pricing".to_string(),
-            "faq".to_string(),
-            "cta".to_string(),
-            "footer".to_string(),
-        ],
-        PageType::Blog => vec![
-            "header".to_string(),
-            "blog".to_string(),
-            "footer".to_string(),
-        ],
-        PageType::Docs => vec![
-            "header".to_string(),
-            "content".to_string(),
-            "footer".to_string(),
-        ],
-        PageType::About => vec![
-            "header".to_string(),
-            "hero".to_string(),
-            "team".to_string(),
-            "footer".to_string(),
-        ],
-        PageType::Contact => vec![
-            "header".to_string(),
-            "contact".to_string(),
-            "footer".to_string(),
-        ],
-        PageType::Portfolio => vec![
-            "header".to_string(),
-            "hero".to_string(),
-            "portfolio".to_string(),
-            "footer".to_string(),
-        ],
-        PageType::Auth => vec!["auth".to_string()],
-        PageType::Dashboard => vec!["sidebar".to_string(), "content".to_string()],
-        _ => vec![
-            "header".to_string(),
-            "content".to_string(),
-            "footer".to_string(),
-        ],
-    }
+fn compile_time_date() -> &'static str {
+    option_env!("BUILD_DATE").unwrap_or(env!("CARGO_PKG_VERSION"))
 }
 
-/// Parse a page type string to PageType enum.
-fn parse_page_type(s: &str) -> PageType {
-    match s.to_lowercase().as_str() {
-        "home" | "landing" | "index" => PageType::Home,
-        "about" | "team" | "company" => PageType::About,
-        "pricing" | "plans" => PageType::Pricing,
-        "blog" | "articles" | "posts" => PageType::Blog,
-        "docs" | "documentation" | "guide" | "guides" => PageType::Docs,
-        "contact" => PageType::Contact,
-        "legal" | "privacy" | "terms" => PageType::Legal,
-        "changelog" | "releases" => PageType::Changelog,
-        "api" | "api-reference" => PageType::ApiReference,
-        "portfolio" | "projects" | "work" => PageType::Portfolio,
-        "auth" | "login" | "signin" | "register" => PageType::Auth,
-        "dashboard" | "app" | "settings" => PageType::Dashboard,
-        "media" | "podcast" | "videos" => PageType::Media,
-        "error" | "404" | "500" => PageType::Error,
-        "resources" | "downloads" => PageType::Resources,
-        _ => PageType::Content,
+/// Warning for a session that has expired or expires within a day.
+fn expiry_warning(session: &auth::Session) -> Option<String> {
+    if session.is_expired() {
+        Some("The TailwindPlus session has expired. Run `draftkit auth --refresh` to renew.".into())
+    } else if session.is_expiring_soon() {
+        Some(format!(
+            "The TailwindPlus session expires at {}. Run `draftkit auth --refresh` to renew it.",
+            session.expires_display().unwrap_or_default()
+        ))
+    } else {
+        None
     }
 }
 
-fn compile_time_date() -> &'static str {
-    option_env!("BUILD_DATE").unwrap_or(env!("CARGO_PKG_VERSION"))
+/// One-line session status for the summary.
+fn session_status(session: Option<&auth::Session>) -> String {
+    let Some(session) = session else {
+        return "Not authenticated (run `draftkit auth` to fetch component code)".into();
+    };
+    match (expiry_warning(session), session.expires_display()) {
+        (Some(warning), _) => format!("Warning: {warning}"),
+        (None, Some(expires)) => format!("Signed in, valid until {expires}"),
+        (None, None) => {
+            "Signed in, expiry unknown (run `draftkit auth --status --verify` to check)".into()
+        }
+    }
 }
 
 #[tool_handler]
@@ -1624,9 +1604,24 @@ impl DraftkitServer {
             tool_router: Self::tool_router(),
             component_reader: ComponentReader::new(),
             fetcher: Arc::new(Mutex::new(None)),
+            session: Arc::default(),
         }
     }
 
+    /// The stored session, read from the keyring at most once per
+    /// [`SESSION_TTL`] so tool calls don't each hit the keyring.
+    fn stored_session(&self) -> Option<auth::Session> {
+        let mut cached = self.session.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((read_at, session)) = cached.as_ref()
+            && read_at.elapsed() < SESSION_TTL
+        {
+            return session.clone();
+        }
+        let session = auth::get_session().ok().flatten();
+        *cached = Some((Instant::now(), session.clone()));
+        session
+    }
+
     /// Get or initialize the component fetcher for on-demand fetching.
     ///
     /// Returns an error if not authenticated or session is expired.
@@ -1638,16 +1633,14 @@ impl DraftkitServer {
         }
 
         // Get session cookie
-        let session = auth::get_session()
-            .map_err(|_| FetchError::NotAuthenticated)?
-            .ok_or(FetchError::NotAuthenticated)?;
+        let session = self.stored_session().ok_or(FetchError::NotAuthenticated)?;
 
         if session.is_expired() {
             return Err(FetchError::SessionExpired);
         }
 
         // Initialize new fetcher
-        let mut fetcher = ComponentFetcher::new(session.cookie);
+        let fetcher = ComponentFetcher::new(session.cookie);
         fetcher.init().await?;
 
         // Cache for future use
@@ -1713,3 +1706,64 @@ impl Default for DraftkitServer {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session_expiring_in(seconds: i64) -> auth::Session {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            .cast_signed();
+        auth::Session {
+            cookie: "cookie".into(),
+            expires_at: Some(now + seconds),
+            domain: "tailwindcss.com".into(),
+        }
+    }
+
+    #[test]
+    fn expired_session_warns_and_shows_in_summary() {
+        let session = session_expiring_in(-60);
+        assert!(expiry_warning(&session).unwrap().contains("has expired"));
+        assert!(
+            session_status(Some(&session))
+                .starts_with("Warning: The TailwindPlus session has expired")
+        );
+    }
+
+    #[test]
+    fn session_expiring_soon_warns_with_its_expiry() {
+        let session = session_expiring_in(60 * 60);
+        let expires = session.expires_display().unwrap();
+        let warning = expiry_warning(&session).unwrap();
+        assert!(warning.contains(&format!("expires at {expires}")));
+        assert_eq!(
+            session_status(Some(&session)),
+            format!("Warning: {warning}")
+        );
+    }
+
+    #[test]
+    fn valid_session_has_no_warning() {
+        let session = session_expiring_in(7 * 24 * 60 * 60);
+        assert!(expiry_warning(&session).is_none());
+        assert_eq!(
+            session_status(Some(&session)),
+            format!(
+                "Signed in, valid until {}",
+                session.expires_display().unwrap()
+            )
+        );
+
+        let unknown = auth::Session {
+            expires_at: None,
+            ..session
+        };
+        assert!(expiry_warning(&unknown).is_none());
+        assert!(session_status(Some(&unknown)).contains("expiry unknown"));
+        assert!(session_status(None).starts_with("Not authenticated"));
+    }
+}
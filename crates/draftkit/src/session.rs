@@ -0,0 +1,562 @@
+//! Storage for the TailwindPlus session.
+//!
+//! The session cookie is a credential, so it is kept in the OS keyring
+//! (macOS Keychain, Windows Credential Manager or the Secret Service on
+//! Linux) when one is available. Otherwise it is written to an encrypted
+//! file in the data directory that only the owner can read.
+//!
+//! The file is encrypted with XChaCha20-Poly1305 under a key derived with
+//! Argon2id from `DRAFTKIT_SESSION_PASSPHRASE` when set, or else from the
+//! machine ID. A machine key only stops the file being used on another
+//! machine, so set a passphrase on shared machines.
+//!
+//! Set `DRAFTKIT_SESSION_STORE=file` to skip the keyring, e.g. on headless
+//! machines where unlocking it would prompt.
+//!
+//! Sessions saved in plaintext by earlier versions (`session.json`) are
+//! moved into the store and deleted the first time they are read.
+//!
+//! Each [profile](draftkit_core::profile) has its own keyring entry and file.
+
+use std::fmt;
+use std::fs;
+
+use anyhow::{Context, Result, bail};
+use argon2::Argon2;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use camino::{Utf8Path, Utf8PathBuf};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+
+use draftkit_core::profile::DEFAULT_PROFILE;
+
+use crate::commands::auth::Session;
+
+/// Keyring service name
+const KEYRING_SERVICE: &str = "draftkit";
+
+/// Keyring account holding the session of the default profile; other
+/// profiles append `:{profile}`
+const KEYRING_USER: &str = "tailwindplus";
+
+/// Encrypted session file name within the data directory
+pub const ENCRYPTED_SESSION_FILE: &str = "session.enc";
+
+/// Environment variable selecting the store (`keyring` or `file`)
+pub const STORE_ENV: &str = "DRAFTKIT_SESSION_STORE";
+
+/// Environment variable with a passphrase for the encrypted file
+pub const PASSPHRASE_ENV: &str = "DRAFTKIT_SESSION_PASSPHRASE";
+
+/// Encrypted file format version
+const FILE_VERSION: u32 = 1;
+
+/// Where a session is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// The OS keyring
+    Keyring,
+    /// An encrypted file in the data directory
+    EncryptedFile,
+    /// The `DRAFTKIT_SESSION` environment variable, which is never written
+    Environment,
+    /// A plaintext file from earlier versions, not yet moved into the store
+    PlaintextFile,
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Keyring => write!(f, "OS keyring"),
+            Self::EncryptedFile => write!(f, "encrypted file"),
+            Self::Environment => write!(f, "DRAFTKIT_SESSION environment variable"),
+            Self::PlaintextFile => write!(f, "plaintext file, moved on next use"),
+        }
+    }
+}
+
+/// A place to keep the session.
+pub trait SessionStore: Send + Sync {
+    /// Which backend this is.
+    fn backend(&self) -> Backend;
+
+    /// Read the stored session, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store is unavailable or its contents can't
+    /// be read.
+    fn load(&self) -> Result<Option<Session>>;
+
+    /// Store `session`, replacing any previous one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store is unavailable or can't be written.
+    fn save(&self, session: &Session) -> Result<()>;
+
+    /// Delete the stored session. Succeeds if there is none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store is unavailable or can't be written.
+    fn remove(&self) -> Result<()>;
+}
+
+/// Session stored as JSON in the OS keyring.
+pub struct KeyringStore {
+    entry: keyring::Entry,
+}
+
+impl KeyringStore {
+    /// Open the keyring entry for the session of `profile`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the platform has no usable keyring.
+    pub fn new(profile: &str) -> Result<Self> {
+        let user = if profile == DEFAULT_PROFILE {
+            KEYRING_USER.to_string()
+        } else {
+            format!("{KEYRING_USER}:{profile}")
+        };
+        let entry =
+            keyring::Entry::new(KEYRING_SERVICE, &user).context("Failed to open keyring entry")?;
+        Ok(Self { entry })
+    }
+}
+
+impl SessionStore for KeyringStore {
+    fn backend(&self) -> Backend {
+        Backend::Keyring
+    }
+
+    fn load(&self) -> Result<Option<Session>> {
+        match self.entry.get_password() {
+            Ok(json) => Ok(Some(
+                serde_json::from_str(&json).context("Failed to parse session from keyring")?,
+            )),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e).context("Failed to read session from keyring"),
+        }
+    }
+
+    fn save(&self, session: &Session) -> Result<()> {
+        let json = serde_json::to_string(session).context("Failed to serialize session")?;
+        self.entry
+            .set_password(&json)
+            .context("Failed to store session in keyring")
+    }
+
+    fn remove(&self) -> Result<()> {
+        match self.entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e).context("Failed to remove session from keyring"),
+        }
+    }
+}
+
+/// What the encryption key was derived from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum KeySource {
+    Machine,
+    Passphrase,
+}
+
+/// On-disk format of the encrypted session file
+#[derive(Debug, Serialize, Deserialize)]
+struct EncryptedSession {
+    version: u32,
+    key: KeySource,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Session encrypted in a file readable only by its owner.
+pub struct EncryptedFileStore {
+    path: Utf8PathBuf,
+    passphrase: Option<String>,
+}
+
+impl EncryptedFileStore {
+    /// Store at `path`, encrypted with `passphrase` or else the machine key.
+    #[must_use]
+    pub const fn new(path: Utf8PathBuf, passphrase: Option<String>) -> Self {
+        Self { path, passphrase }
+    }
+
+    /// Store at `path`, with the passphrase from `DRAFTKIT_SESSION_PASSPHRASE`.
+    #[must_use]
+    pub fn from_env(path: Utf8PathBuf) -> Self {
+        let passphrase = std::env::var(PASSPHRASE_ENV).ok().filter(|p| !p.is_empty());
+        Self::new(path, passphrase)
+    }
+
+    /// Path of the file.
+    #[must_use]
+    pub fn path(&self) -> &Utf8Path {
+        &self.path
+    }
+
+    /// Derive the key for `source` with `salt`.
+    fn key(&self, source: KeySource, salt: &[u8]) -> Result<[u8; 32]> {
+        let secret = match source {
+            KeySource::Passphrase => match &self.passphrase {
+                Some(passphrase) => passphrase.clone(),
+                None => bail!(
+                    "The stored session is protected by a passphrase. Set {PASSPHRASE_ENV} to use it."
+                ),
+            },
+            KeySource::Machine => {
+                let id = machine_uid::get().map_err(|e| {
+                    anyhow::anyhow!(
+                        "Failed to read the machine ID ({e}). Set {PASSPHRASE_ENV} to encrypt the session with a passphrase."
+                    )
+                })?;
+                format!("draftkit-session:{id}")
+            }
+        };
+        let mut key = [0; 32];
+        Argon2::default()
+            .hash_password_into(secret.as_bytes(), salt, &mut key)
+            .map_err(|e| anyhow::anyhow!("Failed to derive session key: {e}"))?;
+        Ok(key)
+    }
+}
+
+impl SessionStore for EncryptedFileStore {
+    fn backend(&self) -> Backend {
+        Backend::EncryptedFile
+    }
+
+    fn load(&self) -> Result<Option<Session>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        restrict_permissions(&self.path)?;
+
+        let json = fs::read_to_string(&self.path).context("Failed to read session file")?;
+        let file: EncryptedSession =
+            serde_json::from_str(&json).context("Failed to parse session file")?;
+        if file.version != FILE_VERSION {
+            bail!(
+                "Unsupported session file version {}. Run `draftkit auth` to log in again.",
+                file.version
+            );
+        }
+        let decode = |field: &str| BASE64.decode(field).context("Corrupt session file");
+        let salt = decode(&file.salt)?;
+        let nonce = decode(&file.nonce)?;
+        if nonce.len() != 24 {
+            bail!("Corrupt session file");
+        }
+
+        let key = self.key(file.key, &salt)?;
+        let plaintext = XChaCha20Poly1305::new(&key.into())
+            .decrypt(
+                XNonce::from_slice(&nonce),
+                decode(&file.ciphertext)?.as_slice(),
+            )
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "Failed to decrypt the stored session. Run `draftkit auth` to log in again."
+                )
+            })?;
+        Ok(Some(
+            serde_json::from_slice(&plaintext).context("Failed to parse session")?,
+        ))
+    }
+
+    fn save(&self, session: &Session) -> Result<()> {
+        let source = if self.passphrase.is_some() {
+            KeySource::Passphrase
+        } else {
+            KeySource::Machine
+        };
+        let mut salt = [0; 16];
+        let mut nonce = [0; 24];
+        getrandom::fill(&mut salt).map_err(|e| anyhow::anyhow!("No randomness: {e}"))?;
+        getrandom::fill(&mut nonce).map_err(|e| anyhow::anyhow!("No randomness: {e}"))?;
+
+        let key = self.key(source, &salt)?;
+        let plaintext = serde_json::to_vec(session).context("Failed to serialize session")?;
+        let ciphertext = XChaCha20Poly1305::new(&key.into())
+            .encrypt(XNonce::from_slice(&nonce), plaintext.as_slice())
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt session"))?;
+
+        let file = EncryptedSession {
+            version: FILE_VERSION,
+            key: source,
+            salt: BASE64.encode(salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        };
+        let json = serde_json::to_string_pretty(&file).context("Failed to serialize session")?;
+        write_private(&self.path, json.as_bytes()).context("Failed to write session file")
+    }
+
+    fn remove(&self) -> Result<()> {
+        if self.path.exists() {
+            fs::remove_file(&self.path).context("Failed to remove session file")?;
+        }
+        Ok(())
+    }
+}
+
+/// The keyring, with the encrypted file as fallback.
+pub struct SessionStorage {
+    keyring: Option<Box<dyn SessionStore>>,
+    file: EncryptedFileStore,
+    legacy: Utf8PathBuf,
+}
+
+impl SessionStorage {
+    /// Storage for the session of `profile`, whose files are in `dir`,
+    /// configured from the environment.
+    #[must_use]
+    pub fn open(profile: &str, dir: &Utf8Path, legacy_file: &str) -> Self {
+        let use_keyring = !std::env::var(STORE_ENV).is_ok_and(|store| store == "file");
+        let keyring =
+            use_keyring
+                .then(|| KeyringStore::new(profile))
+                .and_then(|store| match store {
+                    Ok(store) => Some(Box::new(store) as Box<dyn SessionStore>),
+                    Err(e) => {
+                        tracing::debug!("Keyring unavailable: {e:#}");
+                        None
+                    }
+                });
+        Self::new(
+            keyring,
+            EncryptedFileStore::from_env(dir.join(ENCRYPTED_SESSION_FILE)),
+            dir.join(legacy_file),
+        )
+    }
+
+    /// Storage with explicit stores; `legacy` is a plaintext session to migrate.
+    #[must_use]
+    pub fn new(
+        keyring: Option<Box<dyn SessionStore>>,
+        file: EncryptedFileStore,
+        legacy: Utf8PathBuf,
+    ) -> Self {
+        Self {
+            keyring,
+            file,
+            legacy,
+        }
+    }
+
+    /// Read the session and where it is stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a stored session can't be read or migrated.
+    pub fn load(&self) -> Result<Option<(Session, Backend)>> {
+        self.migrate()?;
+        self.peek()
+    }
+
+    /// Read the session and where it is stored without changing any store,
+    /// so a plaintext session from earlier versions stays where it is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a stored session can't be read.
+    pub fn peek(&self) -> Result<Option<(Session, Backend)>> {
+        if let Some(keyring) = &self.keyring {
+            match keyring.load() {
+                Ok(Some(session)) => return Ok(Some((session, Backend::Keyring))),
+                Ok(None) => {}
+                Err(e) => tracing::debug!("Keyring unavailable: {e:#}"),
+            }
+        }
+        if let Some(session) = self.file.load()? {
+            return Ok(Some((session, Backend::EncryptedFile)));
+        }
+        self.read_legacy()
+            .map(|session| session.map(|session| (session, Backend::PlaintextFile)))
+    }
+
+    /// Store `session` in the keyring, or the encrypted file if the keyring
+    /// is unavailable. Returns where it went.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if neither store can be written.
+    pub fn save(&self, session: &Session) -> Result<Backend> {
+        if let Some(keyring) = &self.keyring {
+            match keyring.save(session) {
+                Ok(()) => {
+                    // Don't leave an older copy behind
+                    self.file.remove()?;
+                    return Ok(Backend::Keyring);
+                }
+                Err(e) => tracing::debug!("Keyring unavailable, using encrypted file: {e:#}"),
+            }
+        }
+        self.file.save(session)?;
+        Ok(Backend::EncryptedFile)
+    }
+
+    /// Delete the session from every store.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a stored session can't be deleted.
+    pub fn remove(&self) -> Result<()> {
+        if let Some(keyring) = &self.keyring
+            && let Err(e) = keyring.remove()
+        {
+            tracing::debug!("Keyring unavailable: {e:#}");
+        }
+        self.file.remove()?;
+        if self.legacy.exists() {
+            fs::remove_file(&self.legacy).context("Failed to remove session file")?;
+        }
+        Ok(())
+    }
+
+    /// Move a plaintext session from earlier versions into the store.
+    fn migrate(&self) -> Result<()> {
+        let Some(session) = self.read_legacy()? else {
+            return Ok(());
+        };
+        let backend = self.save(&session)?;
+        fs::remove_file(&self.legacy).context("Failed to remove plaintext session file")?;
+        tracing::info!("Moved plaintext session to the {backend}");
+        Ok(())
+    }
+
+    /// Read the plaintext session from earlier versions, if there is one.
+    fn read_legacy(&self) -> Result<Option<Session>> {
+        if !self.legacy.exists() {
+            return Ok(None);
+        }
+        let json = fs::read_to_string(&self.legacy).context("Failed to read session file")?;
+        Ok(Some(
+            serde_json::from_str(&json).context("Failed to parse session file")?,
+        ))
+    }
+}
+
+/// Write `contents` to `path`, readable and writable only by the owner.
+fn write_private(path: &Utf8Path, contents: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    std::io::Write::write_all(&mut file, contents)?;
+    restrict_permissions(path)
+}
+
+/// Make `path` private to its owner if it isn't already.
+fn restrict_permissions(path: &Utf8Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = fs::metadata(path)?.permissions().mode();
+        if mode & 0o077 != 0 {
+            tracing::warn!("Session file {path} was readable by others; restricting to owner");
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> Session {
+        Session {
+            cookie: "eyJpdiI6InNlY3JldC1zZXNzaW9uLWNvb2tpZS12YWx1ZSJ9".into(),
+            expires_at: Some(1_900_000_000),
+            domain: "tailwindcss.com".into(),
+        }
+    }
+
+    fn temp_dir() -> (tempfile::TempDir, Utf8PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = Utf8PathBuf::try_from(dir.path().to_path_buf()).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn encrypted_file_roundtrip() {
+        let (_dir, root) = temp_dir();
+        let path = root.join(ENCRYPTED_SESSION_FILE);
+        let store = EncryptedFileStore::new(path.clone(), Some("correct horse".into()));
+
+        store.save(&session()).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains(&session().cookie));
+        assert!(contents.contains("\"passphrase\""));
+        assert_eq!(store.load().unwrap().unwrap().cookie, session().cookie);
+
+        let wrong = EncryptedFileStore::new(path.clone(), Some("battery staple".into()));
+        assert!(wrong.load().is_err());
+        let missing = EncryptedFileStore::new(path, None);
+        let error = missing.load().unwrap_err().to_string();
+        assert!(error.contains(PASSPHRASE_ENV));
+
+        store.remove().unwrap();
+        assert!(store.load().unwrap().is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn encrypted_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (_dir, root) = temp_dir();
+        let path = root.join(ENCRYPTED_SESSION_FILE);
+        let store = EncryptedFileStore::new(path.clone(), Some("passphrase".into()));
+        let mode = || fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+
+        store.save(&session()).unwrap();
+        assert_eq!(mode(), 0o600);
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        store.load().unwrap();
+        assert_eq!(mode(), 0o600);
+    }
+
+    #[test]
+    fn storage_migrates_plaintext_session() {
+        let (_dir, root) = temp_dir();
+        let legacy = root.join("session.json");
+        fs::write(&legacy, serde_json::to_string(&session()).unwrap()).unwrap();
+        let storage = SessionStorage::new(
+            None,
+            EncryptedFileStore::new(root.join(ENCRYPTED_SESSION_FILE), Some("pass".into())),
+            legacy.clone(),
+        );
+
+        let (peeked, backend) = storage.peek().unwrap().unwrap();
+        assert_eq!(peeked.cookie, session().cookie);
+        assert_eq!(backend, Backend::PlaintextFile);
+        assert!(legacy.exists());
+        assert!(!root.join(ENCRYPTED_SESSION_FILE).exists());
+
+        let (loaded, backend) = storage.load().unwrap().unwrap();
+        assert_eq!(loaded.cookie, session().cookie);
+        assert_eq!(backend, Backend::EncryptedFile);
+        assert!(!legacy.exists());
+        assert!(root.join(ENCRYPTED_SESSION_FILE).exists());
+
+        storage.remove().unwrap();
+        assert!(storage.load().unwrap().is_none());
+    }
+}
@@ -195,3 +195,456 @@ fn chdir_nonexistent_fails() {
         .assert()
         .failure();
 }
+
+// =============================================================================
+// Dry Run
+// =============================================================================
+
+#[test]
+fn init_dry_run_json_writes_nothing() {
+    let base = std::env::temp_dir();
+    let name = format!("draftkit-dry-run-{}", std::process::id());
+
+    let output = cmd()
+        .args(["-C", base.to_str().unwrap(), "init", &name])
+        .args(["--dry-run", "--json", "--skip-install"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let files = plan["files"].as_array().unwrap();
+    assert!(files.iter().any(|f| f["path"] == "package.json"));
+    assert!(files.iter().all(|f| f["kind"] == "create"));
+    assert!(!base.join(&name).exists());
+}
+
+#[test]
+fn json_requires_dry_run() {
+    cmd()
+        .args(["init", "site", "--json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--dry-run"));
+}
+
+// =============================================================================
+// Template Packs
+// =============================================================================
+
+#[test]
+fn init_layers_project_template_pack() {
+    let base = std::env::temp_dir().join(format!("draftkit-pack-{}", std::process::id()));
+    let pack = base.join(".draftkit/templates/acme");
+    std::fs::create_dir_all(pack.join("files/src")).unwrap();
+    std::fs::write(
+        pack.join("template.toml"),
+        "[template]\nframework = \"vite-vue\"\n\n[[variables]]\nname = \"brand\"\ndefault = \"indigo\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        pack.join("files/src/tokens.css.tmpl"),
+        "--brand: {{brand}};\n",
+    )
+    .unwrap();
+
+    let output = cmd()
+        .args([
+            "-C",
+            base.to_str().unwrap(),
+            "init",
+            "site",
+            "--template",
+            "acme",
+        ])
+        .args([
+            "--var",
+            "brand=rose",
+            "--dry-run",
+            "--json",
+            "--skip-install",
+        ])
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&base).unwrap();
+    assert!(output.status.success());
+
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let files = plan["files"].as_array().unwrap();
+    assert!(files.iter().any(|f| f["path"] == "src/App.vue"));
+    let tokens = files
+        .iter()
+        .find(|f| f["path"] == "src/tokens.css")
+        .unwrap();
+    assert!(tokens["diff"].as_str().unwrap().contains("+--brand: rose;"));
+}
+
+#[test]
+fn init_rejects_unknown_template_pack() {
+    cmd()
+        .args(["init", "site", "--template", "no-such-pack", "--dry-run"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no-such-pack"));
+}
+
+// =============================================================================
+// Site Generation
+// =============================================================================
+
+#[test]
+fn generate_site_keeps_hand_written_app_without_force() {
+    let base = std::env::temp_dir().join(format!("draftkit-site-{}", std::process::id()));
+    std::fs::create_dir_all(base.join("src")).unwrap();
+    std::fs::write(
+        base.join("package.json"),
+        r#"{"dependencies":{"react":"^19"},"devDependencies":{"vite":"^6","tailwindcss":"^4"}}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        base.join("site.toml"),
+        "[site]\nname = \"Acme\"\n\n[[site.pages]]\nroute = \"/blog/[slug]\"\npage_type = \"blog\"\n",
+    )
+    .unwrap();
+    std::fs::write(base.join("src/App.tsx"), "// hand written\n").unwrap();
+
+    let refused = cmd()
+        .args([
+            "-C",
+            base.to_str().unwrap(),
+            "generate",
+            "--site",
+            "site.toml",
+        ])
+        .output()
+        .unwrap();
+    let kept = std::fs::read_to_string(base.join("src/App.tsx")).unwrap();
+
+    let forced = cmd()
+        .args([
+            "-C",
+            base.to_str().unwrap(),
+            "generate",
+            "--site",
+            "site.toml",
+        ])
+        .args(["--force", "--dry-run", "--json"])
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&base).unwrap();
+
+    assert!(!refused.status.success());
+    assert!(String::from_utf8_lossy(&refused.stderr).contains("--force"));
+    assert_eq!(kept, "// hand written\n");
+
+    assert!(forced.status.success());
+    let plan: serde_json::Value = serde_json::from_slice(&forced.stdout).unwrap();
+    let app = plan["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["path"] == "src/App.tsx")
+        .unwrap();
+    assert!(app["diff"].as_str().unwrap().contains("/blog/:slug"));
+}
+
+// =============================================================================
+// Pattern Generation
+// =============================================================================
+
+/// A Vite React project plus a data directory whose catalog and component
+/// cache hold one dark hero in React and Vue, so generation never touches
+/// the network.
+fn component_project(label: &str) -> std::path::PathBuf {
+    let base = std::env::temp_dir().join(format!("draftkit-{label}-{}", std::process::id()));
+    let project = base.join("project");
+    let data = base.join("data");
+    std::fs::create_dir_all(project.join(".draftkit/patterns")).unwrap();
+    std::fs::create_dir_all(data.join("data/components")).unwrap();
+    std::fs::create_dir_all(data.join("cache/components/u-hero")).unwrap();
+
+    std::fs::write(
+        project.join("package.json"),
+        r#"{"dependencies":{"react":"^19"},"devDependencies":{"vite":"^6","tailwindcss":"^4"}}"#,
+    )
+    .unwrap();
+    std::fs::write(project.join("vite.config.ts"), "").unwrap();
+    std::fs::write(
+        project.join(".draftkit/patterns/solo.toml"),
+        "[pattern]\nid = \"solo\"\nname = \"Solo\"\n\n[[pattern.sections]]\ntype = \"hero\"\nrequired = true\n\n[[pattern.sections.variants]]\nid = \"hero-simple-centered\"\n",
+    )
+    .unwrap();
+    let hero = r#"{"id":"hero-simple-centered","uuid":"u-hero","name":"Simple centered","category":"Marketing","subcategory":"Page Sections","sub_subcategory":"Hero Sections","has_light":true,"has_dark":true}"#;
+    std::fs::write(data.join("data/components/react-v4.ndjson"), hero).unwrap();
+    std::fs::write(data.join("data/components/vue-v4.ndjson"), hero).unwrap();
+    std::fs::write(
+        data.join("cache/components/u-hero/vue-v4-dark.vue"),
+        "<template>\n  <button @click=\"open = true\">Menu</button>\n</template>\n\n<script setup>\nimport { ref } from 'vue'\n\nconst open = ref(false)\n</script>\n",
+    )
+    .unwrap();
+    std::fs::write(
+        data.join("cache/components/u-hero/react-v4-dark.jsx"),
+        "import { ArrowRightIcon } from '@heroicons/react/20/solid'\n\nexport default function Example() {\n  return (\n    <div className=\"bg-gray-900\">\n      <ArrowRightIcon className=\"size-5\" />\n    </div>\n  )\n}\n",
+    )
+    .unwrap();
+    base
+}
+
+/// Run the binary in a [`component_project`] with a session set
+fn component_cmd(base: &std::path::Path) -> Command {
+    let mut command = cmd();
+    command
+        .env("DRAFTKIT_DATA_DIR", base.join("data"))
+        .env("DRAFTKIT_SESSION", "test-session")
+        .args(["-C", base.join("project").to_str().unwrap()]);
+    command
+}
+
+#[test]
+fn generate_locks_components_and_reuses_the_locked_mode() {
+    let base = component_project("lock");
+
+    let first = component_cmd(&base)
+        .args(["generate", "index", "--pattern", "solo", "--mode", "dark"])
+        .output()
+        .unwrap();
+    let lock = std::fs::read_to_string(base.join("project/.draftkit/lock.toml"));
+    let app = std::fs::read_to_string(base.join("project/src/App.tsx"));
+    let hero = std::fs::read_to_string(base.join("project/src/components/sections/Hero.tsx"));
+
+    let again = component_cmd(&base)
+        .args(["generate", "index", "--pattern", "solo"])
+        .args(["--force", "--dry-run"])
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&base).unwrap();
+
+    assert!(
+        first.status.success(),
+        "{}",
+        String::from_utf8_lossy(&first.stderr)
+    );
+    let lock = lock.unwrap();
+    assert!(lock.contains(r#"component = "hero-simple-centered""#));
+    assert!(lock.contains(r#"uuid = "u-hero""#));
+    assert!(lock.contains(r#"mode = "dark""#));
+    assert!(app.unwrap().contains("<Hero />"));
+    let hero = hero.unwrap();
+    assert!(hero.contains("export default function Hero()"));
+    assert!(hero.contains("bg-gray-900"));
+
+    assert!(again.status.success());
+    let stdout = String::from_utf8_lossy(&again.stdout);
+    assert!(stdout.contains("dark"), "{stdout}");
+}
+
+#[test]
+fn generate_vue_writes_each_section_as_its_own_component() {
+    let base = component_project("vue");
+    std::fs::write(
+        base.join("project/package.json"),
+        r#"{"dependencies":{"vue":"^3"},"devDependencies":{"vite":"^6","tailwindcss":"^4"}}"#,
+    )
+    .unwrap();
+
+    let output = component_cmd(&base)
+        .args(["generate", "index", "--pattern", "solo", "--mode", "dark"])
+        .output()
+        .unwrap();
+    let app = std::fs::read_to_string(base.join("project/src/App.vue"));
+    let hero = std::fs::read_to_string(base.join("project/src/components/sections/Hero.vue"));
+    std::fs::remove_dir_all(&base).unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let app = app.unwrap();
+    assert!(app.contains("import Hero from './components/sections/Hero.vue'"));
+    assert!(app.contains("<Hero />"));
+    assert!(!app.contains("const open"));
+    assert!(hero.unwrap().contains("const open = ref(false)"));
+}
+
+#[test]
+fn generate_site_builds_pages_from_fetched_components() {
+    let base = component_project("site-components");
+    std::fs::write(
+        base.join("project/site.toml"),
+        "[site]\nname = \"Acme\"\n\n[[site.pages]]\nroute = \"/\"\npattern = \"solo\"\n",
+    )
+    .unwrap();
+
+    let output = component_cmd(&base)
+        .args(["generate", "--site", "site.toml", "--mode", "dark"])
+        .output()
+        .unwrap();
+    let page = std::fs::read_to_string(base.join("project/src/pages/HomePage.tsx"));
+    let hero = std::fs::read_to_string(base.join("project/src/components/Home/Hero.tsx"));
+    std::fs::remove_dir_all(&base).unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let page = page.unwrap();
+    assert!(
+        page.contains("export default function HomePage()"),
+        "{page}"
+    );
+    assert!(
+        page.contains("import Hero from '../components/Home/Hero'"),
+        "{page}"
+    );
+    assert!(hero.unwrap().contains("bg-gray-900"));
+}
+
+#[test]
+fn generate_site_keeps_edited_pages_without_force() {
+    let base = component_project("site-lock");
+    std::fs::write(
+        base.join("project/site.toml"),
+        "[site]\nname = \"Acme\"\n\n[[site.pages]]\nroute = \"/\"\npattern = \"solo\"\n",
+    )
+    .unwrap();
+    let page_path = base.join("project/src/pages/HomePage.tsx");
+
+    let first = component_cmd(&base)
+        .args(["generate", "--site", "site.toml", "--mode", "dark"])
+        .output()
+        .unwrap();
+    let lock = std::fs::read_to_string(base.join("project/.draftkit/lock.toml"));
+    std::fs::write(&page_path, "// edited\n").unwrap();
+    let refused = component_cmd(&base)
+        .args(["generate", "--site", "site.toml", "--mode", "dark"])
+        .output()
+        .unwrap();
+    let kept = std::fs::read_to_string(&page_path).unwrap();
+    std::fs::remove_dir_all(&base).unwrap();
+
+    assert!(
+        first.status.success(),
+        "{}",
+        String::from_utf8_lossy(&first.stderr)
+    );
+    let lock = lock.unwrap();
+    assert!(lock.contains(r#"[pages."/"]"#), "{lock}");
+    assert!(lock.contains(r#"pattern = "solo""#), "{lock}");
+
+    assert!(!refused.status.success());
+    assert!(String::from_utf8_lossy(&refused.stdout).contains("modified"));
+    assert!(String::from_utf8_lossy(&refused.stderr).contains("--force"));
+    assert_eq!(kept, "// edited\n");
+}
+
+#[test]
+fn generate_rewrites_heroicons_and_lists_unmapped_icons() {
+    let base = component_project("icons");
+    std::fs::write(
+        base.join("data/cache/components/u-hero/react-v4-dark.jsx"),
+        "import { ArrowRightIcon, CubeTransparentIcon } from '@heroicons/react/20/solid'\n\nexport default function Example() {\n  return (\n    <div className=\"bg-gray-900\">\n      <ArrowRightIcon className=\"size-5\" />\n      <CubeTransparentIcon className=\"size-5\" />\n    </div>\n  )\n}\n",
+    )
+    .unwrap();
+
+    let output = component_cmd(&base)
+        .args(["generate", "index", "--pattern", "solo", "--mode", "dark"])
+        .args(["--icons", "lucide"])
+        .output()
+        .unwrap();
+    let hero = std::fs::read_to_string(base.join("project/src/components/sections/Hero.tsx"));
+    let package_json = std::fs::read_to_string(base.join("project/package.json"));
+    std::fs::remove_dir_all(&base).unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let hero = hero.unwrap();
+    assert!(
+        hero.contains("import { ArrowRightIcon } from 'lucide-react'"),
+        "{hero}"
+    );
+    assert!(
+        hero.contains("import { CubeTransparentIcon } from '@heroicons/react/20/solid'"),
+        "{hero}"
+    );
+
+    assert!(package_json.unwrap().contains("\"lucide-react\""));
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("No icon mapping for"), "{stdout}");
+    assert!(stdout.contains("- CubeTransparentIcon"), "{stdout}");
+}
+
+#[test]
+fn generate_refuses_v4_only_components_in_v3_projects() {
+    let base = component_project("v4-only");
+    std::fs::write(
+        base.join("project/package.json"),
+        r#"{"dependencies":{"react":"^19"},"devDependencies":{"vite":"^6","tailwindcss":"^3.4.0"}}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        base.join("data/data/components/react-v4.ndjson"),
+        r#"{"id":"hero-simple-centered","uuid":"u-hero","name":"Simple centered","category":"Marketing","subcategory":"Page Sections","sub_subcategory":"Hero Sections","has_light":true,"has_dark":true,"meta":{"tailwind":{"v4_only":["size-"],"v3_compatible":false}}}"#,
+    )
+    .unwrap();
+    let generate = || {
+        let mut command = component_cmd(&base);
+        command.args(["generate", "index", "--pattern", "solo", "--mode", "dark"]);
+        command
+    };
+
+    let refused = generate().output().unwrap();
+    let written = base.join("project/src/App.tsx").exists();
+    let allowed = generate().arg("--allow-v4-only").output().unwrap();
+    std::fs::remove_dir_all(&base).unwrap();
+
+    assert!(!refused.status.success());
+    assert!(String::from_utf8_lossy(&refused.stderr).contains("--allow-v4-only"));
+    assert!(!written);
+    assert!(
+        allowed.status.success(),
+        "{}",
+        String::from_utf8_lossy(&allowed.stderr)
+    );
+}
+
+#[test]
+fn generate_dry_run_runs_the_same_checks() {
+    let base = component_project("dry-checks");
+    let generate = |extra: &[&str]| {
+        component_cmd(&base)
+            .args([
+                "generate",
+                "index",
+                "--pattern",
+                "solo",
+                "--split",
+                "--mode",
+                "dark",
+            ])
+            .args(extra)
+            .output()
+            .unwrap()
+    };
+
+    let first = generate(&[]);
+    std::fs::write(
+        base.join("project/src/components/sections/Hero.tsx"),
+        "// edited\n",
+    )
+    .unwrap();
+    let edited = generate(&["--dry-run"]);
+    let missing = generate(&["--dry-run", "--force", "--section", "faq"]);
+    std::fs::remove_dir_all(&base).unwrap();
+
+    assert!(first.status.success());
+    assert!(!edited.status.success());
+    assert!(String::from_utf8_lossy(&edited.stderr).contains("--force"));
+    assert!(!missing.status.success());
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("no 'faq' section"));
+}